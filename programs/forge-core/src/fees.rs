@@ -0,0 +1,173 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::{ForgeError, ForgeProtocol};
+
+/// Seed prefix of the per-mint protocol fee vaults ([b"fee_vault", mint])
+pub const FEE_VAULT_SEED: &[u8] = b"fee_vault";
+
+/// Address of the protocol fee vault for `mint` - crucibles send protocol fees here
+pub fn fee_vault_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[FEE_VAULT_SEED, mint.as_ref()], &crate::ID).0
+}
+
+/// `ForgeProtocol.treasury` is either a treasury token account itself or the owner of
+/// per-mint treasury token accounts, so one treasury can receive every fee mint
+fn is_treasury_account(forge_protocol: &ForgeProtocol, treasury: &Account<TokenAccount>) -> bool {
+    treasury.key() == forge_protocol.treasury || treasury.owner == forge_protocol.treasury
+}
+
+/// Create the protocol fee vault for a mint (permissionless - the payer only funds rent)
+pub fn initialize_fee_vault(ctx: Context<InitializeFeeVault>) -> Result<()> {
+    emit!(FeeVaultInitialized {
+        fee_vault: ctx.accounts.fee_vault.key(),
+        mint: ctx.accounts.mint.key(),
+        payer: ctx.accounts.payer.key(),
+    });
+    Ok(())
+}
+
+/// Move the full fee vault balance to the configured treasury (permissionless)
+/// Only until a revenue splitter exists - after that fees leave through `distribute` alone,
+/// so a sweep can't front-run the weighted split.
+pub fn sweep_fees(ctx: Context<SweepFees>) -> Result<()> {
+    require!(
+        ctx.accounts.revenue_splitter.data_is_empty(),
+        ForgeError::RevenueSplitterActive
+    );
+    let amount = ctx.accounts.fee_vault.amount;
+    require!(amount > 0, ForgeError::InsufficientBalance);
+
+    let bump = ctx.accounts.forge_protocol.bump;
+    let seeds = &[b"forge_protocol".as_ref(), &[bump]];
+    let signer = &[&seeds[..]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.fee_vault.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+                authority: ctx.accounts.forge_protocol.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+
+    emit!(FeesSwept {
+        mint: ctx.accounts.fee_vault.mint,
+        fee_vault: ctx.accounts.fee_vault.key(),
+        treasury: ctx.accounts.treasury.key(),
+        amount,
+        caller: ctx.accounts.caller.key(),
+    });
+    Ok(())
+}
+
+/// Rotate the protocol treasury (only authority)
+pub fn update_treasury(ctx: Context<UpdateProtocolTreasury>, new_treasury: Pubkey) -> Result<()> {
+    let forge_protocol = &mut ctx.accounts.forge_protocol;
+    require!(
+        new_treasury != Pubkey::default() && new_treasury != forge_protocol.treasury,
+        ForgeError::InvalidConfig
+    );
+
+    let old_treasury = forge_protocol.treasury;
+    forge_protocol.treasury = new_treasury;
+
+    emit!(ProtocolTreasuryUpdated {
+        old_treasury,
+        new_treasury,
+        authority: ctx.accounts.authority.key(),
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeFeeVault<'info> {
+    #[account(seeds = [b"forge_protocol"], bump = forge_protocol.bump)]
+    pub forge_protocol: Account<'info, ForgeProtocol>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [FEE_VAULT_SEED, mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = forge_protocol,
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct SweepFees<'info> {
+    #[account(seeds = [b"forge_protocol"], bump = forge_protocol.bump)]
+    pub forge_protocol: Account<'info, ForgeProtocol>,
+
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, fee_vault.mint.as_ref()],
+        bump,
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+
+    /// SECURITY FIX: Sweeps can only pay out to the configured treasury
+    #[account(
+        mut,
+        constraint = treasury.mint == fee_vault.mint @ ForgeError::InvalidConfig,
+        constraint = is_treasury_account(&forge_protocol, &treasury) @ ForgeError::InvalidConfig,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    /// CHECK: Revenue splitter PDA - must not exist yet, checked in sweep_fees
+    #[account(seeds = [b"revenue_splitter", forge_protocol.key().as_ref()], bump)]
+    pub revenue_splitter: UncheckedAccount<'info>,
+
+    pub caller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateProtocolTreasury<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_protocol"],
+        bump = forge_protocol.bump,
+        has_one = authority @ ForgeError::Unauthorized,
+    )]
+    pub forge_protocol: Account<'info, ForgeProtocol>,
+    pub authority: Signer<'info>,
+}
+
+#[event]
+pub struct FeeVaultInitialized {
+    pub fee_vault: Pubkey,
+    pub mint: Pubkey,
+    pub payer: Pubkey,
+}
+
+#[event]
+pub struct FeesSwept {
+    pub mint: Pubkey,
+    pub fee_vault: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,
+    pub caller: Pubkey,
+}
+
+#[event]
+pub struct ProtocolTreasuryUpdated {
+    pub old_treasury: Pubkey,
+    pub new_treasury: Pubkey,
+    pub authority: Pubkey,
+}
@@ -1,5 +1,18 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer, MintTo};
+use anchor_lang::Discriminator;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+pub mod fees;
+pub mod multisig;
+pub mod oracle;
+pub mod pause;
+pub mod revenue;
+pub mod timelock;
+
+use fees::*;
+use multisig::*;
+use revenue::*;
+use timelock::*;
 
 declare_id!("9XAEC5TPTzd2UZjJ4DZkTwqzBCyx4VDwHjSp3N7cGWkM");
 
@@ -36,6 +49,7 @@ pub mod forge_core {
         forge_protocol.is_active = true;
         forge_protocol.created_at = clock.unix_timestamp;
         forge_protocol.bump = ctx.bumps.forge_protocol;
+        forge_protocol.pending_authority = None;
 
         msg!("Forge Protocol initialized with {} max crucibles", protocol_config.max_crucibles);
         Ok(())
@@ -116,13 +130,71 @@ pub mod forge_core {
         Ok(())
     }
 
-    /// Collect protocol fees to treasury
+    /// Deactivate a registered crucible (only authority)
+    pub fn deactivate_crucible(ctx: Context<SetCrucibleStatus>) -> Result<()> {
+        let crucible_registry = &mut ctx.accounts.crucible_registry;
+        require!(crucible_registry.is_active, ForgeError::InvalidConfig);
+        crucible_registry.is_active = false;
+
+        emit!(CrucibleDeactivated {
+            crucible: crucible_registry.crucible,
+            crucible_id: crucible_registry.id,
+            authority: ctx.accounts.authority.key(),
+        });
+        Ok(())
+    }
+
+    /// Reactivate a deactivated crucible (only authority)
+    pub fn reactivate_crucible(ctx: Context<SetCrucibleStatus>) -> Result<()> {
+        require!(ctx.accounts.forge_protocol.is_active, ForgeError::ProtocolInactive);
+
+        let crucible_registry = &mut ctx.accounts.crucible_registry;
+        require!(!crucible_registry.is_active, ForgeError::InvalidConfig);
+        crucible_registry.is_active = true;
+
+        emit!(CrucibleReactivated {
+            crucible: crucible_registry.crucible,
+            crucible_id: crucible_registry.id,
+            authority: ctx.accounts.authority.key(),
+        });
+        Ok(())
+    }
+
+    /// Deregister an empty crucible, closing its registry entry and returning rent to the authority
+    pub fn deregister_crucible(ctx: Context<DeregisterCrucible>) -> Result<()> {
+        // SECURITY FIX: Only empty crucibles can leave the registry - live deposits or
+        // positions would otherwise be orphaned from protocol oversight
+        require!(
+            crucible_is_empty(&ctx.accounts.crucible, &ctx.accounts.forge_protocol.crucibles_program)?,
+            ForgeError::CrucibleNotEmpty
+        );
+
+        let forge_protocol = &mut ctx.accounts.forge_protocol;
+        forge_protocol.crucible_count = forge_protocol.crucible_count
+            .checked_sub(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        emit!(CrucibleDeregistered {
+            crucible: ctx.accounts.crucible.key(),
+            crucible_id: ctx.accounts.crucible_registry.id,
+            authority: ctx.accounts.authority.key(),
+            remaining_crucibles: forge_protocol.crucible_count,
+        });
+        Ok(())
+    }
+
+    /// Collect part of a fee vault balance to treasury (only authority, until a revenue splitter exists)
     pub fn collect_fees(
         ctx: Context<CollectFees>,
         amount: u64,
     ) -> Result<()> {
         // SECURITY FIX (AUDIT-004): Validate amount is non-zero and reasonable
         require!(amount > 0, ForgeError::InvalidConfig);
+        // Once a revenue splitter exists, fees leave only through `distribute`
+        require!(
+            ctx.accounts.revenue_splitter.data_is_empty(),
+            ForgeError::RevenueSplitterActive
+        );
         
         // SECURITY FIX (AUDIT-005): Verify fee vault has sufficient balance
         require!(
@@ -137,16 +209,20 @@ pub mod forge_core {
             ForgeError::InvalidConfig
         );
         
+        // Fee vaults are owned by the forge_protocol PDA, which signs the transfer
         let cpi_accounts = Transfer {
             from: ctx.accounts.fee_vault.to_account_info(),
             to: ctx.accounts.treasury.to_account_info(),
-            authority: ctx.accounts.authority.to_account_info(),
+            authority: ctx.accounts.forge_protocol.to_account_info(),
         };
+        let seeds = &[b"forge_protocol".as_ref(), &[ctx.accounts.forge_protocol.bump]];
+        let signer = &[&seeds[..]];
 
         token::transfer(
-            CpiContext::new(
+            CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
                 cpi_accounts,
+                signer,
             ),
             amount,
         )?;
@@ -155,6 +231,47 @@ pub mod forge_core {
         Ok(())
     }
 
+    /// Create the protocol fee vault PDA for a mint (permissionless)
+    pub fn initialize_fee_vault(ctx: Context<InitializeFeeVault>) -> Result<()> {
+        fees::initialize_fee_vault(ctx)
+    }
+
+    /// Sweep a fee vault's full balance to the protocol treasury (permissionless, until a revenue splitter exists)
+    pub fn sweep_fees(ctx: Context<SweepFees>) -> Result<()> {
+        fees::sweep_fees(ctx)
+    }
+
+    /// Rotate the protocol treasury (only authority)
+    pub fn update_treasury(
+        ctx: Context<UpdateProtocolTreasury>,
+        new_treasury: Pubkey,
+    ) -> Result<()> {
+        fees::update_treasury(ctx, new_treasury)
+    }
+
+    /// Create the weighted revenue splitter (only authority)
+    pub fn initialize_revenue_splitter(
+        ctx: Context<InitializeRevenueSplitter>,
+        recipients: Vec<RevenueRecipient>,
+    ) -> Result<()> {
+        revenue::initialize_revenue_splitter(ctx, recipients)
+    }
+
+    /// Replace revenue recipients and weights (only authority)
+    pub fn update_revenue_recipients(
+        ctx: Context<UpdateRevenueRecipients>,
+        recipients: Vec<RevenueRecipient>,
+    ) -> Result<()> {
+        revenue::update_revenue_recipients(ctx, recipients)
+    }
+
+    /// Split a fee vault balance across revenue recipients by weight (permissionless)
+    pub fn distribute<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Distribute<'info>>,
+    ) -> Result<()> {
+        revenue::distribute(ctx)
+    }
+
     /// Pause/Resume protocol (emergency function)
     pub fn set_protocol_status(
         ctx: Context<SetProtocolStatus>,
@@ -180,6 +297,189 @@ pub mod forge_core {
         msg!("Protocol status set to: {}", is_active);
         Ok(())
     }
+
+    /// Propose a new protocol authority - takes effect once the new authority accepts
+    pub fn propose_authority(
+        ctx: Context<ProposeProtocolAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        let forge_protocol = &mut ctx.accounts.forge_protocol;
+        require!(
+            new_authority != Pubkey::default() && new_authority != forge_protocol.authority,
+            ForgeError::InvalidConfig
+        );
+        forge_protocol.pending_authority = Some(new_authority);
+
+        msg!("Protocol authority transfer proposed: {} -> {}", forge_protocol.authority, new_authority);
+        Ok(())
+    }
+
+    /// Accept a pending protocol authority transfer (signed by the proposed authority)
+    pub fn accept_authority(ctx: Context<AcceptProtocolAuthority>) -> Result<()> {
+        let forge_protocol = &mut ctx.accounts.forge_protocol;
+        let pending = forge_protocol
+            .pending_authority
+            .ok_or(ForgeError::NoPendingAuthority)?;
+        require_keys_eq!(
+            pending,
+            ctx.accounts.new_authority.key(),
+            ForgeError::Unauthorized
+        );
+
+        let previous_authority = forge_protocol.authority;
+        forge_protocol.authority = pending;
+        forge_protocol.pending_authority = None;
+
+        msg!("Protocol authority transferred: {} -> {}", previous_authority, pending);
+        Ok(())
+    }
+
+    /// Migrate the protocol account created before pending_authority existed
+    pub fn migrate_protocol(ctx: Context<MigrateProtocol>) -> Result<()> {
+        let protocol_info = ctx.accounts.forge_protocol.to_account_info();
+        require_keys_eq!(*protocol_info.owner, crate::ID, ForgeError::InvalidConfig);
+
+        let data = protocol_info.try_borrow_data()?;
+        require!(
+            data.len() == ForgeProtocolLegacy::LEN,
+            ForgeError::InvalidConfig
+        );
+        let discriminator: [u8; 8] = data[..8]
+            .try_into()
+            .map_err(|_| ForgeError::InvalidConfig)?;
+        require!(
+            discriminator == ForgeProtocol::DISCRIMINATOR,
+            ForgeError::InvalidConfig
+        );
+        let legacy = ForgeProtocolLegacy::deserialize(&mut &data[8..])?;
+        drop(data);
+
+        require_keys_eq!(
+            legacy.authority,
+            ctx.accounts.authority.key(),
+            ForgeError::Unauthorized
+        );
+
+        let migrated = ForgeProtocol {
+            authority: legacy.authority,
+            treasury: legacy.treasury,
+            crucibles_program: legacy.crucibles_program,
+            protocol_fee_rate: legacy.protocol_fee_rate,
+            max_crucibles: legacy.max_crucibles,
+            crucible_count: legacy.crucible_count,
+            is_active: legacy.is_active,
+            created_at: legacy.created_at,
+            bump: legacy.bump,
+            pending_authority: None,
+        };
+
+        // Grow the account and top up rent for the new size
+        let required_lamports = Rent::get()?.minimum_balance(ForgeProtocol::LEN);
+        let top_up = required_lamports.saturating_sub(protocol_info.lamports());
+        if top_up > 0 {
+            let cpi_accounts = anchor_lang::system_program::Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: protocol_info.clone(),
+            };
+            let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+            anchor_lang::system_program::transfer(cpi_ctx, top_up)?;
+        }
+        protocol_info.realloc(ForgeProtocol::LEN, false)?;
+
+        let mut data = protocol_info.try_borrow_mut_data()?;
+        let mut slice = &mut data[8..]; // Skip discriminator
+        migrated.serialize(&mut slice)?;
+
+        msg!("Migrated Forge Protocol account {}", protocol_info.key());
+        Ok(())
+    }
+
+    /// Create the M-of-N admin multisig (current protocol authority only)
+    pub fn create_multisig(
+        ctx: Context<CreateMultisig>,
+        owners: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        multisig::create_multisig(ctx, owners, threshold)
+    }
+
+    /// Replace multisig owners and threshold (multisig signer only, via a proposal)
+    pub fn set_multisig_owners(
+        ctx: Context<SetMultisigOwners>,
+        owners: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        multisig::set_multisig_owners(ctx, owners, threshold)
+    }
+
+    /// Propose an instruction to be signed by the multisig
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        program_id: Pubkey,
+        accounts: Vec<ProposalAccount>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        multisig::create_proposal(ctx, program_id, accounts, data)
+    }
+
+    /// Approve a multisig proposal
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        multisig::approve_proposal(ctx)
+    }
+
+    /// Execute a multisig proposal that reached its threshold
+    pub fn execute_proposal<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteProposal<'info>>,
+    ) -> Result<()> {
+        multisig::execute_proposal(ctx)
+    }
+
+    /// Create the governance timelock and hand it the protocol authority (current protocol authority only)
+    pub fn create_timelock(
+        ctx: Context<CreateTimelock>,
+        admin: Pubkey,
+        guardian: Pubkey,
+        delay: i64,
+    ) -> Result<()> {
+        timelock::create_timelock(ctx, admin, guardian, delay)
+    }
+
+    /// Update timelock admin, guardian and delay (timelock signer only, via a queued operation)
+    pub fn update_timelock(
+        ctx: Context<UpdateTimelock>,
+        admin: Pubkey,
+        guardian: Pubkey,
+        delay: i64,
+    ) -> Result<()> {
+        timelock::update_timelock(ctx, admin, guardian, delay)
+    }
+
+    /// Queue an admin instruction behind the timelock delay (timelock admin only)
+    pub fn queue_operation(
+        ctx: Context<QueueOperation>,
+        program_id: Pubkey,
+        accounts: Vec<ProposalAccount>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        timelock::queue_operation(ctx, program_id, accounts, data)
+    }
+
+    /// Cancel a queued operation (guardian only)
+    pub fn cancel_operation(ctx: Context<CancelOperation>) -> Result<()> {
+        timelock::cancel_operation(ctx)
+    }
+
+    /// Pause the protocol immediately without queueing (timelock guardian only)
+    pub fn guardian_pause_protocol(ctx: Context<GuardianPauseProtocol>) -> Result<()> {
+        timelock::guardian_pause_protocol(ctx)
+    }
+
+    /// Execute a queued operation after its delay (permissionless)
+    pub fn execute_operation<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteOperation<'info>>,
+    ) -> Result<()> {
+        timelock::execute_operation(ctx)
+    }
 }
 
 #[derive(Accounts)]
@@ -187,7 +487,7 @@ pub struct InitializeProtocol<'info> {
     #[account(
         init,
         payer = authority,
-        space = 8 + 32 * 3 + 8 * 3 + 1 + 8 + 1,
+        space = ForgeProtocol::LEN,
         seeds = [b"forge_protocol"],
         bump
     )]
@@ -238,16 +538,60 @@ pub struct RegisterCrucible<'info> {
 }
 
 #[derive(Accounts)]
-pub struct CollectFees<'info> {
+pub struct SetCrucibleStatus<'info> {
+    #[account(has_one = authority)]
+    pub forge_protocol: Account<'info, ForgeProtocol>,
+    #[account(
+        mut,
+        seeds = [b"crucible_registry", crucible_registry.crucible.as_ref()],
+        bump = crucible_registry.bump
+    )]
+    pub crucible_registry: Account<'info, CrucibleRegistry>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DeregisterCrucible<'info> {
     #[account(mut, has_one = authority)]
     pub forge_protocol: Account<'info, ForgeProtocol>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"crucible_registry", crucible.key().as_ref()],
+        bump = crucible_registry.bump,
+        constraint = crucible_registry.crucible == crucible.key() @ ForgeError::InvalidConfig
+    )]
+    pub crucible_registry: Account<'info, CrucibleRegistry>,
+    /// CHECK: Registered crucible - ownership and emptiness validated in handler
+    pub crucible: UncheckedAccount<'info>,
     #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CollectFees<'info> {
+    #[account(
+        seeds = [b"forge_protocol"],
+        bump = forge_protocol.bump,
+        has_one = authority,
+    )]
+    pub forge_protocol: Account<'info, ForgeProtocol>,
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, fee_vault.mint.as_ref()],
+        bump,
+    )]
     pub fee_vault: Account<'info, TokenAccount>,
     #[account(
         mut,
-        constraint = treasury.key() == forge_protocol.treasury @ ForgeError::InvalidConfig
+        constraint = treasury.mint == fee_vault.mint @ ForgeError::InvalidConfig,
+        constraint = treasury.key() == forge_protocol.treasury
+            || treasury.owner == forge_protocol.treasury @ ForgeError::InvalidConfig
     )]
     pub treasury: Account<'info, TokenAccount>,
+    /// CHECK: Revenue splitter PDA - must not exist yet, checked in collect_fees
+    #[account(seeds = [b"revenue_splitter", forge_protocol.key().as_ref()], bump)]
+    pub revenue_splitter: UncheckedAccount<'info>,
     pub authority: Signer<'info>,
     pub token_program: Program<'info, Token>,
 }
@@ -259,6 +603,39 @@ pub struct SetProtocolStatus<'info> {
     pub authority: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct ProposeProtocolAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_protocol"],
+        bump = forge_protocol.bump,
+        has_one = authority @ ForgeError::Unauthorized,
+    )]
+    pub forge_protocol: Account<'info, ForgeProtocol>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptProtocolAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_protocol"],
+        bump = forge_protocol.bump,
+    )]
+    pub forge_protocol: Account<'info, ForgeProtocol>,
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateProtocol<'info> {
+    /// CHECK: Legacy protocol account - owner, size and discriminator validated in handler
+    #[account(mut, seeds = [b"forge_protocol"], bump)]
+    pub forge_protocol: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
 #[account]
 pub struct ForgeProtocol {
     pub authority: Pubkey,
@@ -270,6 +647,76 @@ pub struct ForgeProtocol {
     pub is_active: bool,
     pub created_at: i64,
     pub bump: u8,
+    pub pending_authority: Option<Pubkey>, // Two-step authority transfer
+}
+
+impl ForgeProtocol {
+    pub const LEN: usize = 8 + // discriminator
+        32 * 3 + // authority, treasury, crucibles_program
+        8 * 3 +  // protocol_fee_rate, max_crucibles, crucible_count
+        1 +      // is_active
+        8 +      // created_at
+        1 +      // bump
+        1 + 32;  // pending_authority
+}
+
+/// Protocol account layout before pending_authority was added
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ForgeProtocolLegacy {
+    pub authority: Pubkey,
+    pub treasury: Pubkey,
+    pub crucibles_program: Pubkey,
+    pub protocol_fee_rate: u64,
+    pub max_crucibles: u64,
+    pub crucible_count: u64,
+    pub is_active: bool,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl ForgeProtocolLegacy {
+    pub const LEN: usize = 8 + 32 * 3 + 8 * 3 + 1 + 8 + 1;
+}
+
+/// Load protocol configuration for other Forge programs
+/// Validates that `forge_protocol` is the ForgeProtocol PDA. Reads the legacy field
+/// prefix, so it works before and after `migrate_protocol`.
+pub fn load_protocol(forge_protocol: &AccountInfo) -> Result<ForgeProtocolLegacy> {
+    require_keys_eq!(*forge_protocol.owner, crate::ID, ForgeError::InvalidConfig);
+
+    let data = forge_protocol.try_borrow_data()?;
+    require!(data.len() >= ForgeProtocolLegacy::LEN, ForgeError::InvalidConfig);
+    require!(
+        data[..8] == ForgeProtocol::DISCRIMINATOR,
+        ForgeError::InvalidConfig
+    );
+    let protocol = ForgeProtocolLegacy::deserialize(&mut &data[8..])?;
+
+    let expected = Pubkey::create_program_address(&[b"forge_protocol", &[protocol.bump]], &crate::ID)
+        .map_err(|_| ForgeError::InvalidConfig)?;
+    require_keys_eq!(forge_protocol.key(), expected, ForgeError::InvalidConfig);
+
+    Ok(protocol)
+}
+
+/// Protocol-wide kill switch for other Forge programs
+pub fn protocol_is_active(forge_protocol: &AccountInfo) -> Result<bool> {
+    Ok(load_protocol(forge_protocol)?.is_active)
+}
+
+/// Refuse state changes while the Forge protocol kill switch is off
+pub fn require_protocol_active(forge_protocol: &AccountInfo) -> Result<()> {
+    require!(protocol_is_active(forge_protocol)?, ForgeError::ProtocolInactive);
+    Ok(())
+}
+
+/// `require_protocol_active` for exits and liquidations, which take the protocol account
+/// optionally so a client that omits it can still unwind
+pub fn require_protocol_active_if_present(forge_protocol: Option<&AccountInfo>) -> Result<()> {
+    match forge_protocol {
+        Some(forge_protocol) => require_protocol_active(forge_protocol),
+        None => Ok(()),
+    }
 }
 
 #[account]
@@ -282,6 +729,44 @@ pub struct CrucibleRegistry {
     pub bump: u8,
 }
 
+/// Leading fields of a forge-crucibles `Crucible` account, used to check it is empty
+/// (forge-crucibles depends on forge-core, so the type cannot be imported here)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CrucibleSupplySnapshot {
+    pub base_mint: Pubkey,
+    pub ctoken_mint: Pubkey,
+    pub lp_token_mint: Pubkey,
+    pub vault: Pubkey,
+    pub vault_bump: u8,
+    pub bump: u8,
+    pub total_base_deposited: u64,
+    pub total_ctoken_supply: u64,
+    pub total_lp_token_supply: u64,
+    pub exchange_rate: u64,
+    pub last_update_slot: u64,
+    pub fee_rate: u64,
+    pub pause_flags: u8,
+    pub total_leveraged_positions: u64,
+    pub total_lp_positions: u64,
+}
+
+/// A crucible is empty once it holds no deposits, supply or open positions.
+/// A crucible account that has already been closed counts as empty.
+fn crucible_is_empty(crucible: &AccountInfo, crucibles_program: &Pubkey) -> Result<bool> {
+    if crucible.data_is_empty() {
+        return Ok(true);
+    }
+    require_keys_eq!(*crucible.owner, *crucibles_program, ForgeError::InvalidConfig);
+    let data = crucible.try_borrow_data()?;
+    require!(data.len() > 8, ForgeError::InvalidConfig);
+    let snapshot = CrucibleSupplySnapshot::deserialize(&mut &data[8..])?;
+    Ok(snapshot.total_base_deposited == 0
+        && snapshot.total_ctoken_supply == 0
+        && snapshot.total_lp_token_supply == 0
+        && snapshot.total_leveraged_positions == 0
+        && snapshot.total_lp_positions == 0)
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct ProtocolConfig {
     pub crucibles_program: Pubkey,
@@ -289,6 +774,28 @@ pub struct ProtocolConfig {
     pub max_crucibles: u64,
 }
 
+#[event]
+pub struct CrucibleDeactivated {
+    pub crucible: Pubkey,
+    pub crucible_id: u64,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct CrucibleReactivated {
+    pub crucible: Pubkey,
+    pub crucible_id: u64,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct CrucibleDeregistered {
+    pub crucible: Pubkey,
+    pub crucible_id: u64,
+    pub authority: Pubkey,
+    pub remaining_crucibles: u64,
+}
+
 #[error_code]
 pub enum ForgeError {
     #[msg("Maximum number of crucibles reached")]
@@ -301,4 +808,32 @@ pub enum ForgeError {
     InvalidConfig,
     #[msg("Insufficient balance")]
     InsufficientBalance,
+    #[msg("No pending authority transfer")]
+    NoPendingAuthority,
+    #[msg("Invalid multisig owners or threshold")]
+    InvalidMultisigOwners,
+    #[msg("Signer is not a multisig owner")]
+    NotMultisigOwner,
+    #[msg("Proposal already approved by this owner")]
+    AlreadyApproved,
+    #[msg("Proposal already executed")]
+    ProposalAlreadyExecuted,
+    #[msg("Proposal does not have enough approvals")]
+    NotEnoughApprovals,
+    #[msg("Multisig owners changed since the proposal was created")]
+    StaleProposal,
+    #[msg("Crucible still has deposits or open positions")]
+    CrucibleNotEmpty,
+    #[msg("Invalid revenue recipients or weights")]
+    InvalidRevenueRecipients,
+    #[msg("Timelock delay out of bounds")]
+    InvalidTimelockDelay,
+    #[msg("Timelock operation is not executable yet")]
+    TimelockNotReady,
+    #[msg("Timelock operation was cancelled")]
+    OperationCancelled,
+    #[msg("Account is not a Pyth price update")]
+    InvalidPriceUpdate,
+    #[msg("Fees are distributed by the revenue splitter")]
+    RevenueSplitterActive,
 }
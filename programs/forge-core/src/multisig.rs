@@ -0,0 +1,339 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+
+use crate::{ForgeError, ForgeProtocol};
+
+/// Maximum number of multisig owners (space is allocated for the maximum up front)
+pub const MAX_MULTISIG_OWNERS: usize = 10;
+
+/// M-of-N protocol admin multisig
+/// Its signer PDA ([b"multisig_signer", multisig]) can be made the protocol authority,
+/// after which admin instructions are executed through approved proposals.
+#[account]
+pub struct Multisig {
+    pub forge_protocol: Pubkey,
+    pub owners: Vec<Pubkey>,
+    pub threshold: u8, // Approvals required to execute a proposal
+    pub owner_set_seqno: u32, // Bumped on owner changes to invalidate pending proposals
+    pub proposal_count: u64,
+    pub signer_bump: u8,
+    pub bump: u8,
+}
+
+impl Multisig {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // forge_protocol
+        4 + 32 * MAX_MULTISIG_OWNERS + // owners
+        1 +  // threshold
+        4 +  // owner_set_seqno
+        8 +  // proposal_count
+        1 +  // signer_bump
+        1;   // bump
+
+    fn owner_index(&self, key: &Pubkey) -> Option<usize> {
+        self.owners.iter().position(|owner| owner == key)
+    }
+}
+
+/// A proposed instruction to be signed by the multisig signer PDA
+#[account]
+pub struct MultisigProposal {
+    pub multisig: Pubkey,
+    pub proposer: Pubkey,
+    pub program_id: Pubkey,
+    pub accounts: Vec<ProposalAccount>,
+    pub data: Vec<u8>,
+    pub approvals: Vec<bool>, // One flag per multisig owner, in owner order
+    pub owner_set_seqno: u32,
+    pub executed: bool,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl MultisigProposal {
+    pub fn space(num_accounts: usize, data_len: usize) -> usize {
+        8 +  // discriminator
+        32 + // multisig
+        32 + // proposer
+        32 + // program_id
+        4 + num_accounts * ProposalAccount::LEN + // accounts
+        4 + data_len + // data
+        4 + MAX_MULTISIG_OWNERS + // approvals
+        4 +  // owner_set_seqno
+        1 +  // executed
+        8 +  // created_at
+        1    // bump
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ProposalAccount {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl ProposalAccount {
+    pub const LEN: usize = 32 + 1 + 1;
+}
+
+impl From<&ProposalAccount> for AccountMeta {
+    fn from(account: &ProposalAccount) -> AccountMeta {
+        if account.is_writable {
+            AccountMeta::new(account.pubkey, account.is_signer)
+        } else {
+            AccountMeta::new_readonly(account.pubkey, account.is_signer)
+        }
+    }
+}
+
+fn validate_owners(owners: &[Pubkey], threshold: u8) -> Result<()> {
+    require!(
+        !owners.is_empty() && owners.len() <= MAX_MULTISIG_OWNERS,
+        ForgeError::InvalidMultisigOwners
+    );
+    for (i, owner) in owners.iter().enumerate() {
+        require!(*owner != Pubkey::default(), ForgeError::InvalidMultisigOwners);
+        // SECURITY FIX: Duplicate owners would let one key count twice toward the threshold
+        require!(!owners[..i].contains(owner), ForgeError::InvalidMultisigOwners);
+    }
+    require!(
+        threshold > 0 && threshold as usize <= owners.len(),
+        ForgeError::InvalidMultisigOwners
+    );
+    Ok(())
+}
+
+/// Create the protocol multisig (current protocol authority only)
+pub fn create_multisig(
+    ctx: Context<CreateMultisig>,
+    owners: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    validate_owners(&owners, threshold)?;
+
+    let multisig = &mut ctx.accounts.multisig;
+    multisig.forge_protocol = ctx.accounts.forge_protocol.key();
+    multisig.owners = owners;
+    multisig.threshold = threshold;
+    multisig.owner_set_seqno = 0;
+    multisig.proposal_count = 0;
+    multisig.signer_bump = ctx.bumps.multisig_signer;
+    multisig.bump = ctx.bumps.multisig;
+
+    msg!(
+        "Multisig created: {} of {} owners, signer {}",
+        threshold,
+        multisig.owners.len(),
+        ctx.accounts.multisig_signer.key()
+    );
+    Ok(())
+}
+
+/// Replace the owner set and threshold - only callable by the multisig itself via a proposal
+pub fn set_multisig_owners(
+    ctx: Context<SetMultisigOwners>,
+    owners: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    validate_owners(&owners, threshold)?;
+
+    let multisig = &mut ctx.accounts.multisig;
+    multisig.owners = owners;
+    multisig.threshold = threshold;
+    multisig.owner_set_seqno = multisig.owner_set_seqno
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    msg!("Multisig owners updated: {} of {}", threshold, multisig.owners.len());
+    Ok(())
+}
+
+/// Propose an instruction for the multisig to sign - the proposer approves it
+pub fn create_proposal(
+    ctx: Context<CreateProposal>,
+    program_id: Pubkey,
+    accounts: Vec<ProposalAccount>,
+    data: Vec<u8>,
+) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+    let proposer_index = multisig
+        .owner_index(&ctx.accounts.proposer.key())
+        .ok_or(ForgeError::NotMultisigOwner)?;
+
+    let mut approvals = vec![false; multisig.owners.len()];
+    approvals[proposer_index] = true;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.multisig = multisig.key();
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.program_id = program_id;
+    proposal.accounts = accounts;
+    proposal.data = data;
+    proposal.approvals = approvals;
+    proposal.owner_set_seqno = multisig.owner_set_seqno;
+    proposal.executed = false;
+    proposal.created_at = Clock::get()?.unix_timestamp;
+    proposal.bump = ctx.bumps.proposal;
+
+    msg!("Multisig proposal {} created", multisig.proposal_count);
+    multisig.proposal_count = multisig.proposal_count
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    Ok(())
+}
+
+/// Approve a pending proposal (multisig owners only)
+pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+    let multisig = &ctx.accounts.multisig;
+    let proposal = &mut ctx.accounts.proposal;
+
+    require!(!proposal.executed, ForgeError::ProposalAlreadyExecuted);
+    // SECURITY FIX: Approvals index into the owner set the proposal was created with
+    require!(
+        proposal.owner_set_seqno == multisig.owner_set_seqno,
+        ForgeError::StaleProposal
+    );
+    let owner_index = multisig
+        .owner_index(&ctx.accounts.owner.key())
+        .ok_or(ForgeError::NotMultisigOwner)?;
+    require!(!proposal.approvals[owner_index], ForgeError::AlreadyApproved);
+    proposal.approvals[owner_index] = true;
+
+    msg!("Multisig proposal {} approved by {}", proposal.key(), ctx.accounts.owner.key());
+    Ok(())
+}
+
+/// Execute a proposal once it has enough approvals (permissionless)
+/// Remaining accounts: every account in the proposal, plus the target program
+pub fn execute_proposal<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteProposal<'info>>) -> Result<()> {
+    let multisig = &ctx.accounts.multisig;
+    let proposal = &mut ctx.accounts.proposal;
+
+    require!(!proposal.executed, ForgeError::ProposalAlreadyExecuted);
+    require!(
+        proposal.owner_set_seqno == multisig.owner_set_seqno,
+        ForgeError::StaleProposal
+    );
+    let approvals = proposal.approvals.iter().filter(|approved| **approved).count();
+    require!(
+        approvals >= multisig.threshold as usize,
+        ForgeError::NotEnoughApprovals
+    );
+
+    // SECURITY FIX: Mark executed before the CPI so the proposal cannot be re-entered
+    proposal.executed = true;
+
+    let instruction = Instruction {
+        program_id: proposal.program_id,
+        accounts: proposal.accounts.iter().map(AccountMeta::from).collect(),
+        data: proposal.data.clone(),
+    };
+    let multisig_key = multisig.key();
+    let seeds = &[b"multisig_signer", multisig_key.as_ref(), &[multisig.signer_bump]];
+    let signer = &[&seeds[..]];
+    invoke_signed(&instruction, ctx.remaining_accounts, signer)?;
+
+    msg!("Multisig proposal {} executed", proposal.key());
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateMultisig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"forge_protocol"],
+        bump = forge_protocol.bump,
+        has_one = authority @ ForgeError::Unauthorized,
+    )]
+    pub forge_protocol: Account<'info, ForgeProtocol>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Multisig::LEN,
+        seeds = [b"multisig", forge_protocol.key().as_ref()],
+        bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    /// CHECK: Data-less signer PDA for the multisig - only derived here
+    #[account(seeds = [b"multisig_signer", multisig.key().as_ref()], bump)]
+    pub multisig_signer: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetMultisigOwners<'info> {
+    #[account(
+        mut,
+        seeds = [b"multisig", multisig.forge_protocol.as_ref()],
+        bump = multisig.bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        seeds = [b"multisig_signer", multisig.key().as_ref()],
+        bump = multisig.signer_bump,
+    )]
+    pub multisig_signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(program_id: Pubkey, accounts: Vec<ProposalAccount>, data: Vec<u8>)]
+pub struct CreateProposal<'info> {
+    #[account(
+        mut,
+        seeds = [b"multisig", multisig.forge_protocol.as_ref()],
+        bump = multisig.bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = MultisigProposal::space(accounts.len(), data.len()),
+        seeds = [b"proposal", multisig.key().as_ref(), &multisig.proposal_count.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, MultisigProposal>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    #[account(
+        seeds = [b"multisig", multisig.forge_protocol.as_ref()],
+        bump = multisig.bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(mut, has_one = multisig @ ForgeError::InvalidConfig)]
+    pub proposal: Account<'info, MultisigProposal>,
+
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(
+        seeds = [b"multisig", multisig.forge_protocol.as_ref()],
+        bump = multisig.bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(mut, has_one = multisig @ ForgeError::InvalidConfig)]
+    pub proposal: Account<'info, MultisigProposal>,
+
+    /// CHECK: Multisig signer PDA - signs the proposed instruction
+    #[account(seeds = [b"multisig_signer", multisig.key().as_ref()], bump = multisig.signer_bump)]
+    pub multisig_signer: UncheckedAccount<'info>,
+}
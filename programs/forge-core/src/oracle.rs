@@ -0,0 +1,153 @@
+// Pyth receiver `PriceUpdateV2` accounts, read by hand for the other Forge programs.
+// pyth-solana-receiver-sdk is built on a newer anchor-lang than this workspace, so its
+// account type can't be used with `Account<>` or `AccountDeserialize` here. Accounts are
+// validated by owner and discriminator and decoded from their borsh layout instead.
+
+use anchor_lang::prelude::*;
+
+use crate::ForgeError;
+
+/// Pyth receiver program - owner of every `PriceUpdateV2` account
+/// Program ID: rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = Pubkey::new_from_array([
+    12, 183, 250, 187, 82, 247, 166, 72, 187, 91, 49, 125, 154, 1, 139, 144,
+    87, 203, 2, 71, 116, 250, 254, 1, 230, 196, 223, 152, 204, 56, 88, 129
+]);
+
+/// Anchor discriminator of `PriceUpdateV2` (sha256("account:PriceUpdateV2")[..8])
+pub const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+// VerificationLevel borsh variant tags
+const VERIFICATION_PARTIAL: u8 = 0;
+const VERIFICATION_FULL: u8 = 1;
+
+/// Price fields of a `PriceUpdateV2` account
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PriceUpdate {
+    pub fully_verified: bool,
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+impl PriceUpdate {
+    /// Same checks as the SDK's `get_price_no_older_than`: full verification, matching feed, fresh
+    pub fn price_no_older_than(&self, now: i64, maximum_age: u64, feed_id: &[u8; 32]) -> Option<&Self> {
+        let max_age = i64::try_from(maximum_age).ok()?;
+        let fresh = self.publish_time.saturating_add(max_age) >= now;
+        (self.fully_verified && self.feed_id == *feed_id && fresh).then_some(self)
+    }
+}
+
+/// Decode `PriceUpdateV2` account data (discriminator included)
+pub fn parse_price_update(data: &[u8]) -> Option<PriceUpdate> {
+    if data.get(..8)? != PRICE_UPDATE_V2_DISCRIMINATOR {
+        return None;
+    }
+    // Skip discriminator and write_authority
+    let mut rest = data.get(8 + 32..)?;
+    let fully_verified = match *rest.first()? {
+        VERIFICATION_PARTIAL => {
+            rest = rest.get(2..)?; // tag + num_signatures
+            false
+        }
+        VERIFICATION_FULL => {
+            rest = rest.get(1..)?;
+            true
+        }
+        _ => return None,
+    };
+
+    let feed_id: [u8; 32] = rest.get(..32)?.try_into().ok()?;
+    let price = i64::from_le_bytes(rest.get(32..40)?.try_into().ok()?);
+    let conf = u64::from_le_bytes(rest.get(40..48)?.try_into().ok()?);
+    let exponent = i32::from_le_bytes(rest.get(48..52)?.try_into().ok()?);
+    let publish_time = i64::from_le_bytes(rest.get(52..60)?.try_into().ok()?);
+    Some(PriceUpdate {
+        fully_verified,
+        feed_id,
+        price,
+        conf,
+        exponent,
+        publish_time,
+    })
+}
+
+/// Require `account` to be a Pyth receiver `PriceUpdateV2` account
+pub fn validate_price_update(account: &AccountInfo) -> Result<()> {
+    require_keys_eq!(*account.owner, PYTH_RECEIVER_PROGRAM_ID, ForgeError::InvalidPriceUpdate);
+    let data = account.try_borrow_data()?;
+    require!(
+        data.len() >= 8 && data[..8] == PRICE_UPDATE_V2_DISCRIMINATOR,
+        ForgeError::InvalidPriceUpdate
+    );
+    Ok(())
+}
+
+/// Validate and decode a Pyth receiver `PriceUpdateV2` account
+pub fn load_price_update(account: &AccountInfo) -> Result<PriceUpdate> {
+    validate_price_update(account)?;
+    let data = account.try_borrow_data()?;
+    parse_price_update(&data).ok_or_else(|| ForgeError::InvalidPriceUpdate.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(verification: &[u8], feed_id: [u8; 32], price: i64, exponent: i32, publish_time: i64) -> Vec<u8> {
+        let mut data = PRICE_UPDATE_V2_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[7u8; 32]); // write_authority
+        data.extend_from_slice(verification);
+        data.extend_from_slice(&feed_id);
+        data.extend_from_slice(&price.to_le_bytes());
+        data.extend_from_slice(&25u64.to_le_bytes()); // conf
+        data.extend_from_slice(&exponent.to_le_bytes());
+        data.extend_from_slice(&publish_time.to_le_bytes());
+        data.extend_from_slice(&(publish_time - 1).to_le_bytes()); // prev_publish_time
+        data.extend_from_slice(&price.to_le_bytes()); // ema_price
+        data.extend_from_slice(&25u64.to_le_bytes()); // ema_conf
+        data.extend_from_slice(&42u64.to_le_bytes()); // posted_slot
+        data
+    }
+
+    #[test]
+    fn parses_fully_verified_update() {
+        let data = encode(&[VERIFICATION_FULL], [3u8; 32], 15_000_000_000, -8, 1_700_000_000);
+        let update = parse_price_update(&data).unwrap();
+        assert!(update.fully_verified);
+        assert_eq!(update.feed_id, [3u8; 32]);
+        assert_eq!(update.price, 15_000_000_000);
+        assert_eq!(update.conf, 25);
+        assert_eq!(update.exponent, -8);
+        assert_eq!(update.publish_time, 1_700_000_000);
+    }
+
+    #[test]
+    fn parses_partial_update_and_rejects_it_for_pricing() {
+        let data = encode(&[VERIFICATION_PARTIAL, 5], [3u8; 32], 100, -2, 1_000);
+        let update = parse_price_update(&data).unwrap();
+        assert!(!update.fully_verified);
+        assert_eq!(update.price, 100);
+        assert!(update.price_no_older_than(1_000, 60, &[3u8; 32]).is_none());
+    }
+
+    #[test]
+    fn rejects_wrong_discriminator_and_truncated_data() {
+        let mut data = encode(&[VERIFICATION_FULL], [3u8; 32], 100, -2, 1_000);
+        assert!(parse_price_update(&data[..data.len() - 40]).is_none());
+        data[0] ^= 1;
+        assert!(parse_price_update(&data).is_none());
+    }
+
+    #[test]
+    fn price_no_older_than_checks_feed_and_age() {
+        let data = encode(&[VERIFICATION_FULL], [3u8; 32], 100, -2, 1_000);
+        let update = parse_price_update(&data).unwrap();
+        assert!(update.price_no_older_than(1_060, 60, &[3u8; 32]).is_some());
+        assert!(update.price_no_older_than(1_061, 60, &[3u8; 32]).is_none());
+        assert!(update.price_no_older_than(1_000, 60, &[4u8; 32]).is_none());
+    }
+}
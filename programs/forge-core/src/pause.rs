@@ -0,0 +1,77 @@
+// Per-operation pause flags shared by crucibles, Inferno crucibles and the lending pool.
+// The flags occupy the byte that used to hold `paused: bool`, so account layouts are
+// unchanged. That byte is stored encoded (see `encode_pause_flags`) so an account paused
+// under the old layout (0x01) still reads as `PAUSE_ALL`.
+
+pub const PAUSE_DEPOSITS: u8 = 1 << 0; // mint_ctoken, deposit_usdc
+pub const PAUSE_WITHDRAWALS: u8 = 1 << 1; // burn_ctoken, withdraw_usdc
+pub const PAUSE_LP_OPEN: u8 = 1 << 2;
+pub const PAUSE_LP_CLOSE: u8 = 1 << 3;
+pub const PAUSE_LEVERAGE_OPEN: u8 = 1 << 4; // Leveraged opens, borrow_usdc
+pub const PAUSE_LEVERAGE_CLOSE: u8 = 1 << 5; // Leveraged closes, repay_usdc
+pub const PAUSE_LIQUIDATIONS: u8 = 1 << 6;
+pub const PAUSE_ARBITRAGE_DEPOSITS: u8 = 1 << 7;
+
+pub const PAUSE_NONE: u8 = 0;
+pub const PAUSE_ALL: u8 = u8::MAX;
+
+// Legacy `paused: bool = true`
+const LEGACY_PAUSED: u8 = 1;
+
+/// Withdraw-only wind-down: no new exposure, while exits and liquidations keep working
+pub const PAUSE_WIND_DOWN: u8 =
+    PAUSE_DEPOSITS | PAUSE_LP_OPEN | PAUSE_LEVERAGE_OPEN | PAUSE_ARBITRAGE_DEPOSITS;
+
+/// Flags held by a stored pause byte
+/// The legacy paused value 0x01 decodes to `PAUSE_ALL`; `PAUSE_DEPOSITS` alone is stored as 0xFF instead.
+pub fn decode_pause_flags(stored: u8) -> u8 {
+    match stored {
+        LEGACY_PAUSED => PAUSE_ALL,
+        PAUSE_ALL => PAUSE_DEPOSITS,
+        flags => flags,
+    }
+}
+
+/// Byte to store for `flags` - inverse of `decode_pause_flags`
+pub fn encode_pause_flags(flags: u8) -> u8 {
+    // The swap is its own inverse
+    decode_pause_flags(flags)
+}
+
+/// Whether `operation` (one or more PAUSE_* flags) is paused by the stored pause byte
+pub fn is_paused(stored: u8, operation: u8) -> bool {
+    decode_pause_flags(stored) & operation != 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_paused_byte_pauses_everything() {
+        assert_eq!(decode_pause_flags(1), PAUSE_ALL);
+        assert!(is_paused(1, PAUSE_WITHDRAWALS));
+        assert!(is_paused(1, PAUSE_LIQUIDATIONS));
+        assert!(!is_paused(0, PAUSE_ALL));
+    }
+
+    #[test]
+    fn every_flag_set_round_trips() {
+        for flags in 0..=u8::MAX {
+            assert_eq!(decode_pause_flags(encode_pause_flags(flags)), flags);
+        }
+        assert_eq!(encode_pause_flags(PAUSE_NONE), 0);
+        assert_eq!(encode_pause_flags(PAUSE_ALL), 1);
+    }
+
+    #[test]
+    fn deposits_only_pause_stays_deposits_only() {
+        let stored = encode_pause_flags(PAUSE_DEPOSITS);
+        assert!(is_paused(stored, PAUSE_DEPOSITS));
+        assert!(!is_paused(stored, PAUSE_WITHDRAWALS));
+
+        let stored = encode_pause_flags(PAUSE_WIND_DOWN);
+        assert!(is_paused(stored, PAUSE_LEVERAGE_OPEN));
+        assert!(!is_paused(stored, PAUSE_LEVERAGE_CLOSE | PAUSE_LIQUIDATIONS));
+    }
+}
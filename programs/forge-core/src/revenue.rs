@@ -0,0 +1,277 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::fees::FEE_VAULT_SEED;
+use crate::{ForgeError, ForgeProtocol};
+
+/// Maximum number of revenue recipients (space is allocated for the maximum up front)
+pub const MAX_REVENUE_RECIPIENTS: usize = 8;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RevenueRecipientKind {
+    InsuranceFund,
+    Team,
+    Stakers,
+    Buyback,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RevenueRecipient {
+    pub kind: RevenueRecipientKind,
+    pub owner: Pubkey, // Token account, or owner of per-mint token accounts
+    pub weight_bps: u16,
+}
+
+impl RevenueRecipient {
+    pub const LEN: usize = 1 + 32 + 2;
+}
+
+/// Weighted split of protocol fee vault balances ([b"revenue_splitter", forge_protocol])
+#[account]
+pub struct RevenueSplitter {
+    pub forge_protocol: Pubkey,
+    pub recipients: Vec<RevenueRecipient>, // Weights sum to 10,000 bps
+    pub total_distributed: u64, // Raw token units across all mints, for monitoring
+    pub bump: u8,
+}
+
+impl RevenueSplitter {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // forge_protocol
+        4 + RevenueRecipient::LEN * MAX_REVENUE_RECIPIENTS + // recipients
+        8 +  // total_distributed
+        1;   // bump
+}
+
+fn validate_recipients(recipients: &[RevenueRecipient]) -> Result<()> {
+    require!(
+        !recipients.is_empty() && recipients.len() <= MAX_REVENUE_RECIPIENTS,
+        ForgeError::InvalidRevenueRecipients
+    );
+    let mut total_weight: u64 = 0;
+    for (i, recipient) in recipients.iter().enumerate() {
+        require!(
+            recipient.owner != Pubkey::default() && recipient.weight_bps > 0,
+            ForgeError::InvalidRevenueRecipients
+        );
+        // SECURITY FIX: A duplicate owner would silently receive two shares
+        require!(
+            !recipients[..i].iter().any(|r| r.owner == recipient.owner),
+            ForgeError::InvalidRevenueRecipients
+        );
+        total_weight += recipient.weight_bps as u64;
+    }
+    require!(total_weight == 10_000, ForgeError::InvalidRevenueRecipients);
+    Ok(())
+}
+
+/// Create the revenue splitter (only authority)
+pub fn initialize_revenue_splitter(
+    ctx: Context<InitializeRevenueSplitter>,
+    recipients: Vec<RevenueRecipient>,
+) -> Result<()> {
+    validate_recipients(&recipients)?;
+
+    let splitter = &mut ctx.accounts.revenue_splitter;
+    splitter.forge_protocol = ctx.accounts.forge_protocol.key();
+    splitter.recipients = recipients;
+    splitter.total_distributed = 0;
+    splitter.bump = ctx.bumps.revenue_splitter;
+
+    emit!(RevenueRecipientsUpdated {
+        revenue_splitter: splitter.key(),
+        recipients: splitter.recipients.len() as u8,
+        authority: ctx.accounts.authority.key(),
+    });
+    Ok(())
+}
+
+/// Replace the revenue recipients and weights (only authority)
+pub fn update_revenue_recipients(
+    ctx: Context<UpdateRevenueRecipients>,
+    recipients: Vec<RevenueRecipient>,
+) -> Result<()> {
+    validate_recipients(&recipients)?;
+
+    let splitter = &mut ctx.accounts.revenue_splitter;
+    splitter.recipients = recipients;
+
+    emit!(RevenueRecipientsUpdated {
+        revenue_splitter: splitter.key(),
+        recipients: splitter.recipients.len() as u8,
+        authority: ctx.accounts.authority.key(),
+    });
+    Ok(())
+}
+
+/// Split a fee vault's full balance across the recipients by weight (permissionless)
+/// Remaining accounts: one token account for the vault mint per recipient, in recipient order
+pub fn distribute<'info>(ctx: Context<'_, '_, 'info, 'info, Distribute<'info>>) -> Result<()> {
+    let amount = ctx.accounts.fee_vault.amount;
+    require!(amount > 0, ForgeError::InsufficientBalance);
+
+    let recipients = ctx.accounts.revenue_splitter.recipients.clone();
+    require!(
+        ctx.remaining_accounts.len() == recipients.len(),
+        ForgeError::InvalidRevenueRecipients
+    );
+
+    let mint = ctx.accounts.fee_vault.mint;
+    let bump = ctx.accounts.forge_protocol.bump;
+    let seeds = &[b"forge_protocol".as_ref(), &[bump]];
+    let signer = &[&seeds[..]];
+
+    let mut distributed: u64 = 0;
+    for (i, (recipient, account_info)) in recipients.iter().zip(ctx.remaining_accounts).enumerate() {
+        let destination = Account::<TokenAccount>::try_from(account_info)?;
+        // SECURITY FIX: Each payout must land in the configured recipient's account for this mint
+        require_keys_eq!(destination.mint, mint, ForgeError::InvalidRevenueRecipients);
+        require!(
+            destination.key() == recipient.owner || destination.owner == recipient.owner,
+            ForgeError::InvalidRevenueRecipients
+        );
+
+        // The last recipient takes the rounding dust so the vault is fully drained
+        let payout = if i + 1 == recipients.len() {
+            amount
+                .checked_sub(distributed)
+                .ok_or(ProgramError::ArithmeticOverflow)?
+        } else {
+            ((amount as u128)
+                .checked_mul(recipient.weight_bps as u128)
+                .ok_or(ProgramError::ArithmeticOverflow)?
+                / 10_000u128) as u64
+        };
+        if payout == 0 {
+            continue;
+        }
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.fee_vault.to_account_info(),
+                    to: account_info.clone(),
+                    authority: ctx.accounts.forge_protocol.to_account_info(),
+                },
+                signer,
+            ),
+            payout,
+        )?;
+        distributed = distributed
+            .checked_add(payout)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        emit!(RevenuePaid {
+            mint,
+            kind: recipient.kind,
+            recipient: recipient.owner,
+            destination: destination.key(),
+            weight_bps: recipient.weight_bps,
+            amount: payout,
+        });
+    }
+
+    let splitter = &mut ctx.accounts.revenue_splitter;
+    splitter.total_distributed = splitter.total_distributed.saturating_add(distributed);
+
+    emit!(RevenueDistributed {
+        mint,
+        fee_vault: ctx.accounts.fee_vault.key(),
+        amount: distributed,
+        caller: ctx.accounts.caller.key(),
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeRevenueSplitter<'info> {
+    #[account(
+        seeds = [b"forge_protocol"],
+        bump = forge_protocol.bump,
+        has_one = authority @ ForgeError::Unauthorized,
+    )]
+    pub forge_protocol: Account<'info, ForgeProtocol>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = RevenueSplitter::LEN,
+        seeds = [b"revenue_splitter", forge_protocol.key().as_ref()],
+        bump
+    )]
+    pub revenue_splitter: Account<'info, RevenueSplitter>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRevenueRecipients<'info> {
+    #[account(
+        seeds = [b"forge_protocol"],
+        bump = forge_protocol.bump,
+        has_one = authority @ ForgeError::Unauthorized,
+    )]
+    pub forge_protocol: Account<'info, ForgeProtocol>,
+
+    #[account(
+        mut,
+        seeds = [b"revenue_splitter", forge_protocol.key().as_ref()],
+        bump = revenue_splitter.bump,
+    )]
+    pub revenue_splitter: Account<'info, RevenueSplitter>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Distribute<'info> {
+    #[account(seeds = [b"forge_protocol"], bump = forge_protocol.bump)]
+    pub forge_protocol: Account<'info, ForgeProtocol>,
+
+    #[account(
+        mut,
+        seeds = [b"revenue_splitter", forge_protocol.key().as_ref()],
+        bump = revenue_splitter.bump,
+    )]
+    pub revenue_splitter: Account<'info, RevenueSplitter>,
+
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, fee_vault.mint.as_ref()],
+        bump,
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+
+    pub caller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[event]
+pub struct RevenueRecipientsUpdated {
+    pub revenue_splitter: Pubkey,
+    pub recipients: u8,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct RevenuePaid {
+    pub mint: Pubkey,
+    pub kind: RevenueRecipientKind,
+    pub recipient: Pubkey,
+    pub destination: Pubkey,
+    pub weight_bps: u16,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RevenueDistributed {
+    pub mint: Pubkey,
+    pub fee_vault: Pubkey,
+    pub amount: u64,
+    pub caller: Pubkey,
+}
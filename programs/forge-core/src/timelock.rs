@@ -0,0 +1,398 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+
+use crate::multisig::ProposalAccount;
+use crate::{ForgeError, ForgeProtocol};
+
+/// Bounds on the timelock delay (1 hour - 30 days)
+pub const MIN_TIMELOCK_DELAY: i64 = 60 * 60;
+pub const MAX_TIMELOCK_DELAY: i64 = 30 * 24 * 60 * 60;
+
+/// Governance timelock for admin parameter changes
+/// Its signer PDA ([b"timelock_signer", timelock]) becomes the protocol authority when the
+/// timelock is created (and can be handed the authority of other Forge admin accounts, e.g.
+/// Inferno crucibles and lending pools), after which every admin instruction is queued here
+/// and can only execute once `delay` has elapsed.
+#[account]
+pub struct Timelock {
+    pub forge_protocol: Pubkey,
+    pub admin: Pubkey,    // Queues operations (e.g. the multisig signer)
+    pub guardian: Pubkey, // Cancels queued operations and can pause the protocol immediately
+    pub delay: i64,       // Seconds between queueing and execution
+    pub operation_count: u64,
+    pub signer_bump: u8,
+    pub bump: u8,
+}
+
+impl Timelock {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // forge_protocol
+        32 + // admin
+        32 + // guardian
+        8 +  // delay
+        8 +  // operation_count
+        1 +  // signer_bump
+        1;   // bump
+}
+
+/// A queued instruction to be signed by the timelock signer PDA
+#[account]
+pub struct TimelockOperation {
+    pub timelock: Pubkey,
+    pub program_id: Pubkey,
+    pub accounts: Vec<ProposalAccount>,
+    pub data: Vec<u8>,
+    pub queued_at: i64,
+    pub eta: i64, // Earliest execution time
+    pub executed: bool,
+    pub cancelled: bool,
+    pub bump: u8,
+}
+
+impl TimelockOperation {
+    pub fn space(num_accounts: usize, data_len: usize) -> usize {
+        8 +  // discriminator
+        32 + // timelock
+        32 + // program_id
+        4 + num_accounts * ProposalAccount::LEN + // accounts
+        4 + data_len + // data
+        8 +  // queued_at
+        8 +  // eta
+        1 +  // executed
+        1 +  // cancelled
+        1    // bump
+    }
+}
+
+fn validate_delay(delay: i64) -> Result<()> {
+    require!(
+        (MIN_TIMELOCK_DELAY..=MAX_TIMELOCK_DELAY).contains(&delay),
+        ForgeError::InvalidTimelockDelay
+    );
+    Ok(())
+}
+
+/// Create the governance timelock and hand it the protocol authority (current protocol authority only)
+pub fn create_timelock(
+    ctx: Context<CreateTimelock>,
+    admin: Pubkey,
+    guardian: Pubkey,
+    delay: i64,
+) -> Result<()> {
+    validate_delay(delay)?;
+    require!(
+        admin != Pubkey::default() && guardian != Pubkey::default(),
+        ForgeError::InvalidConfig
+    );
+
+    let timelock = &mut ctx.accounts.timelock;
+    timelock.forge_protocol = ctx.accounts.forge_protocol.key();
+    timelock.admin = admin;
+    timelock.guardian = guardian;
+    timelock.delay = delay;
+    timelock.operation_count = 0;
+    timelock.signer_bump = ctx.bumps.timelock_signer;
+    timelock.bump = ctx.bumps.timelock;
+
+    // SECURITY FIX: The timelock signer takes over the protocol authority, so parameter and
+    // authority changes can no longer bypass the queue
+    let forge_protocol = &mut ctx.accounts.forge_protocol;
+    forge_protocol.authority = ctx.accounts.timelock_signer.key();
+    forge_protocol.pending_authority = None;
+
+    msg!(
+        "Timelock created: {}s delay, signer {}",
+        delay,
+        ctx.accounts.timelock_signer.key()
+    );
+    Ok(())
+}
+
+/// Update the delay, admin and guardian - only callable by the timelock itself,
+/// so weakening the timelock is subject to the current delay
+pub fn update_timelock(
+    ctx: Context<UpdateTimelock>,
+    admin: Pubkey,
+    guardian: Pubkey,
+    delay: i64,
+) -> Result<()> {
+    validate_delay(delay)?;
+    require!(
+        admin != Pubkey::default() && guardian != Pubkey::default(),
+        ForgeError::InvalidConfig
+    );
+
+    let timelock = &mut ctx.accounts.timelock;
+    timelock.admin = admin;
+    timelock.guardian = guardian;
+    timelock.delay = delay;
+
+    emit!(TimelockUpdated {
+        timelock: timelock.key(),
+        admin,
+        guardian,
+        delay,
+    });
+    Ok(())
+}
+
+/// Queue an instruction - executable once the timelock delay has elapsed (admin only)
+pub fn queue_operation(
+    ctx: Context<QueueOperation>,
+    program_id: Pubkey,
+    accounts: Vec<ProposalAccount>,
+    data: Vec<u8>,
+) -> Result<()> {
+    let timelock = &mut ctx.accounts.timelock;
+    let now = Clock::get()?.unix_timestamp;
+    let eta = now
+        .checked_add(timelock.delay)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let operation = &mut ctx.accounts.operation;
+    operation.timelock = timelock.key();
+    operation.program_id = program_id;
+    operation.accounts = accounts;
+    operation.data = data;
+    operation.queued_at = now;
+    operation.eta = eta;
+    operation.executed = false;
+    operation.cancelled = false;
+    operation.bump = ctx.bumps.operation;
+
+    emit!(OperationQueued {
+        timelock: timelock.key(),
+        operation: operation.key(),
+        id: timelock.operation_count,
+        program_id,
+        eta,
+    });
+    timelock.operation_count = timelock.operation_count
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    Ok(())
+}
+
+/// Cancel a queued operation (guardian only)
+pub fn cancel_operation(ctx: Context<CancelOperation>) -> Result<()> {
+    let timelock = &ctx.accounts.timelock;
+
+    let operation = &mut ctx.accounts.operation;
+    require!(!operation.executed, ForgeError::ProposalAlreadyExecuted);
+    require!(!operation.cancelled, ForgeError::OperationCancelled);
+    operation.cancelled = true;
+
+    emit!(OperationCancelled {
+        timelock: timelock.key(),
+        operation: operation.key(),
+        guardian: timelock.guardian,
+    });
+    Ok(())
+}
+
+/// Pause the protocol immediately, bypassing the queue (guardian only)
+/// Resuming stays a queued `set_protocol_status` call by the timelock signer.
+pub fn guardian_pause_protocol(ctx: Context<GuardianPauseProtocol>) -> Result<()> {
+    let forge_protocol = &mut ctx.accounts.forge_protocol;
+    require!(forge_protocol.is_active, ForgeError::InvalidConfig);
+    forge_protocol.is_active = false;
+
+    emit!(ProtocolPausedByGuardian {
+        timelock: ctx.accounts.timelock.key(),
+        guardian: ctx.accounts.guardian.key(),
+    });
+    Ok(())
+}
+
+/// Execute a queued operation once its eta has passed (permissionless)
+/// Remaining accounts: every account in the operation, plus the target program
+pub fn execute_operation<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteOperation<'info>>) -> Result<()> {
+    let timelock = &ctx.accounts.timelock;
+    let operation = &mut ctx.accounts.operation;
+
+    require!(!operation.executed, ForgeError::ProposalAlreadyExecuted);
+    require!(!operation.cancelled, ForgeError::OperationCancelled);
+    require!(
+        Clock::get()?.unix_timestamp >= operation.eta,
+        ForgeError::TimelockNotReady
+    );
+
+    // SECURITY FIX: Mark executed before the CPI so the operation cannot be re-entered
+    operation.executed = true;
+
+    let instruction = Instruction {
+        program_id: operation.program_id,
+        accounts: operation.accounts.iter().map(AccountMeta::from).collect(),
+        data: operation.data.clone(),
+    };
+    let timelock_key = timelock.key();
+    let seeds = &[b"timelock_signer", timelock_key.as_ref(), &[timelock.signer_bump]];
+    let signer = &[&seeds[..]];
+    invoke_signed(&instruction, ctx.remaining_accounts, signer)?;
+
+    emit!(OperationExecuted {
+        timelock: timelock_key,
+        operation: operation.key(),
+        program_id: operation.program_id,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateTimelock<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"forge_protocol"],
+        bump = forge_protocol.bump,
+        has_one = authority @ ForgeError::Unauthorized,
+    )]
+    pub forge_protocol: Account<'info, ForgeProtocol>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Timelock::LEN,
+        seeds = [b"timelock", forge_protocol.key().as_ref()],
+        bump
+    )]
+    pub timelock: Account<'info, Timelock>,
+
+    /// CHECK: Data-less signer PDA for the timelock - only derived here
+    #[account(seeds = [b"timelock_signer", timelock.key().as_ref()], bump)]
+    pub timelock_signer: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTimelock<'info> {
+    #[account(
+        mut,
+        seeds = [b"timelock", timelock.forge_protocol.as_ref()],
+        bump = timelock.bump,
+    )]
+    pub timelock: Account<'info, Timelock>,
+
+    #[account(
+        seeds = [b"timelock_signer", timelock.key().as_ref()],
+        bump = timelock.signer_bump,
+    )]
+    pub timelock_signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(program_id: Pubkey, accounts: Vec<ProposalAccount>, data: Vec<u8>)]
+pub struct QueueOperation<'info> {
+    #[account(
+        mut,
+        seeds = [b"timelock", timelock.forge_protocol.as_ref()],
+        bump = timelock.bump,
+        has_one = admin @ ForgeError::Unauthorized,
+    )]
+    pub timelock: Account<'info, Timelock>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = TimelockOperation::space(accounts.len(), data.len()),
+        seeds = [b"timelock_op", timelock.key().as_ref(), &timelock.operation_count.to_le_bytes()],
+        bump
+    )]
+    pub operation: Account<'info, TimelockOperation>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelOperation<'info> {
+    #[account(
+        seeds = [b"timelock", timelock.forge_protocol.as_ref()],
+        bump = timelock.bump,
+        has_one = guardian @ ForgeError::Unauthorized,
+    )]
+    pub timelock: Account<'info, Timelock>,
+
+    #[account(mut, has_one = timelock @ ForgeError::InvalidConfig)]
+    pub operation: Account<'info, TimelockOperation>,
+
+    pub guardian: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GuardianPauseProtocol<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_protocol"],
+        bump = forge_protocol.bump,
+    )]
+    pub forge_protocol: Account<'info, ForgeProtocol>,
+
+    #[account(
+        seeds = [b"timelock", forge_protocol.key().as_ref()],
+        bump = timelock.bump,
+        has_one = guardian @ ForgeError::Unauthorized,
+    )]
+    pub timelock: Account<'info, Timelock>,
+
+    pub guardian: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteOperation<'info> {
+    #[account(
+        seeds = [b"timelock", timelock.forge_protocol.as_ref()],
+        bump = timelock.bump,
+    )]
+    pub timelock: Account<'info, Timelock>,
+
+    #[account(mut, has_one = timelock @ ForgeError::InvalidConfig)]
+    pub operation: Account<'info, TimelockOperation>,
+
+    /// CHECK: Timelock signer PDA - signs the queued instruction
+    #[account(seeds = [b"timelock_signer", timelock.key().as_ref()], bump = timelock.signer_bump)]
+    pub timelock_signer: UncheckedAccount<'info>,
+}
+
+#[event]
+pub struct TimelockUpdated {
+    pub timelock: Pubkey,
+    pub admin: Pubkey,
+    pub guardian: Pubkey,
+    pub delay: i64,
+}
+
+#[event]
+pub struct OperationQueued {
+    pub timelock: Pubkey,
+    pub operation: Pubkey,
+    pub id: u64,
+    pub program_id: Pubkey,
+    pub eta: i64,
+}
+
+#[event]
+pub struct OperationCancelled {
+    pub timelock: Pubkey,
+    pub operation: Pubkey,
+    pub guardian: Pubkey,
+}
+
+#[event]
+pub struct OperationExecuted {
+    pub timelock: Pubkey,
+    pub operation: Pubkey,
+    pub program_id: Pubkey,
+}
+
+#[event]
+pub struct ProtocolPausedByGuardian {
+    pub timelock: Pubkey,
+    pub guardian: Pubkey,
+}
@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token};
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_lang::Discriminator;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use forge_core::pause::{decode_pause_flags, encode_pause_flags, PAUSE_NONE};
 
 pub mod lp;
 pub mod metadata;
@@ -156,7 +159,7 @@ pub mod forge_crucibles_inferno {
         crucible.exchange_rate = 1_000_000;
         crucible.last_update_slot = clock.slot;
         crucible.fee_rate = fee_rate;
-        crucible.paused = false;
+        crucible.pause_flags = PAUSE_NONE;
         crucible.expected_vault_balance = 0;
         crucible.expected_usdc_vault_balance = 0;
         let oracle_key = ctx.accounts.oracle.key();
@@ -164,6 +167,9 @@ pub mod forge_crucibles_inferno {
         crucible.treasury_base = ctx.accounts.treasury_base.key();
         crucible.treasury_usdc = ctx.accounts.treasury_usdc.key();
         crucible.total_fees_accrued = 0;
+        crucible.authority = ctx.accounts.authority.key();
+        crucible.pending_authority = None;
+        crucible.liquidation_bonus_bps = DEFAULT_LIQUIDATION_BONUS_BPS;
 
         emit!(InfernoCrucibleInitialized {
             crucible: crucible.key(),
@@ -216,12 +222,22 @@ pub mod forge_crucibles_inferno {
         lp::health_check_inferno(ctx)
     }
 
+    /// Liquidate an unhealthy Inferno LP position - callable by any keeper
+    /// The keeper repays the borrowed USDC and seizes collateral plus the crucible's liquidation bonus
     pub fn liquidate_inferno_lp_position(
-        ctx: Context<CloseInfernoLPPosition>,
-        max_slippage_bps: u64,
+        ctx: Context<LiquidateInfernoLPPosition>,
+        position_nonce: u64,
+    ) -> Result<()> {
+        lp::liquidate_inferno_lp_position(ctx, position_nonce)
+    }
+
+    /// Move the LP tokens of a position opened before LP escrow into its escrow (only position owner)
+    /// Such positions can't be liquidated until they are escrowed.
+    pub fn escrow_legacy_lp_tokens(
+        ctx: Context<EscrowLegacyLPTokens>,
         position_nonce: u64,
     ) -> Result<()> {
-        lp::liquidate_inferno_lp_position(ctx, max_slippage_bps, position_nonce)
+        lp::escrow_legacy_lp_tokens(ctx, position_nonce)
     }
 
     /// Close a legacy Inferno LP position (positions created before nonce was added)
@@ -243,20 +259,157 @@ pub mod forge_crucibles_inferno {
         metadata::create_lp_metadata(ctx, name, symbol, uri, seller_fee_basis_points, is_mutable)
     }
 
+    /// Migrate an Inferno crucible created before the authority field existed
+    /// Only the program upgrade authority can migrate, and becomes the crucible authority
+    pub fn migrate_inferno_crucible(ctx: Context<MigrateInfernoCrucible>) -> Result<()> {
+        let crucible_info = ctx.accounts.crucible.to_account_info();
+        require_keys_eq!(*crucible_info.owner, crate::ID, InfernoCrucibleError::InvalidConfig);
+
+        let data = crucible_info.try_borrow_data()?;
+        require!(
+            data.len() == InfernoCrucibleLegacy::LEN,
+            InfernoCrucibleError::InvalidConfig
+        );
+        let discriminator: [u8; 8] = data[..8]
+            .try_into()
+            .map_err(|_| InfernoCrucibleError::InvalidConfig)?;
+        require!(
+            discriminator == InfernoCrucible::DISCRIMINATOR,
+            InfernoCrucibleError::InvalidConfig
+        );
+        let legacy = InfernoCrucibleLegacy::deserialize(&mut &data[8..])?;
+        drop(data);
+
+        require_keys_eq!(
+            legacy.base_mint,
+            ctx.accounts.base_mint.key(),
+            InfernoCrucibleError::InvalidBaseMint
+        );
+
+        let migrated = InfernoCrucible {
+            base_mint: legacy.base_mint,
+            lp_token_mint: legacy.lp_token_mint,
+            vault: legacy.vault,
+            usdc_vault: legacy.usdc_vault,
+            vault_bump: legacy.vault_bump,
+            bump: legacy.bump,
+            total_lp_token_supply: legacy.total_lp_token_supply,
+            total_lp_positions: legacy.total_lp_positions,
+            exchange_rate: legacy.exchange_rate,
+            last_update_slot: legacy.last_update_slot,
+            fee_rate: legacy.fee_rate,
+            pause_flags: legacy.pause_flags,
+            expected_vault_balance: legacy.expected_vault_balance,
+            expected_usdc_vault_balance: legacy.expected_usdc_vault_balance,
+            oracle: legacy.oracle,
+            treasury_base: legacy.treasury_base,
+            treasury_usdc: legacy.treasury_usdc,
+            total_fees_accrued: legacy.total_fees_accrued,
+            authority: ctx.accounts.authority.key(),
+            pending_authority: None,
+            liquidation_bonus_bps: DEFAULT_LIQUIDATION_BONUS_BPS,
+        };
+
+        // Grow the account and top up rent for the new size
+        let required_lamports = Rent::get()?.minimum_balance(InfernoCrucible::LEN);
+        let top_up = required_lamports.saturating_sub(crucible_info.lamports());
+        if top_up > 0 {
+            let cpi_accounts = anchor_lang::system_program::Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: crucible_info.clone(),
+            };
+            let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+            anchor_lang::system_program::transfer(cpi_ctx, top_up)?;
+        }
+        crucible_info.realloc(InfernoCrucible::LEN, false)?;
+
+        let mut data = crucible_info.try_borrow_mut_data()?;
+        let mut slice = &mut data[8..]; // Skip discriminator
+        migrated.serialize(&mut slice)?;
+
+        msg!("Migrated Inferno crucible {} with authority {}", crucible_info.key(), migrated.authority);
+        Ok(())
+    }
+
+    /// Propose a new crucible authority - takes effect once the new authority accepts
+    pub fn propose_authority(
+        ctx: Context<ProposeAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        let crucible = &mut ctx.accounts.crucible;
+        require!(
+            new_authority != Pubkey::default() && new_authority != crucible.authority,
+            InfernoCrucibleError::InvalidConfig
+        );
+        crucible.pending_authority = Some(new_authority);
+
+        emit!(InfernoAuthorityProposed {
+            crucible: crucible.key(),
+            current_authority: crucible.authority,
+            proposed_authority: new_authority,
+        });
+        Ok(())
+    }
+
+    /// Accept a pending authority transfer - must be signed by the proposed authority
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let crucible = &mut ctx.accounts.crucible;
+        let pending = crucible.pending_authority
+            .ok_or(InfernoCrucibleError::NoPendingAuthority)?;
+        require_keys_eq!(
+            pending,
+            ctx.accounts.new_authority.key(),
+            InfernoCrucibleError::Unauthorized
+        );
+
+        let previous_authority = crucible.authority;
+        crucible.authority = pending;
+        crucible.pending_authority = None;
+
+        emit!(InfernoAuthorityTransferred {
+            crucible: crucible.key(),
+            previous_authority,
+            new_authority: pending,
+        });
+        Ok(())
+    }
+
     /// Update treasury accounts - only authority can call
+    /// The base treasury must be the forge-core fee vault for the base mint.
     pub fn update_treasury(
-        ctx: Context<UpdateTreasury>,
+        ctx: Context<UpdateInfernoTreasury>,
         new_treasury_base: Option<Pubkey>,
         new_treasury_usdc: Option<Pubkey>,
     ) -> Result<()> {
         let crucible = &mut ctx.accounts.crucible;
         
         if let Some(treasury_base) = new_treasury_base {
+            require_keys_eq!(
+                treasury_base,
+                forge_core::fees::fee_vault_address(&crucible.base_mint),
+                InfernoCrucibleError::InvalidTreasury
+            );
             crucible.treasury_base = treasury_base;
             msg!("Updated treasury_base to: {}", treasury_base);
         }
         
         if let Some(treasury_usdc) = new_treasury_usdc {
+            // SECURITY FIX: USDC fees only go to the forge-core fee vault for the vault's own mint
+            let new_treasury_usdc = ctx
+                .accounts
+                .new_treasury_usdc
+                .as_ref()
+                .ok_or(InfernoCrucibleError::InvalidTreasury)?;
+            require_keys_eq!(
+                treasury_usdc,
+                new_treasury_usdc.key(),
+                InfernoCrucibleError::InvalidTreasury
+            );
+            require_keys_eq!(
+                treasury_usdc,
+                forge_core::fees::fee_vault_address(&new_treasury_usdc.mint),
+                InfernoCrucibleError::InvalidTreasury
+            );
             crucible.treasury_usdc = treasury_usdc;
             msg!("Updated treasury_usdc to: {}", treasury_usdc);
         }
@@ -275,6 +428,39 @@ pub mod forge_crucibles_inferno {
         msg!("Updated oracle to: {:?}", new_oracle);
         Ok(())
     }
+
+    /// Update the liquidation bonus paid to keepers - only authority can call
+    pub fn update_liquidation_bonus(
+        ctx: Context<UpdateTreasury>,
+        liquidation_bonus_bps: u64,
+    ) -> Result<()> {
+        require!(
+            liquidation_bonus_bps <= MAX_LIQUIDATION_BONUS_BPS,
+            InfernoCrucibleError::InvalidLiquidationBonus
+        );
+        let crucible = &mut ctx.accounts.crucible;
+        crucible.liquidation_bonus_bps = liquidation_bonus_bps;
+        msg!("Updated liquidation bonus to: {} bps", liquidation_bonus_bps);
+        Ok(())
+    }
+
+    /// Set the per-operation pause flags (forge_core::pause PAUSE_*) - only authority can call
+    pub fn set_pause_flags(
+        ctx: Context<UpdateTreasury>,
+        pause_flags: u8,
+    ) -> Result<()> {
+        let crucible = &mut ctx.accounts.crucible;
+        let old_pause_flags = decode_pause_flags(crucible.pause_flags);
+        crucible.pause_flags = encode_pause_flags(pause_flags);
+
+        emit!(InfernoPauseFlagsUpdated {
+            crucible: crucible.key(),
+            old_pause_flags,
+            pause_flags,
+            authority: ctx.accounts.authority.key(),
+        });
+        Ok(())
+    }
 }
 
 #[derive(Accounts)]
@@ -318,12 +504,18 @@ pub struct InitializeInfernoCrucible<'info> {
     /// CHECK: USDC mint
     pub usdc_mint: UncheckedAccount<'info>,
 
-    /// CHECK: Base token treasury account
-    #[account(mut)]
+    /// CHECK: Forge-core fee vault PDA for the base mint
+    #[account(
+        mut,
+        constraint = treasury_base.key() == forge_core::fees::fee_vault_address(&base_mint.key()) @ InfernoCrucibleError::InvalidTreasury
+    )]
     pub treasury_base: UncheckedAccount<'info>,
 
-    /// CHECK: USDC treasury account
-    #[account(mut)]
+    /// CHECK: Forge-core fee vault PDA for the USDC mint
+    #[account(
+        mut,
+        constraint = treasury_usdc.key() == forge_core::fees::fee_vault_address(&usdc_mint.key()) @ InfernoCrucibleError::InvalidTreasury
+    )]
     pub treasury_usdc: UncheckedAccount<'info>,
 
     /// CHECK: Oracle account for price feeds. Pass system program if not used.
@@ -336,10 +528,82 @@ pub struct InitializeInfernoCrucible<'info> {
 
 #[derive(Accounts)]
 pub struct UpdateTreasury<'info> {
-    /// Only the original authority can update treasury
+    /// Only the crucible authority can update treasury
     #[account(mut)]
     pub authority: Signer<'info>,
 
+    #[account(
+        mut,
+        seeds = [b"crucible", crucible.base_mint.as_ref()],
+        bump = crucible.bump,
+        has_one = authority @ InfernoCrucibleError::Unauthorized
+    )]
+    pub crucible: Account<'info, InfernoCrucible>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateInfernoTreasury<'info> {
+    /// Only the crucible authority can update treasury
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"crucible", crucible.base_mint.as_ref()],
+        bump = crucible.bump,
+        has_one = authority @ InfernoCrucibleError::Unauthorized
+    )]
+    pub crucible: Account<'info, InfernoCrucible>,
+
+    /// New USDC fee vault - required when new_treasury_usdc is set
+    pub new_treasury_usdc: Option<Account<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateInfernoCrucible<'info> {
+    /// Program upgrade authority - becomes the crucible authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Crucible in the legacy layout - deserialized manually in the instruction
+    #[account(
+        mut,
+        seeds = [b"crucible", base_mint.key().as_ref()],
+        bump
+    )]
+    pub crucible: UncheckedAccount<'info>,
+
+    /// CHECK: Base mint for crucible PDA derivation
+    pub base_mint: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(authority.key()) @ InfernoCrucibleError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"crucible", crucible.base_mint.as_ref()],
+        bump = crucible.bump,
+        has_one = authority @ InfernoCrucibleError::Unauthorized
+    )]
+    pub crucible: Account<'info, InfernoCrucible>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    pub new_authority: Signer<'info>,
+
     #[account(
         mut,
         seeds = [b"crucible", crucible.base_mint.as_ref()],
@@ -360,3 +624,25 @@ pub struct InfernoCrucibleInitialized {
     pub fee_rate: u64,
     pub timestamp: i64,
 }
+
+#[event]
+pub struct InfernoAuthorityProposed {
+    pub crucible: Pubkey,
+    pub current_authority: Pubkey,
+    pub proposed_authority: Pubkey,
+}
+
+#[event]
+pub struct InfernoAuthorityTransferred {
+    pub crucible: Pubkey,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct InfernoPauseFlagsUpdated {
+    pub crucible: Pubkey,
+    pub old_pause_flags: u8,
+    pub pause_flags: u8,
+    pub authority: Pubkey,
+}
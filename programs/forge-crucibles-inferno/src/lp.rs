@@ -1,19 +1,16 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer, MintTo, Burn};
-use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
-use lending_pool_usdc::cpi::accounts::RepayUSDC;
+use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer, MintTo, Burn, CloseAccount};
+use lending_pool_usdc::cpi::accounts::{BorrowUSDC, RepayUSDC, RepayUSDCOnBehalf};
 use lending_pool_usdc::program::LendingPoolUsdc;
 
-use crate::state::{InfernoCrucible, InfernoLPPositionAccount, InfernoLPPositionAccountLegacy, InfernoCrucibleError};
+use crate::state::{InfernoCrucible, InfernoLPPositionAccount, InfernoCrucibleError, load_protocol_fee_bps, require_not_paused, split_fee_value};
+use forge_core::pause::{PAUSE_LIQUIDATIONS, PAUSE_LP_CLOSE, PAUSE_LP_OPEN};
 
 const PRICE_SCALE: u64 = 1_000_000;
 const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
-const SLIPPAGE_TOLERANCE_BPS: u64 = 100;
 const OPEN_FEE_BPS: u64 = 100;
 const CLOSE_FEE_PRINCIPAL_BPS: u64 = 200;
 const CLOSE_FEE_YIELD_BPS: u64 = 1_000;
-const VAULT_FEE_SHARE_BPS: u64 = 8_000;
-const PROTOCOL_FEE_SHARE_BPS: u64 = 2_000;
 
 const MIN_LP_BASE_AMOUNT: u64 = 1_000;
 const MIN_LP_USDC_AMOUNT: u64 = 1_000;
@@ -25,6 +22,8 @@ const MIN_LEVERAGE_BPS: u64 = 100;
 const MAX_CONFIDENCE_BPS: u64 = 500;
 const MAX_STALENESS_SECONDS: u64 = 300;
 const LIQUIDATION_THRESHOLD_BPS: u64 = 9_000;
+pub const DEFAULT_LIQUIDATION_BONUS_BPS: u64 = 500; // 5% bonus on seized collateral
+pub const MAX_LIQUIDATION_BONUS_BPS: u64 = 2_000;
 
 pub fn open_inferno_lp_position(
     ctx: Context<OpenInfernoLPPosition>,
@@ -37,7 +36,8 @@ pub fn open_inferno_lp_position(
 ) -> Result<u64> {
     let crucible_key = ctx.accounts.crucible.key();
     let crucible = &mut ctx.accounts.crucible;
-    require!(!crucible.paused, InfernoCrucibleError::ProtocolPaused);
+    require_not_paused(crucible.pause_flags, PAUSE_LP_OPEN)?;
+    let protocol_fee_bps = load_protocol_fee_bps(&ctx.accounts.forge_protocol)?;
 
     require!(
         max_slippage_bps <= 10_000 &&
@@ -116,14 +116,6 @@ pub fn open_inferno_lp_position(
         .checked_mul(OPEN_FEE_BPS as u128)
         .and_then(|v| v.checked_div(10_000u128))
         .ok_or(ProgramError::ArithmeticOverflow)?;
-    let vault_fee_share = open_fee_usdc
-        .checked_mul(VAULT_FEE_SHARE_BPS as u128)
-        .and_then(|v| v.checked_div(10_000u128))
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    let _protocol_fee_share = open_fee_usdc
-        .checked_sub(vault_fee_share)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-
     let fee_base_amount = open_fee_usdc
         .checked_mul(base_amount as u128)
         .and_then(|v| v.checked_div(total_position_value))
@@ -135,18 +127,8 @@ pub fn open_inferno_lp_position(
         .and_then(|v| v.checked_div(total_position_value))
         .ok_or(ProgramError::ArithmeticOverflow)?;
 
-    let vault_fee_base = fee_base_amount
-        .checked_mul(80u128)
-        .and_then(|v| v.checked_div(100u128))
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    let vault_fee_usdc = fee_usdc_amount
-        .checked_mul(80u128)
-        .and_then(|v| v.checked_div(100u128))
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    let protocol_fee_base = fee_base_amount.checked_sub(vault_fee_base)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    let protocol_fee_usdc = fee_usdc_amount.checked_sub(vault_fee_usdc)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let (vault_fee_base, protocol_fee_base) = split_fee_value(fee_base_amount, protocol_fee_bps)?;
+    let (vault_fee_usdc, protocol_fee_usdc) = split_fee_value(fee_usdc_amount, protocol_fee_bps)?;
 
     let vault_fee_base = vault_fee_base as u64;
     let vault_fee_usdc = vault_fee_usdc as u64;
@@ -165,6 +147,44 @@ pub fn open_inferno_lp_position(
         InfernoCrucibleError::InvalidProgram
     );
 
+    let crucible_bump = ctx.bumps.crucible;
+    let seeds = &[
+        b"crucible",
+        crucible.base_mint.as_ref(),
+        &[crucible_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    // Borrow the leveraged USDC into the user's account; the crucible PDA is the
+    // registered borrow authority and signs the borrow
+    if borrowed_usdc > 0 {
+        // The pool must lend the crucible's quote asset (its USDC vault mint, e.g. USDC or USDT)
+        let pool = lending_pool_usdc::load_pool(&ctx.accounts.lending_market)?;
+        require_keys_eq!(
+            pool.usdc_mint,
+            ctx.accounts.crucible_usdc_vault.mint,
+            InfernoCrucibleError::InvalidMint
+        );
+        let cpi_accounts = BorrowUSDC {
+            pool: ctx.accounts.lending_market.to_account_info(),
+            borrower: ctx.accounts.user.to_account_info(),
+            borrower_account: ctx.accounts.borrower_account.to_account_info(),
+            borrow_authority: ctx.accounts.crucible_authority.to_account_info(),
+            authorized_borrower: ctx.accounts.authorized_borrower.to_account_info(),
+            pool_vault: ctx.accounts.lending_vault.to_account_info(),
+            borrower_usdc_account: ctx.accounts.user_usdc_account.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            forge_protocol: ctx.accounts.forge_protocol.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.lending_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        );
+        lending_pool_usdc::cpi::borrow_usdc(cpi_ctx, borrowed_usdc, ctx.accounts.position.key())?;
+    }
+
     // Transfers to vaults + treasury
     let cpi_program = ctx.accounts.token_program.to_account_info();
     let cpi_ctx = CpiContext::new(cpi_program.clone(), Transfer {
@@ -247,16 +267,10 @@ pub fn open_inferno_lp_position(
         lp_tokens_to_mint_u128 as u64
     };
 
-    let crucible_bump = ctx.bumps.crucible;
-    let seeds = &[
-        b"crucible",
-        crucible.base_mint.as_ref(),
-        &[crucible_bump],
-    ];
-    let signer = &[&seeds[..]];
+    // LP tokens are held in the position's escrow so liquidation can burn them
     let mint_to_accounts = MintTo {
         mint: ctx.accounts.lp_token_mint.to_account_info(),
-        to: ctx.accounts.user_lp_token_account.to_account_info(),
+        to: ctx.accounts.lp_escrow.to_account_info(),
         authority: ctx.accounts.crucible_authority.to_account_info(),
     };
     let mint_to_program = ctx.accounts.token_program.to_account_info();
@@ -339,13 +353,14 @@ pub fn open_inferno_lp_position(
 pub fn close_inferno_lp_position(
     ctx: Context<CloseInfernoLPPosition>,
     max_slippage_bps: u64,
-    position_nonce: u64, // Nonce used when opening the position
+    _position_nonce: u64, // Used for PDA derivation in account constraints
 ) -> Result<()> {
     require!(max_slippage_bps <= 10_000, InfernoCrucibleError::InvalidAmount);
 
     let crucible_key = ctx.accounts.crucible.key();
     let crucible = &mut ctx.accounts.crucible;
-    require!(!crucible.paused, InfernoCrucibleError::ProtocolPaused);
+    require_not_paused(crucible.pause_flags, PAUSE_LP_CLOSE)?;
+    let protocol_fee_bps = load_protocol_fee_bps(&ctx.accounts.forge_protocol)?;
     let position = &mut ctx.accounts.position;
 
     require!(position.is_open, InfernoCrucibleError::PositionNotOpen);
@@ -426,16 +441,8 @@ pub fn close_inferno_lp_position(
         .ok_or(ProgramError::ArithmeticOverflow)?;
     let fee_usdc_amount = fee_usdc_value;
 
-    let vault_fee_base = fee_base_amount
-        .checked_mul(80u128)
-        .and_then(|v| v.checked_div(100u128))
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    let vault_fee_usdc = fee_usdc_amount
-        .checked_mul(80u128)
-        .and_then(|v| v.checked_div(100u128))
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    let protocol_fee_base = fee_base_amount - vault_fee_base;
-    let protocol_fee_usdc = fee_usdc_amount - vault_fee_usdc;
+    let (vault_fee_base, protocol_fee_base) = split_fee_value(fee_base_amount, protocol_fee_bps)?;
+    let (vault_fee_usdc, protocol_fee_usdc) = split_fee_value(fee_usdc_amount, protocol_fee_bps)?;
 
     let vault_fee_base = vault_fee_base as u64;
     let _vault_fee_usdc = vault_fee_usdc as u64;
@@ -469,19 +476,32 @@ pub fn close_inferno_lp_position(
     let cpi_ctx = CpiContext::new_with_signer(cpi_program.clone(), cpi_accounts, signer);
     token::transfer(cpi_ctx, base_to_return)?;
 
-    // Calculate repay amount before USDC transfer (if leveraged)
-    // For 1x positions (no borrowed USDC), skip lending calculations entirely
-    let repay_amount: u64 = if position.borrowed_usdc > 0 {
-        // Leveraged position - need to repay borrowed USDC
-        // Note: This requires the lending accounts to be properly initialized
-        position.borrowed_usdc // Simple repay of principal for now
+    // Leveraged positions repay principal plus the interest accrued on the pool index, as
+    // liquidation does. The borrowed principal comes back out of the vault; the owner pays
+    // the interest from their own USDC. 1x positions skip lending entirely.
+    let borrowed_principal = position.borrowed_usdc;
+    let debt = if borrowed_principal > 0 {
+        let (borrower_account, debt_position) = lending_pool_usdc::position_borrower_account(
+            &ctx.accounts.borrower_account,
+            ctx.accounts.legacy_borrower_account.as_ref().map(|a| a.as_ref()),
+            &ctx.accounts.lending_market.key(),
+            &ctx.accounts.user.key(),
+            &position.key(),
+        )?;
+        let total_debt = position_debt(
+            &ctx.accounts.lending_market,
+            borrower_account,
+            &ctx.accounts.user.key(),
+            &debt_position,
+        )?;
+        Some((borrower_account, debt_position, total_debt))
     } else {
-        0
+        None
     };
 
-    // Return USDC to user (include repay amount so user can repay in same tx)
+    // Return USDC to user (include the borrowed principal so user can repay in same tx)
     let usdc_to_user = usdc_to_return
-        .checked_add(repay_amount)
+        .checked_add(borrowed_principal)
         .ok_or(InfernoCrucibleError::InvalidAmount)?;
     let cpi_accounts = Transfer {
         from: ctx.accounts.crucible_usdc_vault.to_account_info(),
@@ -520,36 +540,60 @@ pub fn close_inferno_lp_position(
     }
 
     // Repay borrowed USDC (principal + interest) if leveraged
-    if repay_amount > 0 {
+    if let Some((borrower_account, debt_position, total_debt)) = debt {
         let cpi_program = ctx.accounts.lending_program.to_account_info();
         let cpi_accounts = RepayUSDC {
             pool: ctx.accounts.lending_market.to_account_info(),
             borrower: ctx.accounts.user.to_account_info(),
-            borrower_account: ctx.accounts.borrower_account.to_account_info(),
+            borrower_account: borrower_account.clone(),
             borrower_usdc_account: ctx.accounts.user_usdc_account.to_account_info(),
             pool_vault: ctx.accounts.lending_vault.to_account_info(),
+            authorized_borrower: ctx.accounts.authorized_borrower.as_ref().map(|a| a.to_account_info()),
             token_program: ctx.accounts.token_program.to_account_info(),
+            forge_protocol: Some(ctx.accounts.forge_protocol.to_account_info()),
         };
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        lending_pool_usdc::cpi::repay_usdc(cpi_ctx, repay_amount)?;
+        lending_pool_usdc::cpi::repay_usdc(cpi_ctx, total_debt, debt_position)?;
+
+        // SECURITY FIX: The position may only close once its borrower account is fully repaid
+        require!(
+            position_debt(&ctx.accounts.lending_market, borrower_account, &ctx.accounts.user.key(), &debt_position)? == 0,
+            InfernoCrucibleError::OutstandingDebt
+        );
     }
 
-    // Burn LP tokens
-    let burn_accounts = Burn {
-        mint: ctx.accounts.lp_token_mint.to_account_info(),
-        from: ctx.accounts.user_lp_token_account.to_account_info(),
-        authority: ctx.accounts.user.to_account_info(),
+    // Burn LP tokens - from the position's escrow, or from the owner's account for
+    // positions opened before LP escrow
+    let lp_burned = if ctx.accounts.lp_escrow.data_is_empty() {
+        let user_lp_token_account = ctx.accounts.user_lp_token_account
+            .as_ref()
+            .ok_or(InfernoCrucibleError::InvalidLPAmounts)?;
+        let burn_accounts = Burn {
+            mint: ctx.accounts.lp_token_mint.to_account_info(),
+            from: user_lp_token_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let burn_program = ctx.accounts.token_program.to_account_info();
+        let burn_ctx = CpiContext::new(burn_program, burn_accounts);
+        token::burn(burn_ctx, user_lp_token_account.amount)?;
+        user_lp_token_account.amount
+    } else {
+        burn_lp_escrow(
+            &ctx.accounts.lp_escrow,
+            &ctx.accounts.lp_token_mint.to_account_info(),
+            &ctx.accounts.crucible_authority,
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+            signer,
+        )?
     };
-    let burn_program = ctx.accounts.token_program.to_account_info();
-    let burn_ctx = CpiContext::new(burn_program, burn_accounts);
-    token::burn(burn_ctx, ctx.accounts.user_lp_token_account.amount)?;
 
     let base_vault_out = base_to_return
         .checked_add(protocol_fee_base)
         .ok_or(ProgramError::ArithmeticOverflow)?;
     let usdc_vault_out = usdc_to_return
         .checked_add(protocol_fee_usdc)
-        .and_then(|v| v.checked_add(repay_amount))
+        .and_then(|v| v.checked_add(borrowed_principal))
         .ok_or(ProgramError::ArithmeticOverflow)?;
     crucible.expected_vault_balance = crucible.expected_vault_balance
         .checked_sub(base_vault_out)
@@ -559,7 +603,7 @@ pub fn close_inferno_lp_position(
         .ok_or(InfernoCrucibleError::InvalidAmount)?;
 
     crucible.total_lp_token_supply = crucible.total_lp_token_supply
-        .checked_sub(ctx.accounts.user_lp_token_account.amount)
+        .checked_sub(lp_burned)
         .ok_or(InfernoCrucibleError::InvalidAmount)?;
     if vault_fee_base > 0 {
         crucible.total_fees_accrued = crucible.total_fees_accrued
@@ -588,29 +632,196 @@ pub fn health_check_inferno(ctx: Context<HealthCheckInferno>) -> Result<u64> {
     calculate_ltv_bps(
         &ctx.accounts.crucible,
         &ctx.accounts.position,
+        ctx.accounts.position.borrowed_usdc,
         &ctx.accounts.base_mint.key(),
         &ctx.accounts.oracle,
     )
 }
 
 pub fn liquidate_inferno_lp_position(
-    ctx: Context<CloseInfernoLPPosition>,
-    max_slippage_bps: u64,
-    position_nonce: u64,
+    ctx: Context<LiquidateInfernoLPPosition>,
+    _position_nonce: u64, // Used for PDA derivation in account constraints
 ) -> Result<()> {
+    let crucible_key = ctx.accounts.crucible.key();
+    let crucible = &mut ctx.accounts.crucible;
+    require_not_paused(crucible.pause_flags, PAUSE_LIQUIDATIONS)?;
+    forge_core::require_protocol_active_if_present(ctx.accounts.forge_protocol.as_deref())?;
+    let position = &mut ctx.accounts.position;
+
+    require!(position.is_open, InfernoCrucibleError::PositionNotOpen);
+    require!(position.crucible == crucible_key, InfernoCrucibleError::InvalidLPAmounts);
+    // SECURITY FIX: Positions opened before LP escrow keep their LP tokens in the owner's
+    // wallet, where liquidation can't burn them - they would stay in total_lp_token_supply
+    // and dilute the exchange rate. Those must go through escrow_legacy_lp_tokens first.
+    require!(!ctx.accounts.lp_escrow.data_is_empty(), InfernoCrucibleError::LPEscrowRequired);
+
+    // Principal plus interest accrued on the pool index, not just the borrowed principal
+    let (borrower_account, debt_position) = lending_pool_usdc::position_borrower_account(
+        &ctx.accounts.borrower_account,
+        ctx.accounts.legacy_borrower_account.as_ref().map(|a| a.as_ref()),
+        &ctx.accounts.lending_market.key(),
+        &ctx.accounts.position_owner.key(),
+        &position.key(),
+    )?;
+    let total_debt = position_debt(
+        &ctx.accounts.lending_market,
+        borrower_account,
+        &position.owner,
+        &debt_position,
+    )?;
+
     let ltv_bps = calculate_ltv_bps(
-        &ctx.accounts.crucible,
-        &ctx.accounts.position,
+        crucible,
+        position,
+        total_debt,
         &ctx.accounts.base_mint.key(),
         &ctx.accounts.oracle,
     )?;
-
     require!(
         ltv_bps >= LIQUIDATION_THRESHOLD_BPS,
         InfernoCrucibleError::PositionNotLiquidatable
     );
 
-    close_inferno_lp_position(ctx, max_slippage_bps, position_nonce)
+    let oracle_account_opt = ctx.accounts.oracle.as_ref().map(|o| o.as_ref());
+    let current_base_token_price = get_oracle_price(
+        crucible,
+        &oracle_account_opt,
+        &ctx.accounts.base_mint.key(),
+    )?;
+
+    // Keeper repays the full debt and seizes that value plus the bonus
+    let repay_amount = total_debt;
+    let (base_seized, usdc_seized) = liquidation_seizure(
+        repay_amount,
+        position.base_amount,
+        position.usdc_amount,
+        current_base_token_price,
+        crucible.liquidation_bonus_bps,
+    )?;
+
+    let base_to_owner = position.base_amount
+        .checked_sub(base_seized)
+        .ok_or(InfernoCrucibleError::InvalidAmount)?;
+    let usdc_to_owner = position.usdc_amount
+        .checked_sub(usdc_seized)
+        .ok_or(InfernoCrucibleError::InvalidAmount)?;
+
+    // Repay the position's debt to the lending pool with the keeper's USDC
+    let cpi_accounts = RepayUSDCOnBehalf {
+        pool: ctx.accounts.lending_market.to_account_info(),
+        payer: ctx.accounts.liquidator.to_account_info(),
+        borrower: ctx.accounts.position_owner.to_account_info(),
+        borrower_account: borrower_account.clone(),
+        payer_usdc_account: ctx.accounts.liquidator_usdc_account.to_account_info(),
+        pool_vault: ctx.accounts.lending_vault.to_account_info(),
+        authorized_borrower: ctx.accounts.authorized_borrower.as_ref().map(|a| a.to_account_info()),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        forge_protocol: ctx.accounts.forge_protocol.as_ref().map(|p| p.to_account_info()),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.lending_program.to_account_info(), cpi_accounts);
+    lending_pool_usdc::cpi::repay_usdc_on_behalf(cpi_ctx, repay_amount, debt_position)?;
+
+    let crucible_bump = ctx.bumps.crucible;
+    let seeds = &[
+        b"crucible",
+        crucible.base_mint.as_ref(),
+        &[crucible_bump],
+    ];
+    let signer = &[&seeds[..]];
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+
+    let transfers = [
+        (&ctx.accounts.crucible_base_vault, &ctx.accounts.liquidator_base_token_account, base_seized),
+        (&ctx.accounts.crucible_usdc_vault, &ctx.accounts.liquidator_usdc_account, usdc_seized),
+        (&ctx.accounts.crucible_base_vault, &ctx.accounts.owner_base_token_account, base_to_owner),
+        (&ctx.accounts.crucible_usdc_vault, &ctx.accounts.owner_usdc_account, usdc_to_owner),
+    ];
+    for (from, to, amount) in transfers {
+        if amount == 0 {
+            continue;
+        }
+        let cpi_accounts = Transfer {
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+            authority: ctx.accounts.crucible_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program.clone(), cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+    }
+
+    // Burn the position's escrowed LP tokens
+    let lp_burned = burn_lp_escrow(
+        &ctx.accounts.lp_escrow,
+        &ctx.accounts.lp_token_mint.to_account_info(),
+        &ctx.accounts.crucible_authority,
+        &ctx.accounts.position_owner,
+        &cpi_program,
+        signer,
+    )?;
+    crucible.total_lp_token_supply = crucible.total_lp_token_supply
+        .checked_sub(lp_burned)
+        .ok_or(InfernoCrucibleError::InvalidAmount)?;
+
+    crucible.expected_vault_balance = crucible.expected_vault_balance
+        .checked_sub(position.base_amount)
+        .ok_or(InfernoCrucibleError::InvalidAmount)?;
+    crucible.expected_usdc_vault_balance = crucible.expected_usdc_vault_balance
+        .checked_sub(position.usdc_amount)
+        .ok_or(InfernoCrucibleError::InvalidAmount)?;
+
+    update_lp_exchange_rate(crucible, current_base_token_price)?;
+    crucible.last_update_slot = Clock::get()?.slot;
+
+    position.is_open = false;
+
+    emit!(InfernoLPPositionLiquidated {
+        position_id: position.position_id,
+        owner: position.owner,
+        liquidator: ctx.accounts.liquidator.key(),
+        crucible: position.crucible,
+        repaid_usdc: repay_amount,
+        base_seized,
+        usdc_seized,
+        base_returned: base_to_owner,
+        usdc_returned: usdc_to_owner,
+        liquidation_bonus_bps: crucible.liquidation_bonus_bps,
+        ltv_bps,
+    });
+
+    Ok(())
+}
+
+/// Move the owner's LP tokens into the escrow of a position opened before LP escrow.
+/// Those positions have no per-position LP record, so - as close_inferno_lp_position burns
+/// for them - the owner's whole LP balance is attributed to the position.
+pub fn escrow_legacy_lp_tokens(
+    ctx: Context<EscrowLegacyLPTokens>,
+    _position_nonce: u64, // Used for PDA derivation in account constraints
+) -> Result<()> {
+    require!(ctx.accounts.position.is_open, InfernoCrucibleError::PositionNotOpen);
+    require!(
+        ctx.accounts.position.crucible == ctx.accounts.crucible.key(),
+        InfernoCrucibleError::InvalidLPAmounts
+    );
+
+    let amount = ctx.accounts.user_lp_token_account.amount;
+    if amount > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.user_lp_token_account.to_account_info(),
+            to: ctx.accounts.lp_escrow.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+    }
+
+    emit!(InfernoLPTokensEscrowed {
+        position_id: ctx.accounts.position.position_id,
+        owner: ctx.accounts.user.key(),
+        crucible: ctx.accounts.crucible.key(),
+        amount,
+    });
+    Ok(())
 }
 
 /// Close a legacy Inferno LP position (positions created before nonce was added)
@@ -622,7 +833,8 @@ pub fn close_inferno_lp_position_legacy(
 
     let crucible_key = ctx.accounts.crucible.key();
     let crucible = &mut ctx.accounts.crucible;
-    require!(!crucible.paused, InfernoCrucibleError::ProtocolPaused);
+    require_not_paused(crucible.pause_flags, PAUSE_LP_CLOSE)?;
+    let protocol_fee_bps = load_protocol_fee_bps(&ctx.accounts.forge_protocol)?;
     
     // Manually deserialize the legacy position account
     // Skip 8-byte discriminator, then read fields in order
@@ -744,16 +956,8 @@ pub fn close_inferno_lp_position_legacy(
         .ok_or(ProgramError::ArithmeticOverflow)?;
     let fee_usdc_amount = fee_usdc_value;
 
-    let vault_fee_base = fee_base_amount
-        .checked_mul(80u128)
-        .and_then(|v| v.checked_div(100u128))
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    let vault_fee_usdc = fee_usdc_amount
-        .checked_mul(80u128)
-        .and_then(|v| v.checked_div(100u128))
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    let protocol_fee_base = fee_base_amount - vault_fee_base;
-    let protocol_fee_usdc = fee_usdc_amount - vault_fee_usdc;
+    let (vault_fee_base, protocol_fee_base) = split_fee_value(fee_base_amount, protocol_fee_bps)?;
+    let (vault_fee_usdc, protocol_fee_usdc) = split_fee_value(fee_usdc_amount, protocol_fee_bps)?;
 
     let vault_fee_base = vault_fee_base as u64;
     let _vault_fee_usdc = vault_fee_usdc as u64;
@@ -787,16 +991,23 @@ pub fn close_inferno_lp_position_legacy(
     let cpi_ctx = CpiContext::new_with_signer(cpi_program.clone(), cpi_accounts, signer);
     token::transfer(cpi_ctx, base_to_return)?;
 
-    // Calculate repay amount before USDC transfer (if leveraged)
-    let repay_amount: u64 = if position_borrowed_usdc > 0 {
-        position_borrowed_usdc
+    // Leveraged positions repay principal plus accrued interest; the owner pays the interest.
+    // Legacy positions always borrowed through the owner's legacy borrower account
+    let borrowed_principal = position_borrowed_usdc;
+    let total_debt = if borrowed_principal > 0 {
+        position_debt(
+            &ctx.accounts.lending_market,
+            &ctx.accounts.borrower_account,
+            &ctx.accounts.user.key(),
+            &Pubkey::default(),
+        )?
     } else {
         0
     };
 
     // Return USDC to user
     let usdc_to_user = usdc_to_return
-        .checked_add(repay_amount)
+        .checked_add(borrowed_principal)
         .ok_or(InfernoCrucibleError::InvalidAmount)?;
     let cpi_accounts = Transfer {
         from: ctx.accounts.crucible_usdc_vault.to_account_info(),
@@ -835,7 +1046,7 @@ pub fn close_inferno_lp_position_legacy(
     }
 
     // Repay borrowed USDC if leveraged
-    if repay_amount > 0 {
+    if total_debt > 0 {
         let cpi_program = ctx.accounts.lending_program.to_account_info();
         let cpi_accounts = RepayUSDC {
             pool: ctx.accounts.lending_market.to_account_info(),
@@ -843,10 +1054,12 @@ pub fn close_inferno_lp_position_legacy(
             borrower_account: ctx.accounts.borrower_account.to_account_info(),
             borrower_usdc_account: ctx.accounts.user_usdc_account.to_account_info(),
             pool_vault: ctx.accounts.lending_vault.to_account_info(),
+            authorized_borrower: ctx.accounts.authorized_borrower.as_ref().map(|a| a.to_account_info()),
             token_program: ctx.accounts.token_program.to_account_info(),
+            forge_protocol: Some(ctx.accounts.forge_protocol.to_account_info()),
         };
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        lending_pool_usdc::cpi::repay_usdc(cpi_ctx, repay_amount)?;
+        lending_pool_usdc::cpi::repay_usdc(cpi_ctx, total_debt, Pubkey::default())?;
     }
 
     // Burn LP tokens
@@ -864,7 +1077,7 @@ pub fn close_inferno_lp_position_legacy(
         .ok_or(ProgramError::ArithmeticOverflow)?;
     let usdc_vault_out = usdc_to_return
         .checked_add(protocol_fee_usdc)
-        .and_then(|v| v.checked_add(repay_amount))
+        .and_then(|v| v.checked_add(borrowed_principal))
         .ok_or(ProgramError::ArithmeticOverflow)?;
     crucible.expected_vault_balance = crucible.expected_vault_balance
         .checked_sub(base_vault_out)
@@ -905,41 +1118,98 @@ pub fn close_inferno_lp_position_legacy(
     Ok(())
 }
 
-fn calculate_total_owed(
-    pool: &Account<lending_pool_usdc::LendingPool>,
-    borrower_account: &Account<lending_pool_usdc::BorrowerAccount>,
-) -> Result<u64> {
-    const SECONDS_PER_YEAR: u64 = 31_536_000;
-    let clock = Clock::get()?;
-    let current_timestamp = clock.unix_timestamp as u64;
-    require!(
-        borrower_account.borrow_timestamp <= current_timestamp,
-        InfernoCrucibleError::InvalidConfig
-    );
-    let seconds_elapsed = current_timestamp
-        .checked_sub(borrower_account.borrow_timestamp)
-        .ok_or(InfernoCrucibleError::InvalidConfig)?;
-
-    let principal_u128 = borrower_account.amount_borrowed as u128;
-    let borrow_rate_u128 = pool.borrow_rate as u128;
-    let seconds_elapsed_u128 = seconds_elapsed as u128;
-
-    let interest_accrued = principal_u128
-        .checked_mul(borrow_rate_u128)
-        .and_then(|v| v.checked_mul(seconds_elapsed_u128))
-        .and_then(|v| v.checked_div(100u128))
-        .and_then(|v| v.checked_div(SECONDS_PER_YEAR as u128))
-        .ok_or(InfernoCrucibleError::InvalidAmount)?;
+/// Collateral seized by a keeper repaying `repay_amount`, as (base, usdc)
+/// Worth the repayment plus the bonus, capped at the position's value; USDC is seized first.
+fn liquidation_seizure(
+    repay_amount: u64,
+    base_amount: u64,
+    usdc_amount: u64,
+    base_token_price: u64,
+    liquidation_bonus_bps: u64,
+) -> Result<(u64, u64)> {
+    require!(base_token_price > 0, InfernoCrucibleError::InvalidConfig);
+    let base_value = (base_amount as u128)
+        .checked_mul(base_token_price as u128)
+        .and_then(|v| v.checked_div(LAMPORTS_PER_SOL as u128))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let usdc_value = usdc_amount as u128;
+    let total_value = base_value
+        .checked_add(usdc_value)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let seize_value = (repay_amount as u128)
+        .checked_mul(10_000u128 + liquidation_bonus_bps as u128)
+        .and_then(|v| v.checked_div(10_000u128))
+        .ok_or(ProgramError::ArithmeticOverflow)?
+        .min(total_value);
 
-    let total_owed = principal_u128
-        .checked_add(interest_accrued)
+    let usdc_seized = seize_value.min(usdc_value);
+    let base_value_seized = seize_value
+        .checked_sub(usdc_seized)
         .ok_or(ProgramError::ArithmeticOverflow)?;
+    let base_seized = base_value_seized
+        .checked_mul(LAMPORTS_PER_SOL as u128)
+        .and_then(|v| v.checked_div(base_token_price as u128))
+        .ok_or(ProgramError::ArithmeticOverflow)?
+        .min(base_amount as u128);
+    Ok((base_seized as u64, usdc_seized as u64))
+}
 
-    if total_owed > u64::MAX as u128 {
-        return Err(ProgramError::ArithmeticOverflow.into());
+/// Burn everything in a position's LP escrow and close it, returning the amount burned
+/// Returns 0 without touching anything when the escrow was never created.
+fn burn_lp_escrow<'info>(
+    lp_escrow: &AccountInfo<'info>,
+    lp_token_mint: &AccountInfo<'info>,
+    crucible_authority: &AccountInfo<'info>,
+    rent_destination: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+) -> Result<u64> {
+    if lp_escrow.data_is_empty() {
+        return Ok(0);
     }
+    let amount = {
+        let data = lp_escrow.try_borrow_data()?;
+        TokenAccount::try_deserialize(&mut &data[..])?.amount
+    };
 
-    Ok(total_owed as u64)
+    if amount > 0 {
+        let burn_accounts = Burn {
+            mint: lp_token_mint.clone(),
+            from: lp_escrow.clone(),
+            authority: crucible_authority.clone(),
+        };
+        let burn_ctx = CpiContext::new_with_signer(token_program.clone(), burn_accounts, signer);
+        token::burn(burn_ctx, amount)?;
+    }
+
+    let close_accounts = CloseAccount {
+        account: lp_escrow.clone(),
+        destination: rent_destination.clone(),
+        authority: crucible_authority.clone(),
+    };
+    let close_ctx = CpiContext::new_with_signer(token_program.clone(), close_accounts, signer);
+    token::close_account(close_ctx)?;
+    Ok(amount)
+}
+
+/// Current debt (principal + interest) of a position on the lending pool
+/// `debt_position` is the position reference from lending_pool_usdc::position_borrower_account.
+fn position_debt(
+    lending_market: &AccountInfo,
+    borrower_account: &AccountInfo,
+    owner: &Pubkey,
+    debt_position: &Pubkey,
+) -> Result<u64> {
+    let pool = lending_pool_usdc::load_pool(lending_market)?;
+    let borrower = lending_pool_usdc::load_borrower_account(
+        borrower_account,
+        &lending_market.key(),
+        owner,
+        debt_position,
+    )?;
+    let now = Clock::get()?.unix_timestamp as u64;
+    lending_pool_usdc::borrower_debt(&pool, &borrower, now)
 }
 
 fn update_lp_exchange_rate(crucible: &mut InfernoCrucible, base_token_price: u64) -> Result<()> {
@@ -979,92 +1249,38 @@ pub fn get_oracle_price(
 
     if let Some(oracle_pubkey) = crucible.oracle {
         let oracle = oracle_account.ok_or(InfernoCrucibleError::InvalidOraclePrice)?;
-        require!(!oracle.data_is_empty(), InfernoCrucibleError::InvalidOraclePrice);
-
-        let account_data_result = oracle.try_borrow_data();
-        if account_data_result.is_err() {
-            return Err(InfernoCrucibleError::InvalidOraclePrice.into());
-        }
         require!(
             *oracle.key == oracle_pubkey,
             InfernoCrucibleError::InvalidOraclePrice
         );
-        let account_data = account_data_result.unwrap();
 
-        let _price_update = PriceUpdateV2::try_deserialize(&mut &account_data[..])
+        // SECURITY FIX: Decode with the shared PriceUpdateV2 parser, which validates the Pyth
+        // receiver owner and discriminator and reads the verification level
+        let price_update = forge_core::oracle::load_price_update(oracle)
             .map_err(|_| InfernoCrucibleError::InvalidOraclePrice)?;
-
-        const MIN_REQUIRED_SIZE: usize = 132;
+        // Only fully verified, fresh updates are used; the account is pinned by crucible.oracle
+        let current_time = Clock::get()?.unix_timestamp;
+        let price_update = price_update
+            .price_no_older_than(current_time, MAX_STALENESS_SECONDS, &price_update.feed_id)
+            .ok_or(InfernoCrucibleError::StaleOraclePrice)?;
+        let expo = price_update.exponent;
+
+        let scale = |value: f64| if expo >= 0 {
+            value * (10.0_f64.powi(expo))
+        } else {
+            value / (10.0_f64.powi(-expo))
+        };
+        let price_usd = scale(price_update.price as f64);
+        let confidence_bps = if price_usd > 0.0 {
+            (scale(price_update.conf as f64) / price_usd * 10_000.0) as u64
+        } else {
+            return Err(InfernoCrucibleError::InvalidOraclePrice.into());
+        };
         require!(
-            account_data.len() >= MIN_REQUIRED_SIZE,
+            confidence_bps <= MAX_CONFIDENCE_BPS,
             InfernoCrucibleError::InvalidOraclePrice
         );
 
-        const PRICE_OFFSET: usize = 96;
-        const PRICE_SIZE: usize = 8;
-        let price_bytes = account_data[PRICE_OFFSET..PRICE_OFFSET + PRICE_SIZE]
-            .try_into()
-            .map_err(|_| InfernoCrucibleError::InvalidOraclePrice)?;
-        let price: i64 = i64::from_le_bytes(price_bytes);
-
-        const EXPO_OFFSET: usize = 104;
-        const EXPO_SIZE: usize = 4;
-        let expo_bytes = account_data[EXPO_OFFSET..EXPO_OFFSET + EXPO_SIZE]
-            .try_into()
-            .map_err(|_| InfernoCrucibleError::InvalidOraclePrice)?;
-        let expo: i32 = i32::from_le_bytes(expo_bytes);
-
-        const PUB_TIME_OFFSET: usize = 112;
-        const PUB_TIME_SIZE: usize = 8;
-        let pub_time_bytes = account_data[PUB_TIME_OFFSET..PUB_TIME_OFFSET + PUB_TIME_SIZE]
-            .try_into()
-            .map_err(|_| InfernoCrucibleError::InvalidOraclePrice)?;
-        let publish_time: u64 = u64::from_le_bytes(pub_time_bytes);
-
-        const CONFIDENCE_OFFSET: usize = 120;
-        const CONFIDENCE_SIZE: usize = 8;
-        if account_data.len() >= CONFIDENCE_OFFSET + CONFIDENCE_SIZE {
-            let conf_bytes = account_data[CONFIDENCE_OFFSET..CONFIDENCE_OFFSET + CONFIDENCE_SIZE]
-                .try_into()
-                .map_err(|_| InfernoCrucibleError::InvalidOraclePrice)?;
-            let confidence: u64 = u64::from_le_bytes(conf_bytes);
-            let confidence_f64 = confidence as f64;
-            let expo_val = expo as i32;
-            let conf_price_f64 = if expo_val >= 0 {
-                confidence_f64 * (10.0_f64.powi(expo_val))
-            } else {
-                confidence_f64 / (10.0_f64.powi(-expo_val))
-            };
-            let price_value_f64 = price as f64;
-            let price_usd_temp = if expo_val >= 0 {
-                price_value_f64 * (10.0_f64.powi(expo_val))
-            } else {
-                price_value_f64 / (10.0_f64.powi(-expo_val))
-            };
-            let confidence_bps = if price_usd_temp > 0.0 {
-                (conf_price_f64 / price_usd_temp * 10_000.0) as u64
-            } else {
-                return Err(InfernoCrucibleError::InvalidOraclePrice.into());
-            };
-            require!(
-                confidence_bps <= MAX_CONFIDENCE_BPS,
-                InfernoCrucibleError::InvalidOraclePrice
-            );
-        }
-
-        let clock = Clock::get()?;
-        let current_time = clock.unix_timestamp as u64;
-        let age = current_time.saturating_sub(publish_time);
-        require!(age <= MAX_STALENESS_SECONDS, InfernoCrucibleError::StaleOraclePrice);
-
-        let price_value_f64 = price as f64;
-        let expo_val = expo as i32;
-        let price_usd = if expo_val >= 0 {
-            price_value_f64 * (10.0_f64.powi(expo_val))
-        } else {
-            price_value_f64 / (10.0_f64.powi(-expo_val))
-        };
-
         let (_min_price, _max_price) = match base_mint.to_string().as_str() {
             _ => (MIN_PRICE_USD, MAX_PRICE_USD),
         };
@@ -1123,12 +1339,17 @@ pub struct OpenInfernoLPPosition<'info> {
     pub user_usdc_account: Box<Account<'info, TokenAccount>>,
     #[account(mut)]
     pub crucible_base_vault: Box<Account<'info, TokenAccount>>,
-    #[account(mut)]
+    /// SECURITY FIX: Must be the crucible's quote vault - its mint selects the lending pool asset
+    #[account(
+        mut,
+        address = crucible.usdc_vault @ InfernoCrucibleError::InvalidConfig
+    )]
     pub crucible_usdc_vault: Box<Account<'info, TokenAccount>>,
-    #[account(mut)]
+    #[account(
+        mut,
+        address = crucible.lp_token_mint @ InfernoCrucibleError::InvalidMint
+    )]
     pub lp_token_mint: Box<Account<'info, Mint>>,
-    #[account(mut)]
-    pub user_lp_token_account: Box<Account<'info, TokenAccount>>,
     /// Inferno LP Position - uses nonce to allow multiple positions per user
     #[account(
         init,
@@ -1138,6 +1359,16 @@ pub struct OpenInfernoLPPosition<'info> {
         bump
     )]
     pub position: Box<Account<'info, InfernoLPPositionAccount>>,
+    /// Position's LP tokens, held by the crucible authority until close or liquidation
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"lp_escrow", position.key().as_ref()],
+        bump,
+        token::mint = lp_token_mint,
+        token::authority = crucible_authority,
+    )]
+    pub lp_escrow: Box<Account<'info, TokenAccount>>,
     /// CHECK: Crucible authority PDA
     #[account(
         seeds = [b"crucible", base_mint.key().as_ref()],
@@ -1153,11 +1384,30 @@ pub struct OpenInfernoLPPosition<'info> {
     pub treasury_base: Box<Account<'info, TokenAccount>>,
     #[account(
         mut,
-        constraint = treasury_usdc.mint == user_usdc_account.mint @ InfernoCrucibleError::InvalidTreasury
+        constraint = treasury_usdc.mint == user_usdc_account.mint @ InfernoCrucibleError::InvalidTreasury,
+        constraint = treasury_usdc.key() == forge_core::fees::fee_vault_address(&treasury_usdc.mint) @ InfernoCrucibleError::InvalidTreasury
     )]
     pub treasury_usdc: Box<Account<'info, TokenAccount>>,
+    /// CHECK: Lending pool account - validated by the lending program
+    #[account(mut)]
+    pub lending_market: UncheckedAccount<'info>,
+    /// CHECK: Borrower account for this position - validated (and created if needed) by the lending program
+    #[account(mut)]
+    pub borrower_account: UncheckedAccount<'info>,
+    /// CHECK: SECURITY FIX - Lending pool vault PDA of lending_market, never another pool token account
+    #[account(
+        mut,
+        address = lending_pool_usdc::pool_vault_address(&lending_market.key()) @ InfernoCrucibleError::InvalidLendingProgram
+    )]
+    pub lending_vault: UncheckedAccount<'info>,
+    /// CHECK: Lending pool registry entry for this crucible's borrow authority - validated by the lending program
+    #[account(mut)]
+    pub authorized_borrower: UncheckedAccount<'info>,
+    pub lending_program: Program<'info, LendingPoolUsdc>,
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
+    /// CHECK: Forge protocol PDA - supplies protocol_fee_rate and the kill switch, validated in forge_core::load_protocol
+    pub forge_protocol: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
@@ -1185,8 +1435,20 @@ pub struct CloseInfernoLPPosition<'info> {
     pub user_base_token_account: Box<Account<'info, TokenAccount>>,
     #[account(mut)]
     pub user_usdc_account: Box<Account<'info, TokenAccount>>,
+    /// CHECK: Position's LP escrow - empty for positions opened before LP escrow
+    #[account(
+        mut,
+        seeds = [b"lp_escrow", position.key().as_ref()],
+        bump,
+    )]
+    pub lp_escrow: UncheckedAccount<'info>,
+    /// Owner's LP token account - only for positions opened before LP escrow
     #[account(mut)]
-    pub user_lp_token_account: Box<Account<'info, TokenAccount>>,
+    pub user_lp_token_account: Option<Box<Account<'info, TokenAccount>>>,
+    #[account(
+        mut,
+        address = crucible.lp_token_mint @ InfernoCrucibleError::InvalidMint
+    )]
     pub lp_token_mint: Box<Account<'info, Mint>>,
     #[account(mut)]
     pub crucible_base_vault: Box<Account<'info, TokenAccount>>,
@@ -1207,21 +1469,33 @@ pub struct CloseInfernoLPPosition<'info> {
     pub treasury_base: Box<Account<'info, TokenAccount>>,
     #[account(
         mut,
-        constraint = treasury_usdc.mint == user_usdc_account.mint @ InfernoCrucibleError::InvalidTreasury
+        constraint = treasury_usdc.mint == user_usdc_account.mint @ InfernoCrucibleError::InvalidTreasury,
+        constraint = treasury_usdc.key() == forge_core::fees::fee_vault_address(&treasury_usdc.mint) @ InfernoCrucibleError::InvalidTreasury
     )]
     pub treasury_usdc: Box<Account<'info, TokenAccount>>,
     /// CHECK: Lending market (optional for 1x positions)
     #[account(mut)]
     pub lending_market: UncheckedAccount<'info>,
-    /// CHECK: Borrower account (optional for 1x positions)
+    /// CHECK: Borrower account for this position (optional for 1x positions) - validated by lending_pool_usdc::position_borrower_account
     #[account(mut)]
     pub borrower_account: UncheckedAccount<'info>,
-    /// CHECK: Lending vault (optional for 1x positions)
+    /// CHECK: Owner's legacy borrower account - only for positions that borrowed before per-position debt accounts
     #[account(mut)]
+    pub legacy_borrower_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: SECURITY FIX - Lending pool vault PDA of lending_market, never another pool token account
+    #[account(
+        mut,
+        address = lending_pool_usdc::pool_vault_address(&lending_market.key()) @ InfernoCrucibleError::InvalidLendingProgram
+    )]
     pub lending_vault: UncheckedAccount<'info>,
+    /// CHECK: Credit line the debt was drawn on - validated by the lending program
+    #[account(mut)]
+    pub authorized_borrower: Option<UncheckedAccount<'info>>,
     /// CHECK: Lending program (optional for 1x positions)
     pub lending_program: UncheckedAccount<'info>,
     pub token_program: Program<'info, Token>,
+    /// CHECK: Forge protocol PDA - supplies protocol_fee_rate and the kill switch, validated in forge_core::load_protocol
+    pub forge_protocol: UncheckedAccount<'info>,
 }
 
 /// Legacy close accounts - for positions created WITHOUT nonce in seeds
@@ -1272,7 +1546,8 @@ pub struct CloseInfernoLPPositionLegacy<'info> {
     pub treasury_base: Box<Account<'info, TokenAccount>>,
     #[account(
         mut,
-        constraint = treasury_usdc.mint == user_usdc_account.mint @ InfernoCrucibleError::InvalidTreasury
+        constraint = treasury_usdc.mint == user_usdc_account.mint @ InfernoCrucibleError::InvalidTreasury,
+        constraint = treasury_usdc.key() == forge_core::fees::fee_vault_address(&treasury_usdc.mint) @ InfernoCrucibleError::InvalidTreasury
     )]
     pub treasury_usdc: Box<Account<'info, TokenAccount>>,
     /// CHECK: Lending market (optional for 1x positions)
@@ -1281,12 +1556,168 @@ pub struct CloseInfernoLPPositionLegacy<'info> {
     /// CHECK: Borrower account (optional for 1x positions)
     #[account(mut)]
     pub borrower_account: UncheckedAccount<'info>,
-    /// CHECK: Lending vault (optional for 1x positions)
-    #[account(mut)]
+    /// CHECK: SECURITY FIX - Lending pool vault PDA of lending_market, never another pool token account
+    #[account(
+        mut,
+        address = lending_pool_usdc::pool_vault_address(&lending_market.key()) @ InfernoCrucibleError::InvalidLendingProgram
+    )]
     pub lending_vault: UncheckedAccount<'info>,
+    /// CHECK: Credit line the debt was drawn on - validated by the lending program
+    #[account(mut)]
+    pub authorized_borrower: Option<UncheckedAccount<'info>>,
     /// CHECK: Lending program (optional for 1x positions)
     pub lending_program: UncheckedAccount<'info>,
     pub token_program: Program<'info, Token>,
+    /// CHECK: Forge protocol PDA - supplies protocol_fee_rate and the kill switch, validated in forge_core::load_protocol
+    pub forge_protocol: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(position_nonce: u64)]
+pub struct EscrowLegacyLPTokens<'info> {
+    #[account(
+        seeds = [b"crucible", base_mint.key().as_ref()],
+        bump,
+    )]
+    pub crucible: Box<Account<'info, InfernoCrucible>>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub base_mint: Box<Account<'info, Mint>>,
+    #[account(
+        seeds = [b"lp_position", user.key().as_ref(), base_mint.key().as_ref(), &position_nonce.to_le_bytes()],
+        bump = position.bump,
+        constraint = position.owner == user.key() @ InfernoCrucibleError::Unauthorized,
+        constraint = position.nonce == position_nonce @ InfernoCrucibleError::PositionNotFound,
+    )]
+    pub position: Box<Account<'info, InfernoLPPositionAccount>>,
+    #[account(
+        address = crucible.lp_token_mint @ InfernoCrucibleError::InvalidMint
+    )]
+    pub lp_token_mint: Box<Account<'info, Mint>>,
+    /// Position's LP escrow - must not exist yet, so positions opened with escrow are refused
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"lp_escrow", position.key().as_ref()],
+        bump,
+        token::mint = lp_token_mint,
+        token::authority = crucible_authority,
+    )]
+    pub lp_escrow: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = user_lp_token_account.mint == lp_token_mint.key() @ InfernoCrucibleError::InvalidMint,
+        constraint = user_lp_token_account.owner == user.key() @ InfernoCrucibleError::Unauthorized
+    )]
+    pub user_lp_token_account: Box<Account<'info, TokenAccount>>,
+    /// CHECK: Crucible authority PDA
+    #[account(
+        seeds = [b"crucible", base_mint.key().as_ref()],
+        bump,
+    )]
+    pub crucible_authority: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Keeper liquidation accounts - the position owner does not sign
+#[derive(Accounts)]
+#[instruction(position_nonce: u64)]
+pub struct LiquidateInfernoLPPosition<'info> {
+    #[account(
+        mut,
+        seeds = [b"crucible", base_mint.key().as_ref()],
+        bump,
+    )]
+    pub crucible: Box<Account<'info, InfernoCrucible>>,
+    #[account(mut)]
+    pub liquidator: Signer<'info>,
+    pub base_mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [b"lp_position", position_owner.key().as_ref(), base_mint.key().as_ref(), &position_nonce.to_le_bytes()],
+        bump = position.bump,
+        constraint = position.owner == position_owner.key() @ InfernoCrucibleError::Unauthorized,
+        constraint = position.nonce == position_nonce @ InfernoCrucibleError::PositionNotFound,
+    )]
+    pub position: Box<Account<'info, InfernoLPPositionAccount>>,
+    /// CHECK: Position owner (borrower) - validated against position.owner, receives the LP escrow rent
+    #[account(mut)]
+    pub position_owner: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = crucible.lp_token_mint @ InfernoCrucibleError::InvalidMint
+    )]
+    pub lp_token_mint: Box<Account<'info, Mint>>,
+    /// CHECK: Position's LP escrow - required, positions opened before LP escrow must be escrowed first
+    #[account(
+        mut,
+        seeds = [b"lp_escrow", position.key().as_ref()],
+        bump,
+    )]
+    pub lp_escrow: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = liquidator_base_token_account.mint == base_mint.key() @ InfernoCrucibleError::InvalidMint
+    )]
+    pub liquidator_base_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = liquidator_usdc_account.mint == crucible_usdc_vault.mint @ InfernoCrucibleError::InvalidMint
+    )]
+    pub liquidator_usdc_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = owner_base_token_account.owner == position.owner @ InfernoCrucibleError::Unauthorized,
+        constraint = owner_base_token_account.mint == base_mint.key() @ InfernoCrucibleError::InvalidMint
+    )]
+    pub owner_base_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = owner_usdc_account.owner == position.owner @ InfernoCrucibleError::Unauthorized,
+        constraint = owner_usdc_account.mint == crucible_usdc_vault.mint @ InfernoCrucibleError::InvalidMint
+    )]
+    pub owner_usdc_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = crucible_base_vault.key() == crucible.vault @ InfernoCrucibleError::InvalidConfig
+    )]
+    pub crucible_base_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = crucible_usdc_vault.key() == crucible.usdc_vault @ InfernoCrucibleError::InvalidConfig
+    )]
+    pub crucible_usdc_vault: Box<Account<'info, TokenAccount>>,
+    /// CHECK: Crucible authority PDA
+    #[account(
+        seeds = [b"crucible", base_mint.key().as_ref()],
+        bump,
+    )]
+    pub crucible_authority: UncheckedAccount<'info>,
+    /// CHECK: Optional oracle account for price feeds
+    pub oracle: Option<UncheckedAccount<'info>>,
+    /// CHECK: Lending pool account - validated by the lending program
+    #[account(mut)]
+    pub lending_market: UncheckedAccount<'info>,
+    /// CHECK: Borrower account for this position - validated by lending_pool_usdc::position_borrower_account
+    #[account(mut)]
+    pub borrower_account: UncheckedAccount<'info>,
+    /// CHECK: Owner's legacy borrower account - only for positions that borrowed before per-position debt accounts
+    #[account(mut)]
+    pub legacy_borrower_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: SECURITY FIX - Lending pool vault PDA of lending_market, never another pool token account
+    #[account(
+        mut,
+        address = lending_pool_usdc::pool_vault_address(&lending_market.key()) @ InfernoCrucibleError::InvalidLendingProgram
+    )]
+    pub lending_vault: UncheckedAccount<'info>,
+    /// CHECK: Credit line the debt was drawn on - validated by the lending program
+    #[account(mut)]
+    pub authorized_borrower: Option<UncheckedAccount<'info>>,
+    pub lending_program: Program<'info, LendingPoolUsdc>,
+    pub token_program: Program<'info, Token>,
+    /// CHECK: Optional Forge protocol PDA - state changes are refused while the protocol is inactive
+    pub forge_protocol: Option<UncheckedAccount<'info>>,
 }
 
 #[derive(Accounts)]
@@ -1309,6 +1740,7 @@ pub struct HealthCheckInferno<'info> {
 fn calculate_ltv_bps(
     crucible: &InfernoCrucible,
     position: &InfernoLPPositionAccount,
+    debt: u64,
     base_mint: &Pubkey,
     oracle: &Option<UncheckedAccount>,
 ) -> Result<u64> {
@@ -1329,7 +1761,7 @@ fn calculate_ltv_bps(
         .ok_or(ProgramError::ArithmeticOverflow)?;
     require!(total_value > 0, InfernoCrucibleError::InvalidAmount);
 
-    let ltv_bps = (position.borrowed_usdc as u128)
+    let ltv_bps = (debt as u128)
         .checked_mul(10_000)
         .and_then(|v| v.checked_div(total_value))
         .ok_or(ProgramError::ArithmeticOverflow)?;
@@ -1359,3 +1791,54 @@ pub struct InfernoLPPositionClosed {
     pub usdc_amount_returned: u64,
     pub total_fee: u64,
 }
+
+#[event]
+pub struct InfernoLPTokensEscrowed {
+    pub position_id: u64,
+    pub owner: Pubkey,
+    pub crucible: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct InfernoLPPositionLiquidated {
+    pub position_id: u64,
+    pub owner: Pubkey,
+    pub liquidator: Pubkey,
+    pub crucible: Pubkey,
+    pub repaid_usdc: u64,
+    pub base_seized: u64,
+    pub usdc_seized: u64,
+    pub base_returned: u64,
+    pub usdc_returned: u64,
+    pub liquidation_bonus_bps: u64,
+    pub ltv_bps: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // $100 per base token, USDC with 6 decimals
+    const PRICE: u64 = 100_000_000;
+
+    #[test]
+    fn seizure_takes_usdc_before_base() {
+        // 1 base token ($100) + $100 USDC, repaying $80 at a 5% bonus seizes $84 of USDC
+        let (base, usdc) = liquidation_seizure(80_000_000, LAMPORTS_PER_SOL, 100_000_000, PRICE, 500).unwrap();
+        assert_eq!((base, usdc), (0, 84_000_000));
+
+        // Repaying $150 seizes all USDC and $57.50 of base
+        let (base, usdc) = liquidation_seizure(150_000_000, LAMPORTS_PER_SOL, 100_000_000, PRICE, 500).unwrap();
+        assert_eq!(usdc, 100_000_000);
+        assert_eq!(base, LAMPORTS_PER_SOL * 575 / 1_000);
+    }
+
+    #[test]
+    fn seizure_is_capped_at_position_value() {
+        // Debt plus interest exceeds the $200 position: everything is seized, nothing more
+        let (base, usdc) = liquidation_seizure(250_000_000, LAMPORTS_PER_SOL, 100_000_000, PRICE, 500).unwrap();
+        assert_eq!((base, usdc), (LAMPORTS_PER_SOL, 100_000_000));
+        assert!(liquidation_seizure(1, 1, 1, 0, 500).is_err());
+    }
+}
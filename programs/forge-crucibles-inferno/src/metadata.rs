@@ -3,6 +3,8 @@ use anchor_spl::token::Mint;
 use anchor_spl::metadata::{create_metadata_accounts_v3, CreateMetadataAccountsV3};
 use mpl_token_metadata::types::DataV2;
 
+use forge_core::pause::{decode_pause_flags, PAUSE_NONE};
+
 use crate::state::{InfernoCrucible, InfernoCrucibleError};
 
 /// Create Metaplex Token Metadata for an LP token mint
@@ -15,7 +17,7 @@ pub fn create_lp_metadata(
     is_mutable: bool,
 ) -> Result<()> {
     let crucible = &ctx.accounts.crucible;
-    require!(!crucible.paused, InfernoCrucibleError::ProtocolPaused);
+    require!(decode_pause_flags(crucible.pause_flags) == PAUSE_NONE, InfernoCrucibleError::ProtocolPaused);
 
     require!(
         ctx.accounts.lp_token_mint.key() == crucible.lp_token_mint,
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use forge_core::pause::is_paused;
 
 #[account]
 pub struct InfernoCrucible {
@@ -13,13 +14,47 @@ pub struct InfernoCrucible {
     pub exchange_rate: u64, // Scaled by 1_000_000 (1.0 = 1_000_000)
     pub last_update_slot: u64,
     pub fee_rate: u64, // Fee rate (e.g., 200 = 0.2% = 2 bps)
-    pub paused: bool,
+    pub pause_flags: u8, // forge_core::pause PAUSE_* flags, stored with encode_pause_flags
     pub expected_vault_balance: u64,
     pub expected_usdc_vault_balance: u64,
     pub oracle: Option<Pubkey>,
     pub treasury_base: Pubkey,
     pub treasury_usdc: Pubkey,
     pub total_fees_accrued: u64,
+    pub authority: Pubkey, // Admin authority for treasury/oracle updates
+    pub pending_authority: Option<Pubkey>, // Proposed authority awaiting accept_authority
+    pub liquidation_bonus_bps: u64, // Bonus paid to keepers on seized collateral (500 = 5%)
+}
+
+/// Legacy Inferno crucible struct (before authority and liquidation bonus were added)
+/// Used by migrate_inferno_crucible to upgrade existing accounts
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct InfernoCrucibleLegacy {
+    pub base_mint: Pubkey,
+    pub lp_token_mint: Pubkey,
+    pub vault: Pubkey,
+    pub usdc_vault: Pubkey,
+    pub vault_bump: u8,
+    pub bump: u8,
+    pub total_lp_token_supply: u64,
+    pub total_lp_positions: u64,
+    pub exchange_rate: u64,
+    pub last_update_slot: u64,
+    pub fee_rate: u64,
+    pub pause_flags: u8,
+    pub expected_vault_balance: u64,
+    pub expected_usdc_vault_balance: u64,
+    pub oracle: Option<Pubkey>,
+    pub treasury_base: Pubkey,
+    pub treasury_usdc: Pubkey,
+    pub total_fees_accrued: u64,
+}
+
+impl InfernoCrucibleLegacy {
+    pub const LEN: usize = InfernoCrucible::LEN -
+        32 - // authority
+        33 - // pending_authority
+        8;   // liquidation_bonus_bps
 }
 
 #[account]
@@ -69,14 +104,18 @@ impl InfernoCrucible {
         8 +  // exchange_rate
         8 +  // last_update_slot
         8 +  // fee_rate
-        1 +  // paused
+        1 +  // pause_flags
         8 +  // expected_vault_balance
         8 +  // expected_usdc_vault_balance
         1 +  // oracle option discriminator
         32 + // oracle pubkey (if Some)
         32 + // treasury_base
         32 + // treasury_usdc
-        8;   // total_fees_accrued
+        8 +  // total_fees_accrued
+        32 + // authority
+        1 +  // pending_authority option discriminator
+        32 + // pending_authority pubkey (if Some)
+        8;   // liquidation_bonus_bps
 }
 
 /// Legacy position account struct (for positions created before nonce was added)
@@ -114,6 +153,36 @@ impl InfernoLPPositionAccountLegacy {
         1;   // bump
 }
 
+/// Refuse `operation` (forge_core::pause PAUSE_* flags) while it is paused on the crucible
+pub fn require_not_paused(pause_flags: u8, operation: u8) -> Result<()> {
+    require!(!is_paused(pause_flags, operation), InfernoCrucibleError::ProtocolPaused);
+    Ok(())
+}
+
+/// Protocol share of fees in basis points, from forge-core `protocol_fee_rate`
+/// Fee-charging instructions require the protocol account, so the kill switch always applies.
+pub fn load_protocol_fee_bps(forge_protocol: &AccountInfo) -> Result<u64> {
+    let protocol = forge_core::load_protocol(forge_protocol)?;
+    require!(protocol.is_active, InfernoCrucibleError::ProtocolInactive);
+    require!(protocol.protocol_fee_rate <= 10_000, InfernoCrucibleError::InvalidConfig);
+    Ok(protocol.protocol_fee_rate)
+}
+
+/// Split a fee into (vault share, protocol share) - rounding dust goes to the protocol
+pub fn split_fee_value(fee: u128, protocol_fee_bps: u64) -> Result<(u128, u128)> {
+    let vault_share_bps = 10_000u64
+        .checked_sub(protocol_fee_bps)
+        .ok_or(InfernoCrucibleError::InvalidConfig)?;
+    let vault_share = fee
+        .checked_mul(vault_share_bps as u128)
+        .and_then(|v| v.checked_div(10_000u128))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let protocol_share = fee
+        .checked_sub(vault_share)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    Ok((vault_share, protocol_share))
+}
+
 #[error_code]
 pub enum InfernoCrucibleError {
     #[msg("Insufficient liquidity in vault")]
@@ -160,4 +229,14 @@ pub enum InfernoCrucibleError {
     RepayAmountExceedsDebt,
     #[msg("Position is not liquidatable")]
     PositionNotLiquidatable,
+    #[msg("No pending authority transfer")]
+    NoPendingAuthority,
+    #[msg("Liquidation bonus exceeds maximum")]
+    InvalidLiquidationBonus,
+    #[msg("Forge protocol is inactive")]
+    ProtocolInactive,
+    #[msg("Position debt must be fully repaid before closing")]
+    OutstandingDebt,
+    #[msg("Position's LP tokens must be escrowed first")]
+    LPEscrowRequired,
 }
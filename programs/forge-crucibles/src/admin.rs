@@ -0,0 +1,502 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_spl::token::TokenAccount;
+use forge_core::pause::{decode_pause_flags, encode_pause_flags};
+use forge_core::ForgeProtocol;
+
+use crate::state::{
+    Crucible, CrucibleError, FeeSchedule, LegacyCrucible, LiquidationConfig, PreFeeScheduleCrucible,
+    PreLiquidationConfigCrucible,
+};
+
+/// Crucible account in the legacy (pre-LP token), pre-fee-schedule, pre-liquidation-config
+/// or current layout. Admin instructions must work on all of them so unmigrated crucibles
+/// can still be paused.
+enum AdminCrucible {
+    Legacy(LegacyCrucible),
+    PreFeeSchedule(PreFeeScheduleCrucible),
+    PreLiquidationConfig(PreLiquidationConfigCrucible),
+    Current(Crucible),
+}
+
+impl AdminCrucible {
+    fn load(crucible: &AccountInfo, program_id: &Pubkey) -> Result<Self> {
+        // SECURITY FIX: Only crucible accounts owned by this program can be administered
+        require_keys_eq!(*crucible.owner, *program_id, CrucibleError::InvalidConfig);
+
+        let data = crucible.try_borrow_data()?;
+        let loaded = if data.len() == LegacyCrucible::LEN {
+            AdminCrucible::Legacy(LegacyCrucible::deserialize(&mut &data[8..])?)
+        } else if data.len() == PreFeeScheduleCrucible::LEN {
+            require!(data[..8] == Crucible::DISCRIMINATOR, CrucibleError::InvalidConfig);
+            AdminCrucible::PreFeeSchedule(PreFeeScheduleCrucible::deserialize(&mut &data[8..])?)
+        } else if data.len() == PreLiquidationConfigCrucible::LEN {
+            require!(data[..8] == Crucible::DISCRIMINATOR, CrucibleError::InvalidConfig);
+            AdminCrucible::PreLiquidationConfig(PreLiquidationConfigCrucible::deserialize(&mut &data[8..])?)
+        } else {
+            AdminCrucible::Current(Crucible::try_deserialize(&mut &data[..])?)
+        };
+        drop(data);
+
+        // SECURITY FIX: Validate crucible is the canonical PDA for its base mint
+        let (base_mint, bump) = match &loaded {
+            AdminCrucible::Legacy(c) => (c.base_mint, c.bump),
+            AdminCrucible::PreFeeSchedule(c) => (c.base_mint, c.bump),
+            AdminCrucible::PreLiquidationConfig(c) => (c.base_mint, c.bump),
+            AdminCrucible::Current(c) => (c.base_mint, c.bump),
+        };
+        let expected = Pubkey::create_program_address(
+            &[b"crucible", base_mint.as_ref(), &[bump]],
+            program_id,
+        )
+        .map_err(|_| CrucibleError::InvalidConfig)?;
+        require_keys_eq!(expected, crucible.key(), CrucibleError::InvalidConfig);
+
+        Ok(loaded)
+    }
+
+    fn save(&self, crucible: &AccountInfo) -> Result<()> {
+        let mut data = crucible.try_borrow_mut_data()?;
+        let mut slice = &mut data[8..]; // Skip discriminator
+        match self {
+            AdminCrucible::Legacy(c) => c.serialize(&mut slice)?,
+            AdminCrucible::PreFeeSchedule(c) => c.serialize(&mut slice)?,
+            AdminCrucible::PreLiquidationConfig(c) => c.serialize(&mut slice)?,
+            AdminCrucible::Current(c) => c.serialize(&mut slice)?,
+        }
+        Ok(())
+    }
+
+    fn base_mint(&self) -> Pubkey {
+        match self {
+            AdminCrucible::Legacy(c) => c.base_mint,
+            AdminCrucible::PreFeeSchedule(c) => c.base_mint,
+            AdminCrucible::PreLiquidationConfig(c) => c.base_mint,
+            AdminCrucible::Current(c) => c.base_mint,
+        }
+    }
+
+    fn pause_flags_mut(&mut self) -> &mut u8 {
+        match self {
+            AdminCrucible::Legacy(c) => &mut c.pause_flags,
+            AdminCrucible::PreFeeSchedule(c) => &mut c.pause_flags,
+            AdminCrucible::PreLiquidationConfig(c) => &mut c.pause_flags,
+            AdminCrucible::Current(c) => &mut c.pause_flags,
+        }
+    }
+
+    fn fee_rate_mut(&mut self) -> &mut u64 {
+        match self {
+            AdminCrucible::Legacy(c) => &mut c.fee_rate,
+            AdminCrucible::PreFeeSchedule(c) => &mut c.fee_rate,
+            AdminCrucible::PreLiquidationConfig(c) => &mut c.fee_rate,
+            AdminCrucible::Current(c) => &mut c.fee_rate,
+        }
+    }
+
+    fn oracle_mut(&mut self) -> &mut Option<Pubkey> {
+        match self {
+            AdminCrucible::Legacy(c) => &mut c.oracle,
+            AdminCrucible::PreFeeSchedule(c) => &mut c.oracle,
+            AdminCrucible::PreLiquidationConfig(c) => &mut c.oracle,
+            AdminCrucible::Current(c) => &mut c.oracle,
+        }
+    }
+
+    fn treasury_mut(&mut self) -> &mut Pubkey {
+        match self {
+            AdminCrucible::Legacy(c) => &mut c.treasury,
+            AdminCrucible::PreFeeSchedule(c) => &mut c.treasury,
+            AdminCrucible::PreLiquidationConfig(c) => &mut c.treasury,
+            AdminCrucible::Current(c) => &mut c.treasury,
+        }
+    }
+}
+
+/// Set which crucible operations are paused (emergency function)
+/// e.g. PAUSE_WIND_DOWN keeps withdrawals, closes and liquidations open during an incident
+pub fn set_crucible_pause_flags(ctx: Context<CrucibleAdmin>, pause_flags: u8) -> Result<()> {
+    let crucible_info = ctx.accounts.crucible.to_account_info();
+    let mut crucible = AdminCrucible::load(&crucible_info, ctx.program_id)?;
+
+    // SECURITY FIX: Prevent redundant state changes
+    let current = crucible.pause_flags_mut();
+    let old_pause_flags = decode_pause_flags(*current);
+    require!(old_pause_flags != pause_flags, CrucibleError::InvalidConfig);
+    *current = encode_pause_flags(pause_flags);
+    crucible.save(&crucible_info)?;
+
+    emit!(CruciblePausedUpdated {
+        crucible: crucible_info.key(),
+        authority: ctx.accounts.authority.key(),
+        old_pause_flags,
+        pause_flags,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Crucible pause flags set to: {:#010b}", pause_flags);
+    Ok(())
+}
+
+/// Update the crucible fee rate
+pub fn update_fee_rate(ctx: Context<CrucibleAdmin>, new_fee_rate: u64) -> Result<()> {
+    // SECURITY FIX (AUDIT-011): Validate fee_rate bounds (0-10,000 bps)
+    require!(new_fee_rate <= 10_000, CrucibleError::InvalidConfig);
+
+    let crucible_info = ctx.accounts.crucible.to_account_info();
+    let mut crucible = AdminCrucible::load(&crucible_info, ctx.program_id)?;
+
+    let fee_rate = crucible.fee_rate_mut();
+    let old_fee_rate = *fee_rate;
+    require!(old_fee_rate != new_fee_rate, CrucibleError::InvalidConfig);
+    *fee_rate = new_fee_rate;
+    crucible.save(&crucible_info)?;
+
+    emit!(CrucibleFeeRateUpdated {
+        crucible: crucible_info.key(),
+        authority: ctx.accounts.authority.key(),
+        old_fee_rate,
+        new_fee_rate,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Update (or disable with None) the crucible price oracle
+/// A new oracle must be passed as `new_oracle` and be a valid Pyth PriceUpdateV2 account
+pub fn update_oracle(ctx: Context<UpdateCrucibleOracle>, new_oracle: Option<Pubkey>) -> Result<()> {
+    if let Some(oracle_key) = new_oracle {
+        let oracle = ctx.accounts.new_oracle
+            .as_ref()
+            .ok_or(CrucibleError::InvalidOraclePrice)?;
+        require_keys_eq!(oracle.key(), oracle_key, CrucibleError::InvalidOraclePrice);
+
+        // SECURITY FIX: Reject accounts that are not Pyth price updates
+        forge_core::oracle::validate_price_update(oracle)
+            .map_err(|_| CrucibleError::InvalidOraclePrice)?;
+    }
+
+    let crucible_info = ctx.accounts.crucible.to_account_info();
+    let mut crucible = AdminCrucible::load(&crucible_info, ctx.program_id)?;
+
+    let oracle = crucible.oracle_mut();
+    let old_oracle = *oracle;
+    require!(old_oracle != new_oracle, CrucibleError::InvalidConfig);
+    *oracle = new_oracle;
+    crucible.save(&crucible_info)?;
+
+    emit!(CrucibleOracleUpdated {
+        crucible: crucible_info.key(),
+        authority: ctx.accounts.authority.key(),
+        old_oracle,
+        new_oracle,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Point the crucible's protocol fees at the forge-core fee vault for its base mint
+/// Used to move crucibles created with an arbitrary treasury onto the protocol fee vault.
+pub fn update_treasury(ctx: Context<UpdateCrucibleTreasury>) -> Result<()> {
+    let crucible_info = ctx.accounts.crucible.to_account_info();
+    let mut crucible = AdminCrucible::load(&crucible_info, ctx.program_id)?;
+
+    // SECURITY FIX: Treasury must hold the crucible's base token, matching fee transfers
+    require_keys_eq!(
+        ctx.accounts.new_treasury.mint,
+        crucible.base_mint(),
+        CrucibleError::InvalidTreasury
+    );
+    // Protocol fees only go to the PDA-owned fee vault, paid out by forge_core::sweep_fees or distribute
+    require_keys_eq!(
+        ctx.accounts.new_treasury.key(),
+        forge_core::fees::fee_vault_address(&crucible.base_mint()),
+        CrucibleError::InvalidTreasury
+    );
+
+    let new_treasury = ctx.accounts.new_treasury.key();
+    let treasury = crucible.treasury_mut();
+    let old_treasury = *treasury;
+    require!(old_treasury != new_treasury, CrucibleError::InvalidConfig);
+    *treasury = new_treasury;
+    crucible.save(&crucible_info)?;
+
+    emit!(CrucibleTreasuryUpdated {
+        crucible: crucible_info.key(),
+        authority: ctx.accounts.authority.key(),
+        old_treasury,
+        new_treasury,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Replace the crucible fee schedule - crucible must be migrated to the current layout
+pub fn update_fee_schedule(ctx: Context<CrucibleAdmin>, new_fee_schedule: FeeSchedule) -> Result<()> {
+    new_fee_schedule.validate()?;
+
+    let crucible_info = ctx.accounts.crucible.to_account_info();
+    let mut crucible = match AdminCrucible::load(&crucible_info, ctx.program_id)? {
+        AdminCrucible::Current(c) => c,
+        _ => return err!(CrucibleError::CrucibleNotMigrated),
+    };
+
+    let old_fee_schedule = crucible.fee_schedule;
+    require!(old_fee_schedule != new_fee_schedule, CrucibleError::InvalidConfig);
+    crucible.fee_schedule = new_fee_schedule;
+    AdminCrucible::Current(crucible).save(&crucible_info)?;
+
+    emit!(CrucibleFeeScheduleUpdated {
+        crucible: crucible_info.key(),
+        authority: ctx.accounts.authority.key(),
+        old_fee_schedule,
+        new_fee_schedule,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Grow a crucible created before fee schedules existed, starting it on the default schedule
+pub fn migrate_crucible_fee_schedule(ctx: Context<MigrateCrucibleFeeSchedule>) -> Result<()> {
+    let crucible_info = ctx.accounts.crucible.to_account_info();
+    let legacy = match AdminCrucible::load(&crucible_info, ctx.program_id)? {
+        AdminCrucible::PreFeeSchedule(c) => c,
+        _ => return err!(CrucibleError::InvalidConfig),
+    };
+    let migrated = legacy
+        .with_fee_schedule(FeeSchedule::DEFAULT)
+        .with_liquidation_config(LiquidationConfig::DEFAULT);
+
+    grow_to_current_layout(
+        &crucible_info,
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+    AdminCrucible::Current(migrated).save(&crucible_info)?;
+
+    msg!("Migrated crucible {} to the default fee schedule", crucible_info.key());
+    Ok(())
+}
+
+/// Replace the crucible's LVF liquidation config - crucible must be migrated to the current layout
+pub fn update_liquidation_config(
+    ctx: Context<CrucibleAdmin>,
+    new_liquidation_config: LiquidationConfig,
+) -> Result<()> {
+    new_liquidation_config.validate()?;
+
+    let crucible_info = ctx.accounts.crucible.to_account_info();
+    let mut crucible = match AdminCrucible::load(&crucible_info, ctx.program_id)? {
+        AdminCrucible::Current(c) => c,
+        _ => return err!(CrucibleError::CrucibleNotMigrated),
+    };
+
+    let old_liquidation_config = crucible.liquidation_config;
+    require!(old_liquidation_config != new_liquidation_config, CrucibleError::InvalidConfig);
+    crucible.liquidation_config = new_liquidation_config;
+    AdminCrucible::Current(crucible).save(&crucible_info)?;
+
+    emit!(CrucibleLiquidationConfigUpdated {
+        crucible: crucible_info.key(),
+        authority: ctx.accounts.authority.key(),
+        old_liquidation_config,
+        new_liquidation_config,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Grow a crucible created before liquidation configs existed, starting it on the default config
+pub fn migrate_crucible_liquidation_config(ctx: Context<MigrateCrucibleLiquidationConfig>) -> Result<()> {
+    let crucible_info = ctx.accounts.crucible.to_account_info();
+    let legacy = match AdminCrucible::load(&crucible_info, ctx.program_id)? {
+        AdminCrucible::PreLiquidationConfig(c) => c,
+        _ => return err!(CrucibleError::InvalidConfig),
+    };
+    let migrated = legacy.with_liquidation_config(LiquidationConfig::DEFAULT);
+    grow_to_current_layout(
+        &crucible_info,
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+    AdminCrucible::Current(migrated).save(&crucible_info)?;
+
+    msg!("Migrated crucible {} to the default liquidation config", crucible_info.key());
+    Ok(())
+}
+
+/// Realloc a crucible to `Crucible::LEN`, with the authority topping up rent
+fn grow_to_current_layout<'info>(
+    crucible_info: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let required_lamports = Rent::get()?.minimum_balance(Crucible::LEN);
+    let top_up = required_lamports.saturating_sub(crucible_info.lamports());
+    if top_up > 0 {
+        let cpi_accounts = anchor_lang::system_program::Transfer {
+            from: authority.clone(),
+            to: crucible_info.clone(),
+        };
+        let cpi_ctx = CpiContext::new(system_program.clone(), cpi_accounts);
+        anchor_lang::system_program::transfer(cpi_ctx, top_up)?;
+    }
+    crucible_info.realloc(Crucible::LEN, false)?;
+    Ok(())
+}
+
+/// Crucible admin accounts - the crucible authority is the Forge protocol authority
+#[derive(Accounts)]
+pub struct CrucibleAdmin<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"forge_protocol"],
+        bump = forge_protocol.bump,
+        seeds::program = forge_core::ID,
+        has_one = authority @ CrucibleError::Unauthorized,
+    )]
+    pub forge_protocol: Account<'info, ForgeProtocol>,
+
+    /// CHECK: Crucible account - legacy or current layout, validated in AdminCrucible::load
+    #[account(mut)]
+    pub crucible: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateCrucibleOracle<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"forge_protocol"],
+        bump = forge_protocol.bump,
+        seeds::program = forge_core::ID,
+        has_one = authority @ CrucibleError::Unauthorized,
+    )]
+    pub forge_protocol: Account<'info, ForgeProtocol>,
+
+    /// CHECK: Crucible account - legacy or current layout, validated in AdminCrucible::load
+    #[account(mut)]
+    pub crucible: UncheckedAccount<'info>,
+
+    /// CHECK: New Pyth price account - required when setting an oracle, validated in instruction
+    pub new_oracle: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateCrucibleTreasury<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"forge_protocol"],
+        bump = forge_protocol.bump,
+        seeds::program = forge_core::ID,
+        has_one = authority @ CrucibleError::Unauthorized,
+    )]
+    pub forge_protocol: Account<'info, ForgeProtocol>,
+
+    /// CHECK: Crucible account - legacy or current layout, validated in AdminCrucible::load
+    #[account(mut)]
+    pub crucible: UncheckedAccount<'info>,
+
+    pub new_treasury: Account<'info, TokenAccount>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateCrucibleFeeSchedule<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"forge_protocol"],
+        bump = forge_protocol.bump,
+        seeds::program = forge_core::ID,
+        has_one = authority @ CrucibleError::Unauthorized,
+    )]
+    pub forge_protocol: Account<'info, ForgeProtocol>,
+
+    /// CHECK: Pre-fee-schedule crucible account - validated in AdminCrucible::load
+    #[account(mut)]
+    pub crucible: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateCrucibleLiquidationConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"forge_protocol"],
+        bump = forge_protocol.bump,
+        seeds::program = forge_core::ID,
+        has_one = authority @ CrucibleError::Unauthorized,
+    )]
+    pub forge_protocol: Account<'info, ForgeProtocol>,
+
+    /// CHECK: Pre-liquidation-config crucible account - validated in AdminCrucible::load
+    #[account(mut)]
+    pub crucible: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct CruciblePausedUpdated {
+    pub crucible: Pubkey,
+    pub authority: Pubkey,
+    pub old_pause_flags: u8,
+    pub pause_flags: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CrucibleFeeRateUpdated {
+    pub crucible: Pubkey,
+    pub authority: Pubkey,
+    pub old_fee_rate: u64,
+    pub new_fee_rate: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CrucibleOracleUpdated {
+    pub crucible: Pubkey,
+    pub authority: Pubkey,
+    pub old_oracle: Option<Pubkey>,
+    pub new_oracle: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CrucibleTreasuryUpdated {
+    pub crucible: Pubkey,
+    pub authority: Pubkey,
+    pub old_treasury: Pubkey,
+    pub new_treasury: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CrucibleFeeScheduleUpdated {
+    pub crucible: Pubkey,
+    pub authority: Pubkey,
+    pub old_fee_schedule: FeeSchedule,
+    pub new_fee_schedule: FeeSchedule,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CrucibleLiquidationConfigUpdated {
+    pub crucible: Pubkey,
+    pub authority: Pubkey,
+    pub old_liquidation_config: LiquidationConfig,
+    pub new_liquidation_config: LiquidationConfig,
+    pub timestamp: i64,
+}
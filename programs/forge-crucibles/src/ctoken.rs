@@ -1,28 +1,21 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount, MintTo, Burn};
 use anchor_spl::associated_token::AssociatedToken;
-use crate::state::{Crucible, LegacyCrucible, CrucibleError};
+use crate::state::{Crucible, LegacyCrucible, CrucibleError, FeeSchedule, load_protocol_fee_bps, require_not_paused, split_fee};
+use forge_core::pause::{PAUSE_ARBITRAGE_DEPOSITS, PAUSE_DEPOSITS, PAUSE_WITHDRAWALS};
 
 // Fee and scaling constants
 const PRICE_SCALE_FACTOR: u64 = 1_000_000; // Scale for price/exchange rate precision (1.0 = 1_000_000)
-const WRAP_FEE_BPS: u64 = 50; // 0.5% wrap fee (50 basis points)
-const UNWRAP_FEE_BPS: u64 = 50; // 0.5% unwrap fee (50 basis points)
-const VAULT_FEE_SHARE_BPS: u64 = 8_000; // 80% vault share (8000 basis points)
-const PROTOCOL_FEE_SHARE_BPS: u64 = 2_000; // 20% protocol share (2000 basis points)
-const ARBITRAGE_VAULT_SHARE_BPS: u64 = 8_000; // 80% vault share for arbitrage (8000 basis points)
-const ARBITRAGE_TREASURY_SHARE_BPS: u64 = 2_000; // 20% treasury share for arbitrage (2000 basis points)
-const ARBITRAGE_REWARD_BPS: u64 = 100; // 1% arbitrageur reward (100 basis points)
 
 // Amount bounds to prevent dust and overflow attacks
 const MIN_DEPOSIT_AMOUNT: u64 = 1_000; // Minimum 1000 lamports (0.000001 SOL or equivalent)
-const MIN_CTOKEN_AMOUNT: u64 = 1_000; // Minimum cToken amount to mint/burn
 const MAX_DEPOSIT_AMOUNT: u64 = 1_000_000_000_000_000_000; // 1 billion tokens with 9 decimals
-const MAX_CTOKEN_AMOUNT: u64 = 1_000_000_000_000_000_000; // Maximum cToken amount to mint/burn
 
 /// Mint cToken when user deposits base token
 pub fn mint_ctoken(ctx: Context<MintCToken>, amount: u64) -> Result<()> {
-    // Check if crucible is paused
-    require!(!ctx.accounts.crucible.paused, CrucibleError::ProtocolPaused);
+    // Check if deposits are paused
+    require_not_paused(ctx.accounts.crucible.pause_flags, PAUSE_DEPOSITS)?;
+    let protocol_fee_bps = load_protocol_fee_bps(&ctx.accounts.forge_protocol)?;
     
     require!(
         amount >= MIN_DEPOSIT_AMOUNT && amount <= MAX_DEPOSIT_AMOUNT,
@@ -44,17 +37,11 @@ pub fn mint_ctoken(ctx: Context<MintCToken>, amount: u64) -> Result<()> {
     )?;
     
     let wrap_fee = amount
-        .checked_mul(WRAP_FEE_BPS)
+        .checked_mul(crucible.fee_schedule.wrap_fee_bps)
         .and_then(|v| v.checked_div(10_000u64))
         .ok_or(ProgramError::ArithmeticOverflow)?;
     
-    let vault_fee_share = wrap_fee
-        .checked_mul(VAULT_FEE_SHARE_BPS)
-        .and_then(|v| v.checked_div(10_000u64))
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    let protocol_fee_share = wrap_fee
-        .checked_sub(vault_fee_share)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let (vault_fee_share, protocol_fee_share) = split_fee(wrap_fee, protocol_fee_bps)?;
     
     // Net deposit after fee
     let net_deposit = amount
@@ -192,8 +179,9 @@ pub fn mint_ctoken(ctx: Context<MintCToken>, amount: u64) -> Result<()> {
 
 /// Burn cToken and return base tokens to user
 pub fn burn_ctoken(ctx: Context<BurnCToken>, ctokens_amount: u64) -> Result<()> {
-    // Check if crucible is paused
-    require!(!ctx.accounts.crucible.paused, CrucibleError::ProtocolPaused);
+    // Check if withdrawals are paused
+    require_not_paused(ctx.accounts.crucible.pause_flags, PAUSE_WITHDRAWALS)?;
+    let protocol_fee_bps = load_protocol_fee_bps(&ctx.accounts.forge_protocol)?;
     
     let crucible = &mut ctx.accounts.crucible;
     let clock = Clock::get()?;
@@ -216,19 +204,12 @@ pub fn burn_ctoken(ctx: Context<BurnCToken>, ctokens_amount: u64) -> Result<()>
         CrucibleError::InsufficientLiquidity
     );
     
-    const UNWRAP_FEE_BPS_SPECIAL: u64 = 75; // 0.75% unwrap fee (Note: Can be reduced to 0.3% after 5-day cooldown period)
     let unwrap_fee = base_to_return_before_fee
-        .checked_mul(UNWRAP_FEE_BPS_SPECIAL)
+        .checked_mul(crucible.fee_schedule.unwrap_fee_bps)
         .and_then(|v| v.checked_div(10_000u64))
         .ok_or(ProgramError::ArithmeticOverflow)?;
     
-    let vault_fee_share = unwrap_fee
-        .checked_mul(VAULT_FEE_SHARE_BPS)
-        .and_then(|v| v.checked_div(10_000u64))
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    let protocol_fee_share = unwrap_fee
-        .checked_sub(vault_fee_share)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let (vault_fee_share, protocol_fee_share) = split_fee(unwrap_fee, protocol_fee_bps)?;
     
     // Net amount to return to user (after fee)
     let base_to_return = base_to_return_before_fee
@@ -394,8 +375,9 @@ pub fn burn_ctoken_legacy(ctx: Context<BurnCTokenLegacy>, ctokens_amount: u64) -
     // Skip 8-byte discriminator
     let legacy_crucible: LegacyCrucible = LegacyCrucible::try_from_slice(&data[8..])?;
     
-    // Check if crucible is paused
-    require!(!legacy_crucible.paused, CrucibleError::ProtocolPaused);
+    // Check if withdrawals are paused
+    require_not_paused(legacy_crucible.pause_flags, PAUSE_WITHDRAWALS)?;
+    let protocol_fee_bps = load_protocol_fee_bps(&ctx.accounts.forge_protocol)?;
     
     // Validate base_mint matches
     require!(
@@ -446,19 +428,13 @@ pub fn burn_ctoken_legacy(ctx: Context<BurnCTokenLegacy>, ctokens_amount: u64) -
         CrucibleError::InsufficientLiquidity
     );
     
-    const UNWRAP_FEE_BPS_SPECIAL: u64 = 75; // 0.75% unwrap fee
+    // Legacy crucibles have no stored fee schedule and charge the defaults
     let unwrap_fee = base_to_return_before_fee
-        .checked_mul(UNWRAP_FEE_BPS_SPECIAL)
+        .checked_mul(FeeSchedule::DEFAULT.unwrap_fee_bps)
         .and_then(|v| v.checked_div(10_000u64))
         .ok_or(ProgramError::ArithmeticOverflow)?;
     
-    let vault_fee_share = unwrap_fee
-        .checked_mul(VAULT_FEE_SHARE_BPS)
-        .and_then(|v| v.checked_div(10_000u64))
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    let protocol_fee_share = unwrap_fee
-        .checked_sub(vault_fee_share)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let (vault_fee_share, protocol_fee_share) = split_fee(unwrap_fee, protocol_fee_bps)?;
     
     // Net amount to return to user (after fee)
     let base_to_return = base_to_return_before_fee
@@ -556,7 +532,7 @@ pub fn burn_ctoken_legacy(ctx: Context<BurnCTokenLegacy>, ctokens_amount: u64) -
         exchange_rate: legacy_crucible.exchange_rate,
         last_update_slot: clock.slot,
         fee_rate: legacy_crucible.fee_rate,
-        paused: legacy_crucible.paused,
+        pause_flags: legacy_crucible.pause_flags,
         total_leveraged_positions: legacy_crucible.total_leveraged_positions,
         total_lp_positions: legacy_crucible.total_lp_positions,
         expected_vault_balance: legacy_crucible.expected_vault_balance
@@ -597,8 +573,9 @@ pub fn mint_ctoken_legacy(ctx: Context<MintCTokenLegacy>, amount: u64) -> Result
     // Skip 8-byte discriminator
     let legacy_crucible: LegacyCrucible = LegacyCrucible::try_from_slice(&data[8..])?;
     
-    // Check if crucible is paused
-    require!(!legacy_crucible.paused, CrucibleError::ProtocolPaused);
+    // Check if deposits are paused
+    require_not_paused(legacy_crucible.pause_flags, PAUSE_DEPOSITS)?;
+    let protocol_fee_bps = load_protocol_fee_bps(&ctx.accounts.forge_protocol)?;
     
     // Validate base_mint matches
     require!(
@@ -637,18 +614,13 @@ pub fn mint_ctoken_legacy(ctx: Context<MintCTokenLegacy>, amount: u64) -> Result
     };
     
     // Calculate fees
+    // Legacy crucibles have no stored fee schedule and charge the defaults
     let wrap_fee = amount
-        .checked_mul(WRAP_FEE_BPS)
+        .checked_mul(FeeSchedule::DEFAULT.wrap_fee_bps)
         .and_then(|v| v.checked_div(10_000u64))
         .ok_or(ProgramError::ArithmeticOverflow)?;
     
-    let vault_fee_share = wrap_fee
-        .checked_mul(VAULT_FEE_SHARE_BPS)
-        .and_then(|v| v.checked_div(10_000u64))
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    let protocol_fee_share = wrap_fee
-        .checked_sub(vault_fee_share)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let (vault_fee_share, protocol_fee_share) = split_fee(wrap_fee, protocol_fee_bps)?;
     
     // Net deposit after fee
     let net_deposit = amount
@@ -690,6 +662,12 @@ pub fn mint_ctoken_legacy(ctx: Context<MintCTokenLegacy>, amount: u64) -> Result
     
     // Transfer protocol fee to treasury
     if protocol_fee_share > 0 {
+        // Validate treasury account matches legacy_crucible.treasury
+        require!(
+            ctx.accounts.treasury.key() == legacy_crucible.treasury,
+            CrucibleError::InvalidTreasury
+        );
+        
         token::transfer(
             CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
@@ -742,7 +720,7 @@ pub fn mint_ctoken_legacy(ctx: Context<MintCTokenLegacy>, amount: u64) -> Result
         exchange_rate: exchange_rate,
         last_update_slot: clock.slot,
         fee_rate: legacy_crucible.fee_rate,
-        paused: legacy_crucible.paused,
+        pause_flags: legacy_crucible.pause_flags,
         total_leveraged_positions: legacy_crucible.total_leveraged_positions,
         total_lp_positions: legacy_crucible.total_lp_positions,
         expected_vault_balance: legacy_crucible.expected_vault_balance
@@ -775,14 +753,15 @@ pub fn mint_ctoken_legacy(ctx: Context<MintCTokenLegacy>, amount: u64) -> Result
 
 /// Deposit arbitrage profits directly to crucible vault
 /// This allows arbitrageurs to route profits to cToken holders, increasing yield
-/// 80% of deposit goes to vault (increases yield), 20% goes to treasury (protocol revenue)
-/// Optionally rewards arbitrageur with 1% of deposit as cTokens as incentive
+/// The treasury takes the forge-core protocol_fee_rate share; the rest goes to the vault (increases yield)
+/// Optionally rewards arbitrageur with the crucible's arbitrage_reward_bps of deposit as cTokens as incentive
 pub fn deposit_arbitrage_profit(
     ctx: Context<DepositArbitrageProfit>,
     amount: u64,
 ) -> Result<()> {
-    // Check if crucible is paused
-    require!(!ctx.accounts.crucible.paused, CrucibleError::ProtocolPaused);
+    // Check if arbitrage deposits are paused
+    require_not_paused(ctx.accounts.crucible.pause_flags, PAUSE_ARBITRAGE_DEPOSITS)?;
+    let protocol_fee_bps = load_protocol_fee_bps(&ctx.accounts.forge_protocol)?;
     
     let crucible = &mut ctx.accounts.crucible;
     let clock = Clock::get()?;
@@ -802,13 +781,7 @@ pub fn deposit_arbitrage_profit(
         ctx.accounts.ctoken_mint.supply,
     )?;
     
-    let vault_share = amount
-        .checked_mul(ARBITRAGE_VAULT_SHARE_BPS)
-        .and_then(|v| v.checked_div(10_000u64))
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    let treasury_share = amount
-        .checked_sub(vault_share)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let (vault_share, treasury_share) = split_fee(amount, protocol_fee_bps)?;
     
     // Transfer vault share to vault (increases yield for cToken holders)
     if vault_share > 0 {
@@ -854,11 +827,11 @@ pub fn deposit_arbitrage_profit(
     
     // Calculate reward for arbitrageur (incentivizes routing profits back to protocol)
     let reward_amount = amount
-        .checked_mul(ARBITRAGE_REWARD_BPS)
+        .checked_mul(crucible.fee_schedule.arbitrage_reward_bps)
         .and_then(|v| v.checked_div(10_000u64))
         .ok_or(ProgramError::ArithmeticOverflow)?;
     
-    // Mint reward cTokens to arbitrageur (1% incentive)
+    // Mint reward cTokens to arbitrageur (fee schedule incentive)
     // SECURITY FIX: Return error if calculation fails instead of silently defaulting to 0
     let reward_ctokens = if reward_amount > 0 && ctx.accounts.ctoken_mint.supply > 0 {
         require!(exchange_rate > 0, CrucibleError::InvalidAmount);
@@ -1047,6 +1020,9 @@ pub struct MintCToken<'info> {
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: Forge protocol PDA - supplies protocol_fee_rate and the kill switch, validated in forge_core::load_protocol
+    pub forge_protocol: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
@@ -1093,6 +1069,9 @@ pub struct BurnCToken<'info> {
     
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
+
+    /// CHECK: Forge protocol PDA - supplies protocol_fee_rate and the kill switch, validated in forge_core::load_protocol
+    pub forge_protocol: UncheckedAccount<'info>,
 }
 
 /// Legacy burn accounts - uses UncheckedAccount for crucible to support old format
@@ -1128,6 +1107,9 @@ pub struct BurnCTokenLegacy<'info> {
     
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
+
+    /// CHECK: Forge protocol PDA - supplies protocol_fee_rate and the kill switch, validated in forge_core::load_protocol
+    pub forge_protocol: UncheckedAccount<'info>,
 }
 
 /// Legacy mint accounts - uses UncheckedAccount for crucible to support old format
@@ -1170,6 +1152,9 @@ pub struct MintCTokenLegacy<'info> {
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: Forge protocol PDA - supplies protocol_fee_rate and the kill switch, validated in forge_core::load_protocol
+    pub forge_protocol: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
@@ -1222,6 +1207,9 @@ pub struct DepositArbitrageProfit<'info> {
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
+
+    /// CHECK: Forge protocol PDA - supplies protocol_fee_rate and the kill switch, validated in forge_core::load_protocol
+    pub forge_protocol: UncheckedAccount<'info>,
 }
 
 #[event]
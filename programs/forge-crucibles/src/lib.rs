@@ -1,13 +1,18 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
 use anchor_spl::associated_token::AssociatedToken;
+use forge_core::program::ForgeCore;
+use forge_core::pause::PAUSE_NONE;
+use forge_core::ForgeProtocol;
 
+pub mod admin;
 pub mod ctoken;
 pub mod lvf;
 pub mod lp;
 pub mod metadata;
 pub mod state;
 
+use admin::*;
 use ctoken::*;
 use lvf::*;
 use lp::*;
@@ -32,12 +37,27 @@ pub mod forge_crucibles {
     pub fn initialize_crucible(
         ctx: Context<InitializeCrucible>,
         fee_rate: u64, // Fee rate in basis points (e.g., 200 = 0.2% = 2 bps)
+        crucible_id: u64, // Protocol registry ID for the new crucible
     ) -> Result<()> {
         // SECURITY FIX (AUDIT-011): Validate fee_rate bounds (0-10,000 bps)
         require!(
             fee_rate <= 10_000,
             CrucibleError::InvalidConfig
         );
+
+        // SECURITY FIX: Register the crucible with forge-core in the same transaction.
+        // forge-core enforces the protocol authority, is_active and max_crucibles, so
+        // no crucible can go live without being sanctioned and discoverable.
+        let cpi_accounts = forge_core::cpi::accounts::RegisterCrucible {
+            forge_protocol: ctx.accounts.forge_protocol.to_account_info(),
+            crucible_registry: ctx.accounts.crucible_registry.to_account_info(),
+            crucible: ctx.accounts.crucible.to_account_info(),
+            base_mint: ctx.accounts.base_mint.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.forge_core_program.to_account_info(), cpi_accounts);
+        forge_core::cpi::register_crucible(cpi_ctx, crucible_id)?;
         
         let clock = Clock::get()?;
         let base_mint_key = ctx.accounts.base_mint.key();
@@ -226,7 +246,7 @@ pub mod forge_crucibles {
         crucible.exchange_rate = 1_000_000; // Initial exchange rate: 1.0 (scaled by 1M)
         crucible.last_update_slot = clock.slot;
         crucible.fee_rate = fee_rate;
-        crucible.paused = false;
+        crucible.pause_flags = PAUSE_NONE;
         crucible.total_leveraged_positions = 0;
         crucible.total_lp_positions = 0;
         crucible.expected_vault_balance = 0;
@@ -239,6 +259,8 @@ pub mod forge_crucibles {
         };
         crucible.treasury = ctx.accounts.treasury.key();
         crucible.total_fees_accrued = 0;
+        crucible.fee_schedule = FeeSchedule::DEFAULT;
+        crucible.liquidation_config = LiquidationConfig::DEFAULT;
 
         emit!(CrucibleInitialized {
             crucible: crucible.key(),
@@ -279,7 +301,7 @@ pub mod forge_crucibles {
     }
 
     /// Deposit arbitrage profits directly to crucible vault
-    /// 80% goes to vault (increases yield), 20% goes to treasury (protocol revenue)
+    /// Treasury share follows the forge-core protocol_fee_rate, the rest goes to the vault (increases yield)
     pub fn deposit_arbitrage_profit(
         ctx: Context<DepositArbitrageProfit>,
         amount: u64,
@@ -288,35 +310,50 @@ pub mod forge_crucibles {
     }
 
     /// Open a leveraged LP position (TOKEN/USDC)
+    /// position_nonce allows multiple leveraged positions per user per crucible
     pub fn open_leveraged_position(
         ctx: Context<OpenLeveragedPosition>,
         collateral_amount: u64,
         leverage_factor: u64,
+        position_nonce: u64,
     ) -> Result<u64> {
-        lvf::open_leveraged_position(ctx, collateral_amount, leverage_factor)
+        lvf::open_leveraged_position(ctx, collateral_amount, leverage_factor, position_nonce)
     }
 
     /// Close a leveraged LP position
+    /// position_nonce must match the nonce used when opening the position
     pub fn close_leveraged_position(
         ctx: Context<CloseLeveragedPosition>,
         position_id: Pubkey,
         max_slippage_bps: u64,
+        position_nonce: u64,
     ) -> Result<()> {
-        lvf::close_leveraged_position(ctx, position_id, max_slippage_bps)
+        lvf::close_leveraged_position(ctx, position_id, max_slippage_bps, position_nonce)
+    }
+
+    /// Move a leveraged position created before nonces were added to a nonce PDA
+    /// Legacy positions are closed or liquidated after migrating (can be bundled in one transaction)
+    pub fn migrate_legacy_leveraged_position(
+        ctx: Context<MigrateLegacyLeveragedPosition>,
+        position_nonce: u64,
+    ) -> Result<()> {
+        lvf::migrate_legacy_leveraged_position(ctx, position_nonce)
     }
 
     /// Check position health (LTV in basis points)
     pub fn health_check(
         ctx: Context<HealthCheck>,
+        position_nonce: u64,
     ) -> Result<u64> {
-        lvf::health_check(ctx)
+        lvf::health_check(ctx, position_nonce)
     }
 
     /// Liquidate an undercollateralized leveraged position
     pub fn liquidate_position(
         ctx: Context<LiquidatePosition>,
+        position_nonce: u64,
     ) -> Result<()> {
-        lvf::liquidate_position(ctx)
+        lvf::liquidate_position(ctx, position_nonce)
     }
 
     /// Open a standard LP position (base token + USDC, equal value)
@@ -354,6 +391,53 @@ pub mod forge_crucibles {
         metadata::create_ctoken_metadata(ctx, name, symbol, uri, seller_fee_basis_points, is_mutable)
     }
 
+    /// Set which crucible operations are paused (emergency function) - protocol authority only
+    pub fn set_crucible_pause_flags(ctx: Context<CrucibleAdmin>, pause_flags: u8) -> Result<()> {
+        admin::set_crucible_pause_flags(ctx, pause_flags)
+    }
+
+    /// Update the crucible fee rate - protocol authority only
+    pub fn update_fee_rate(ctx: Context<CrucibleAdmin>, new_fee_rate: u64) -> Result<()> {
+        admin::update_fee_rate(ctx, new_fee_rate)
+    }
+
+    /// Replace the crucible fee schedule - protocol authority only
+    pub fn update_fee_schedule(ctx: Context<CrucibleAdmin>, new_fee_schedule: FeeSchedule) -> Result<()> {
+        admin::update_fee_schedule(ctx, new_fee_schedule)
+    }
+
+    /// Migrate a crucible created before fee schedules to the default schedule - protocol authority only
+    pub fn migrate_crucible_fee_schedule(ctx: Context<MigrateCrucibleFeeSchedule>) -> Result<()> {
+        admin::migrate_crucible_fee_schedule(ctx)
+    }
+
+    /// Replace the crucible's LVF liquidation config - protocol authority only
+    pub fn update_liquidation_config(
+        ctx: Context<CrucibleAdmin>,
+        new_liquidation_config: LiquidationConfig,
+    ) -> Result<()> {
+        admin::update_liquidation_config(ctx, new_liquidation_config)
+    }
+
+    /// Migrate a crucible created before liquidation configs to the default config - protocol authority only
+    pub fn migrate_crucible_liquidation_config(ctx: Context<MigrateCrucibleLiquidationConfig>) -> Result<()> {
+        admin::migrate_crucible_liquidation_config(ctx)
+    }
+
+    /// Update the crucible oracle - protocol authority only
+    /// Pass None to disable the oracle
+    pub fn update_oracle(
+        ctx: Context<UpdateCrucibleOracle>,
+        new_oracle: Option<Pubkey>,
+    ) -> Result<()> {
+        admin::update_oracle(ctx, new_oracle)
+    }
+
+    /// Rotate the crucible treasury - protocol authority only
+    pub fn update_treasury(ctx: Context<UpdateCrucibleTreasury>) -> Result<()> {
+        admin::update_treasury(ctx)
+    }
+
     /// Initialize USDC vault for existing crucibles
     /// This allows crucibles initialized before USDC vault support to add the vault
     pub fn initialize_usdc_vault(
@@ -439,9 +523,28 @@ pub use lvf::{HealthCheck, LiquidatePosition};
 #[derive(Accounts)]
 #[instruction(fee_rate: u64)]
 pub struct InitializeCrucible<'info> {
+    /// Must be the Forge protocol authority - also pays for the registry entry
     #[account(mut)]
     pub authority: Signer<'info>,
 
+    #[account(
+        mut,
+        seeds = [b"forge_protocol"],
+        bump = forge_protocol.bump,
+        seeds::program = forge_core::ID,
+        has_one = authority @ CrucibleError::Unauthorized,
+    )]
+    pub forge_protocol: Box<Account<'info, ForgeProtocol>>,
+
+    /// CHECK: Registry entry - created by the forge-core register_crucible CPI
+    #[account(
+        mut,
+        seeds = [b"crucible_registry", crucible.key().as_ref()],
+        bump,
+        seeds::program = forge_core::ID,
+    )]
+    pub crucible_registry: UncheckedAccount<'info>,
+
     #[account(
         init,
         payer = authority,
@@ -481,13 +584,17 @@ pub struct InitializeCrucible<'info> {
     /// CHECK: USDC mint - needed to initialize USDC vault
     pub usdc_mint: UncheckedAccount<'info>,
 
-    /// CHECK: Protocol treasury token account for fee collection
-    #[account(mut)]
+    /// CHECK: Forge-core fee vault PDA for the base mint - protocol fees are swept from there
+    #[account(
+        mut,
+        constraint = treasury.key() == forge_core::fees::fee_vault_address(&base_mint.key()) @ CrucibleError::InvalidTreasury
+    )]
     pub treasury: UncheckedAccount<'info>,
 
     /// CHECK: Oracle account for price feeds. Pass system program if not used.
     pub oracle: UncheckedAccount<'info>,
 
+    pub forge_core_program: Program<'info, ForgeCore>,
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
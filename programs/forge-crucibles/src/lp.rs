@@ -1,17 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer, MintTo, Burn};
 
-use crate::state::{Crucible, LPPositionAccount, CrucibleError};
+use crate::state::{Crucible, LPPositionAccount, CrucibleError, FeeSchedule, LiquidationConfig, load_protocol_fee_bps, require_not_paused, split_fee_value};
+use forge_core::pause::{PAUSE_LP_CLOSE, PAUSE_LP_OPEN};
 use crate::lvf::get_oracle_price;
 
 // Fee and scaling constants
 const PRICE_SCALE: u64 = 1_000_000; // Scale for price precision
 const SLIPPAGE_TOLERANCE_BPS: u64 = 100; // 1% slippage tolerance (100 basis points)
-const OPEN_FEE_BPS: u64 = 100; // 1% open fee (100 basis points)
-const CLOSE_FEE_PRINCIPAL_BPS: u64 = 200; // 2% principal fee (200 basis points)
-const CLOSE_FEE_YIELD_BPS: u64 = 1_000; // 10% yield fee (1000 basis points)
-const VAULT_FEE_SHARE_BPS: u64 = 8_000; // 80% vault share (8000 basis points)
-const PROTOCOL_FEE_SHARE_BPS: u64 = 2_000; // 20% protocol share (2000 basis points)
 
 // Amount bounds to prevent dust and overflow attacks
 const MIN_LP_BASE_AMOUNT: u64 = 1_000; // Minimum base token amount for LP position
@@ -105,9 +101,9 @@ fn deserialize_old_format_crucible(
     );
     offset += 8;
     
-    // Read paused (1 byte)
+    // Read pause_flags (1 byte)
     require!(data.len() >= offset + 1, CrucibleError::InvalidConfig);
-    let paused = data[offset] != 0;
+    let pause_flags = data[offset];
     offset += 1;
     
     // Read total_leveraged_positions (8 bytes)
@@ -148,13 +144,16 @@ fn deserialize_old_format_crucible(
         exchange_rate,
         last_update_slot,
         fee_rate,
-        paused,
+        pause_flags,
         total_leveraged_positions,
         total_lp_positions,
         expected_vault_balance,
         oracle,
         treasury,
         total_fees_accrued,
+        // Old format: no fee schedule, charge the defaults
+        fee_schedule: FeeSchedule::DEFAULT,
+        liquidation_config: LiquidationConfig::DEFAULT,
     })
 }
 
@@ -248,8 +247,9 @@ pub fn open_lp_position(
         msg!("[DEBUG] New format crucible deserialized successfully");
     }
     
-    // Check if crucible is paused
-    require!(!crucible.paused, CrucibleError::ProtocolPaused);
+    // Check if LP opens are paused
+    require_not_paused(crucible.pause_flags, PAUSE_LP_OPEN)?;
+    let protocol_fee_bps = load_protocol_fee_bps(&ctx.accounts.forge_protocol)?;
     
     // Handle old format crucibles: if lp_token_mint equals ctoken_mint, use the passed lp_token_mint account
     // This allows old crucibles to work with LP positions by passing the LP token mint separately
@@ -340,19 +340,10 @@ pub fn open_lp_position(
         .ok_or(ProgramError::ArithmeticOverflow)?;
     
     let open_fee_usdc = total_position_value
-        .checked_mul(OPEN_FEE_BPS as u128)
+        .checked_mul(crucible.fee_schedule.open_fee_bps as u128)
         .and_then(|v| v.checked_div(10_000u128))
         .ok_or(ProgramError::ArithmeticOverflow)?;
     
-    let vault_fee_share = open_fee_usdc
-        .checked_mul(VAULT_FEE_SHARE_BPS as u128)
-        .and_then(|v| v.checked_div(10_000u128))
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    // Protocol fee share is calculated per-token below, not needed here
-    let _protocol_fee_share = open_fee_usdc
-        .checked_sub(vault_fee_share)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    
     // Calculate fee split proportionally between base and USDC
     // Fee in base tokens: (open_fee_usdc * base_amount / total_position_value) / base_price
     let fee_base_amount = open_fee_usdc
@@ -366,19 +357,9 @@ pub fn open_lp_position(
         .and_then(|v| v.checked_div(total_position_value))
         .ok_or(ProgramError::ArithmeticOverflow)?;
     
-    // Split fees 80/20 with validation to ensure they sum correctly
-    let vault_fee_base = fee_base_amount
-        .checked_mul(80u128)
-        .and_then(|v| v.checked_div(100u128))
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    let vault_fee_usdc = fee_usdc_amount
-        .checked_mul(80u128)
-        .and_then(|v| v.checked_div(100u128))
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    let protocol_fee_base = fee_base_amount.checked_sub(vault_fee_base)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    let protocol_fee_usdc = fee_usdc_amount.checked_sub(vault_fee_usdc)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
+    // Split fees between vault and protocol (forge-core protocol_fee_rate)
+    let (vault_fee_base, protocol_fee_base) = split_fee_value(fee_base_amount, protocol_fee_bps)?;
+    let (vault_fee_usdc, protocol_fee_usdc) = split_fee_value(fee_usdc_amount, protocol_fee_bps)?;
     
     // Validate fee amounts sum correctly (within rounding tolerance)
     let fee_base_value_check = vault_fee_base
@@ -734,8 +715,9 @@ pub fn close_lp_position(
             .map_err(|_| CrucibleError::InvalidConfig)?;
     }
     
-    // Check if crucible is paused
-    require!(!crucible.paused, CrucibleError::ProtocolPaused);
+    // Check if LP closes are paused
+    require_not_paused(crucible.pause_flags, PAUSE_LP_CLOSE)?;
+    let protocol_fee_bps = load_protocol_fee_bps(&ctx.accounts.forge_protocol)?;
     
     let position = &mut ctx.accounts.position;
     
@@ -859,13 +841,13 @@ pub fn close_lp_position(
          entry_exchange_rate, current_exchange_rate, exchange_rate_yield, price_pnl, yield_value);
     
     let principal_fee_value = initial_total_value
-        .checked_mul(CLOSE_FEE_PRINCIPAL_BPS as u128)
+        .checked_mul(crucible.fee_schedule.close_fee_principal_bps as u128)
         .and_then(|v| v.checked_div(10_000u128))
         .ok_or(ProgramError::ArithmeticOverflow)?;
     
-    // Calculate yield fee: 10% of yield earned
+    // Calculate yield fee on yield earned
     let yield_fee_value = yield_value
-        .checked_mul(CLOSE_FEE_YIELD_BPS as u128)
+        .checked_mul(crucible.fee_schedule.close_fee_yield_bps as u128)
         .and_then(|v| v.checked_div(10_000u128))
         .ok_or(ProgramError::ArithmeticOverflow)?;
     
@@ -873,14 +855,6 @@ pub fn close_lp_position(
         .checked_add(yield_fee_value)
         .ok_or(ProgramError::ArithmeticOverflow)?;
     
-    let vault_fee_share_value = total_fee_value
-        .checked_mul(VAULT_FEE_SHARE_BPS as u128)
-        .and_then(|v| v.checked_div(10_000u128))
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    let protocol_fee_share_value = total_fee_value
-        .checked_sub(vault_fee_share_value)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    
     // Calculate fee split proportionally between base and USDC based on current values
     let fee_base_value = total_fee_value
         .checked_mul(current_base_value)
@@ -898,17 +872,9 @@ pub fn close_lp_position(
         .ok_or(ProgramError::ArithmeticOverflow)?;
     let fee_usdc_amount = fee_usdc_value; // USDC is 1:1
     
-    // Split fees 80/20
-    let vault_fee_base = fee_base_amount
-        .checked_mul(80u128)
-        .and_then(|v| v.checked_div(100u128))
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    let vault_fee_usdc = fee_usdc_amount
-        .checked_mul(80u128)
-        .and_then(|v| v.checked_div(100u128))
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    let protocol_fee_base = fee_base_amount - vault_fee_base;
-    let protocol_fee_usdc = fee_usdc_amount - vault_fee_usdc;
+    // Split fees between vault and protocol (forge-core protocol_fee_rate)
+    let (vault_fee_base, protocol_fee_base) = split_fee_value(fee_base_amount, protocol_fee_bps)?;
+    let (vault_fee_usdc, protocol_fee_usdc) = split_fee_value(fee_usdc_amount, protocol_fee_bps)?;
     
     // Ensure fees fit in u64
     let vault_fee_base = if vault_fee_base > u64::MAX as u128 {
@@ -1253,11 +1219,14 @@ pub struct OpenLPPosition<'info> {
     /// Treasury account for USDC
     #[account(
         mut,
-        constraint = treasury_usdc.mint == user_usdc_account.mint @ CrucibleError::InvalidTreasury
+        constraint = treasury_usdc.mint == user_usdc_account.mint @ CrucibleError::InvalidTreasury,
+        constraint = treasury_usdc.key() == forge_core::fees::fee_vault_address(&treasury_usdc.mint) @ CrucibleError::InvalidTreasury
     )]
     pub treasury_usdc: Box<Account<'info, TokenAccount>>,
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
+    /// CHECK: Forge protocol PDA - supplies protocol_fee_rate and the kill switch, validated in forge_core::load_protocol
+    pub forge_protocol: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
@@ -1314,10 +1283,13 @@ pub struct CloseLPPosition<'info> {
     /// SECURITY FIX: Validate treasury_usdc is a TokenAccount for USDC
     #[account(
         mut,
-        constraint = treasury_usdc.mint == user_usdc_account.mint @ CrucibleError::InvalidTreasury
+        constraint = treasury_usdc.mint == user_usdc_account.mint @ CrucibleError::InvalidTreasury,
+        constraint = treasury_usdc.key() == forge_core::fees::fee_vault_address(&treasury_usdc.mint) @ CrucibleError::InvalidTreasury
     )]
     pub treasury_usdc: Account<'info, TokenAccount>,
     pub token_program: Program<'info, Token>,
+    /// CHECK: Forge protocol PDA - supplies protocol_fee_rate and the kill switch, validated in forge_core::load_protocol
+    pub forge_protocol: UncheckedAccount<'info>,
 }
 
 // SECURITY FIX: Event emissions for LP position state changes
@@ -1,11 +1,12 @@
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
 use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer, MintTo, Burn};
 use crate::state::*;
+use forge_core::pause::{PAUSE_LEVERAGE_CLOSE, PAUSE_LEVERAGE_OPEN, PAUSE_LIQUIDATIONS};
 use crate::LENDING_POOL_PROGRAM_ID;
 use lending_pool_usdc::cpi::accounts::BorrowUSDC;
-use lending_pool_usdc::cpi::accounts::RepayUSDC;
+use lending_pool_usdc::cpi::accounts::{RecordBadDebt, RepayUSDC, RepayUSDCOnBehalf};
 use lending_pool_usdc::program::LendingPoolUsdc;
-use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 
 // SECURITY FIX: Minimum amounts to prevent dust attacks
 const MIN_LEVERAGE_COLLATERAL: u64 = 1_000; // Minimum collateral amount for leveraged position
@@ -14,12 +15,14 @@ const MIN_LEVERAGE_COLLATERAL: u64 = 1_000; // Minimum collateral amount for lev
 const MAX_LEVERAGE_COLLATERAL: u64 = 1_000_000_000_000_000_000; // Maximum collateral amount (1 billion tokens with 9 decimals)
 
 // SECURITY FIX: Extract magic numbers to named constants
-const SLOTS_PER_YEAR: u128 = 78_840_000u128; // Approximate slots per year (400ms per slot)
 const PRICE_SCALE_FACTOR: u64 = 1_000_000; // Scale for price precision (1.0 = 1_000_000)
 const MAX_LEVERAGE_BPS: u64 = 200; // Maximum leverage (200 = 2x)
 const MIN_LEVERAGE_BPS: u64 = 100; // Minimum leverage (100 = 1x)
 const MAX_CONFIDENCE_BPS: u64 = 500; // Maximum oracle confidence (500 = 5%)
 const MAX_STALENESS_SECONDS: u64 = 300; // Maximum oracle staleness (5 minutes)
+const LIQUIDATION_THRESHOLD_BPS: u128 = 8_500; // Position is liquidatable above 85% LTV
+const LIQUIDATION_BONUS_BPS: u128 = 500; // 5% bonus on seized collateral
+const QUOTE_MINT_DECIMALS: u8 = 6; // Borrowed quote asset is a USD stablecoin priced 1:1 with 6 decimals (USDC, USDT, PYUSD)
 
 /// Open a leveraged LP position
 /// Lending pool integration is complete - borrows USDC from lending pool via CPI
@@ -27,9 +30,11 @@ pub fn open_leveraged_position(
     ctx: Context<OpenLeveragedPosition>,
     collateral_amount: u64,
     leverage_factor: u64, // 150 = 1.5x, 200 = 2x (scaled by 100)
+    position_nonce: u64, // Nonce to allow multiple positions per user
 ) -> Result<u64> {
-    // Check if crucible is paused
-    require!(!ctx.accounts.crucible.paused, CrucibleError::ProtocolPaused);
+    // Check if leveraged opens are paused
+    require_not_paused(ctx.accounts.crucible.pause_flags, PAUSE_LEVERAGE_OPEN)?;
+    forge_core::require_protocol_active(&ctx.accounts.forge_protocol)?;
     
     // SECURITY FIX: Require minimum collateral amount to prevent dust attacks
     require!(
@@ -115,16 +120,16 @@ pub fn open_leveraged_position(
     let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
     token::transfer(cpi_ctx, collateral_amount)?;
 
-    // Borrow USDC from USDC-only lending pool via CPI
-    // NOTE: Only USDC lending pool is supported for leverage in crucibles
+    // Borrow the quote asset from its lending pool via CPI
+    // Any pool whose asset is a 6-decimal USD stablecoin works (USDC, USDT, PYUSD, ...)
     if borrowed_usdc > 0 {
-        // Get pool bump from pool account data
-        // Pool account structure: discriminator (8) + usdc_mint (32) + total_liquidity (8) + total_borrowed (8) + borrow_rate (8) + lender_rate (8) + bump (1)
-        // Bump is at offset 72
-        let pool_data = ctx.accounts.lending_market.try_borrow_data()?;
-        if pool_data.len() < 73 {
-            return Err(ProgramError::InvalidAccountData.into());
-        }
+        // SECURITY FIX: Validate the lending pool and that it lends the requested quote mint
+        let pool = lending_pool_usdc::load_pool(&ctx.accounts.lending_market)?;
+        require_keys_eq!(
+            pool.usdc_mint,
+            ctx.accounts.quote_mint.key(),
+            CrucibleError::InvalidMint
+        );
         // SECURITY FIX: Validate lending program ID matches expected constant
         require!(
             ctx.accounts.lending_program.key() == crate::LENDING_POOL_PROGRAM_ID,
@@ -132,8 +137,15 @@ pub fn open_leveraged_position(
         );
         
         // SECURITY FIX: Validate borrower_account PDA derivation
+        // Debt is tracked per pool and position, so the borrower account is derived from both
+        let position_key = position.key();
         let (expected_borrower_pda, _bump) = Pubkey::find_program_address(
-            &[b"borrower", ctx.accounts.user.key().as_ref()],
+            &[
+                b"borrower",
+                ctx.accounts.lending_market.key().as_ref(),
+                ctx.accounts.user.key().as_ref(),
+                position_key.as_ref(),
+            ],
             &ctx.accounts.lending_program.key(),
         );
         require!(
@@ -142,21 +154,31 @@ pub fn open_leveraged_position(
         );
         
         // Create CPI context for borrowing
+        // The crucible PDA is the registered borrow authority and signs the borrow
         let cpi_program = ctx.accounts.lending_program.to_account_info();
         let cpi_accounts = BorrowUSDC {
             pool: ctx.accounts.lending_market.to_account_info(),
             borrower: ctx.accounts.user.to_account_info(),
             borrower_account: ctx.accounts.borrower_account.to_account_info(),
+            borrow_authority: ctx.accounts.crucible_authority.to_account_info(),
+            authorized_borrower: ctx.accounts.authorized_borrower.to_account_info(),
             pool_vault: ctx.accounts.lending_vault.to_account_info(),
             borrower_usdc_account: ctx.accounts.user_usdc_account.to_account_info(),
             token_program: ctx.accounts.token_program.to_account_info(),
             system_program: ctx.accounts.system_program.to_account_info(),
+            forge_protocol: ctx.accounts.forge_protocol.to_account_info(),
         };
+        let seeds = &[
+            b"crucible",
+            crucible.base_mint.as_ref(),
+            &[crucible.bump],
+        ];
+        let signer = &[&seeds[..]];
         
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
         
         // Call lending pool borrow_usdc instruction via CPI
-        lending_pool_usdc::cpi::borrow_usdc(cpi_ctx, borrowed_usdc)?;
+        lending_pool_usdc::cpi::borrow_usdc(cpi_ctx, borrowed_usdc, position_key)?;
     }
 
     // Calculate LP tokens to mint for leveraged position
@@ -241,6 +263,7 @@ pub fn open_leveraged_position(
     position.is_open = true;
     position.created_at = clock.slot;
     position.bump = ctx.bumps.position;
+    position.nonce = position_nonce; // Store nonce for PDA derivation
 
     // Update crucible state
     crucible.total_leveraged_positions = crucible.total_leveraged_positions
@@ -257,6 +280,7 @@ pub fn open_leveraged_position(
         token: position.token,
         collateral: collateral_amount,
         borrowed_usdc,
+        quote_mint: ctx.accounts.quote_mint.key(),
         leverage_factor,
         entry_exchange_rate: crucible.exchange_rate, // Store for real yield tracking
     });
@@ -272,9 +296,11 @@ pub fn close_leveraged_position(
     ctx: Context<CloseLeveragedPosition>,
     _position_id: Pubkey,
     max_slippage_bps: u64, // Maximum slippage in basis points (e.g., 100 = 1%)
+    _position_nonce: u64, // Used for PDA derivation in account constraints
 ) -> Result<()> {
-    // Check if crucible is paused
-    require!(!ctx.accounts.crucible.paused, CrucibleError::ProtocolPaused);
+    // Check if leveraged closes are paused
+    require_not_paused(ctx.accounts.crucible.pause_flags, PAUSE_LEVERAGE_CLOSE)?;
+    let protocol_fee_bps = load_protocol_fee_bps(&ctx.accounts.forge_protocol)?;
     
     // SECURITY FIX: Validate max_slippage_bps is within reasonable bounds (<= 10_000 = 100%)
     require!(
@@ -297,64 +323,21 @@ pub fn close_leveraged_position(
     // Repay USDC loan to USDC-only lending pool (including accrued interest)
     // NOTE: Only USDC lending pool is supported for leverage in crucibles
     if position.borrowed_usdc > 0 {
-        // SECURITY FIX: Fetch borrow_rate from lending pool account instead of hardcoding
-        // LendingPool structure: discriminator (8) + usdc_mint (32) + total_liquidity (8) + total_borrowed (8) + borrow_rate (8) + lender_rate (8) + bump (1)
-        // borrow_rate is at offset 56
-        let pool_data = ctx.accounts.lending_market.try_borrow_data()?;
-        require!(
-            pool_data.len() >= 65,
-            CrucibleError::InvalidLendingProgram
-        );
-        let borrow_rate = u64::from_le_bytes(
-            pool_data[56..64].try_into().map_err(|_| CrucibleError::InvalidLendingProgram)?
-        );
-        
-        // SECURITY FIX (HIGH-003): Calculate repayment amount using timestamp-based calculation
-        // Note: This is an estimate - the actual interest will be calculated by the lending pool
-        // based on the borrower account's borrow_timestamp. This calculation is for validation/preview.
-        // SECURITY FIX: Validate created_at <= clock.slot to prevent invalid slot calculations
-        require!(
-            position.created_at <= clock.slot,
-            CrucibleError::InvalidLeverage
-        );
-        
-        // SECURITY FIX (HIGH-003): Use timestamp-based calculation for consistency with lending pool
-        // Convert slot-based created_at to approximate timestamp for interest calculation
-        // Note: This is approximate - actual interest is calculated by lending pool using borrower account's borrow_timestamp
-        const SECONDS_PER_YEAR: u64 = 31_536_000; // Exact: 365 * 24 * 60 * 60
-        let current_timestamp = clock.unix_timestamp as u64;
-        // Approximate: slots * 0.4 seconds per slot (400ms average)
-        // For more accuracy, we'd need to store timestamp in position, but for now use approximation
-        let slots_elapsed = clock.slot
-            .checked_sub(position.created_at)
-            .ok_or(CrucibleError::InvalidLeverage)?;
-        // Approximate seconds elapsed (0.4 seconds per slot)
-        let seconds_elapsed = (slots_elapsed as u128)
-            .checked_mul(400)
-            .and_then(|v| v.checked_div(1000))
-            .ok_or(CrucibleError::InvalidAmount)?;
-        
-        // SECURITY FIX: Use higher precision and multiply before dividing to prevent precision loss
-        // Calculate interest: borrowedAmount × borrowRate × secondsElapsed / (100 × SECONDS_PER_YEAR)
-        // borrow_rate is stored as 10 = 10% APY (scaled by 100)
-        // Use u128 throughout and multiply numerator before dividing
-        let borrowed_u128 = position.borrowed_usdc as u128;
-        let borrow_rate_u128 = borrow_rate as u128;
-        
-        // Interest = (borrowed_usdc × borrow_rate × seconds_elapsed) / (100 × SECONDS_PER_YEAR)
-        // Multiply all numerators first, then divide by denominator to maximize precision
-        // SECURITY FIX: Return error on overflow instead of silently defaulting to 0
-        let interest = borrowed_u128
-            .checked_mul(borrow_rate_u128)
-            .and_then(|v| v.checked_mul(seconds_elapsed))
-            .and_then(|v| v.checked_div(100u128))
-            .and_then(|v| v.checked_div(SECONDS_PER_YEAR as u128))
-            .ok_or(CrucibleError::InvalidAmount)?;
-        
-        let total_owed = (position.borrowed_usdc as u128)
-            .checked_add(interest)
-            .ok_or(ProgramError::ArithmeticOverflow)?;
-        
+        // Repay what the pool will actually demand: the borrower account's scaled debt
+        // at the pool borrow index projected to now
+        let (borrower_account, debt_position) = lending_pool_usdc::position_borrower_account(
+            &ctx.accounts.borrower_account,
+            ctx.accounts.legacy_borrower_account.as_ref().map(|a| a.as_ref()),
+            &ctx.accounts.lending_market.key(),
+            &position.owner,
+            &position.key(),
+        )?;
+        let (total_owed, _) = position_debt(
+            &ctx.accounts.lending_market,
+            borrower_account,
+            &position.owner,
+            &debt_position,
+        )?;
         let repay_amount = if total_owed > u64::MAX as u128 {
             return Err(ProgramError::ArithmeticOverflow.into());
         } else {
@@ -372,15 +355,17 @@ pub fn close_leveraged_position(
         let cpi_accounts = RepayUSDC {
             pool: ctx.accounts.lending_market.to_account_info(),
             borrower: ctx.accounts.user.to_account_info(),
-            borrower_account: ctx.accounts.borrower_account.to_account_info(), // Anchor converts to borrowerAccount
+            borrower_account: borrower_account.clone(), // Anchor converts to borrowerAccount
             borrower_usdc_account: ctx.accounts.user_usdc_account.to_account_info(), // Anchor converts to borrowerUsdcAccount
             pool_vault: ctx.accounts.lending_vault.to_account_info(), // Anchor converts to poolVault
+            authorized_borrower: ctx.accounts.authorized_borrower.as_ref().map(|a| a.to_account_info()),
             token_program: ctx.accounts.token_program.to_account_info(), // Anchor converts to tokenProgram
+            forge_protocol: Some(ctx.accounts.forge_protocol.to_account_info()),
         };
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
         
         // Call lending pool repay_usdc instruction via CPI
-        lending_pool_usdc::cpi::repay_usdc(cpi_ctx, repay_amount)?;
+        lending_pool_usdc::cpi::repay_usdc(cpi_ctx, repay_amount, debt_position)?;
     }
 
     // SECURITY FIX (HIGH-001): Fetch current oracle price and validate slippage with manipulation protection
@@ -454,11 +439,13 @@ pub fn close_leveraged_position(
     let slots_elapsed_for_rate = clock.slot
         .checked_sub(position.created_at)
         .ok_or(CrucibleError::InvalidLeverage)?;
+    let pool_borrow_rate = lending_pool_usdc::load_pool(&ctx.accounts.lending_market)?.borrow_rate;
     let current_exchange_rate = calculate_lvf_exchange_rate(
         crucible,
         position.collateral,
         position.borrowed_usdc,
         slots_elapsed_for_rate,
+        pool_borrow_rate,
     )?;
 
     // Calculate tokens to return (includes yield) with checked arithmetic
@@ -474,10 +461,10 @@ pub fn close_leveraged_position(
         tokens_to_return as u64
     };
 
-    // Calculate close fees: 2% principal + 10% yield
+    // Calculate close fees on principal and yield from the crucible fee schedule
     let principal_fee = (position.collateral as u128)
-        .checked_mul(2)
-        .and_then(|v| v.checked_div(100))
+        .checked_mul(crucible.fee_schedule.close_fee_principal_bps as u128)
+        .and_then(|v| v.checked_div(10_000))
         .ok_or(ProgramError::ArithmeticOverflow)? as u64;
     
     // SECURITY FIX: Validate tokens_to_return >= position.collateral (yield cannot be negative in this context)
@@ -490,22 +477,16 @@ pub fn close_leveraged_position(
         .checked_sub(position.collateral)
         .ok_or(CrucibleError::InvalidAmount)?;
     let yield_fee = (yield_earned as u128)
-        .checked_mul(10)
-        .and_then(|v| v.checked_div(100))
+        .checked_mul(crucible.fee_schedule.close_fee_yield_bps as u128)
+        .and_then(|v| v.checked_div(10_000))
         .ok_or(ProgramError::ArithmeticOverflow)? as u64;
     
     let total_fee = principal_fee
         .checked_add(yield_fee)
         .ok_or(ProgramError::ArithmeticOverflow)?;
     
-    // Split fee: 80% to vault, 20% to treasury
-    let vault_fee_share = (total_fee as u128)
-        .checked_mul(80)
-        .and_then(|v| v.checked_div(100))
-        .ok_or(ProgramError::ArithmeticOverflow)? as u64;
-    let protocol_fee_share = total_fee
-        .checked_sub(vault_fee_share)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
+    // Split fee between vault and treasury (forge-core protocol_fee_rate)
+    let (vault_fee_share, protocol_fee_share) = split_fee(total_fee, protocol_fee_bps)?;
 
     let tokens_after_fee = tokens_to_return
         .checked_sub(total_fee)
@@ -700,163 +681,48 @@ pub fn get_oracle_price(
 ) -> Result<u64> {
     const MIN_PRICE_USD: f64 = 0.001; // $0.001 minimum - prevents rounding attacks
     const MAX_PRICE_USD: f64 = 1_000_000.0; // $1,000,000 maximum
-    // SECURITY FIX: Pyth program ID on Solana (mainnet: FsJ3A3y2mnZkbziN8pWmk1sDv1K8Z4gF8Y4n7yqG1LNv)
-    // For devnet/testnet, use: gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s
-    // Note: In production, consider making this configurable per network
-    const PYTH_PROGRAM_ID_MAINNET: &str = "FsJ3A3y2mnZkbziN8pWmk1sDv1K8Z4gF8Y4n7yqG1LNv";
-    
+
     if let Some(oracle_pubkey) = crucible.oracle {
         // Oracle is configured - must be provided
         let oracle = oracle_account
             .ok_or(CrucibleError::InvalidOraclePrice)?;
         
-        // SAFETY: Check if account exists and is initialized BEFORE accessing .key
-        // This prevents access violations when the account doesn't exist
-        require!(
-            !oracle.data_is_empty(),
-            CrucibleError::InvalidOraclePrice
-        );
-        
-        // SAFETY: Try to borrow data first to ensure account is accessible
-        // This prevents access violations when trying to access .key or .owner
-        let account_data_result = oracle.try_borrow_data();
-        if account_data_result.is_err() {
-            // Account doesn't exist or can't be accessed
-            return Err(CrucibleError::InvalidOraclePrice.into());
-        }
-        
-        // Now safe to access .key since we've validated the account exists
         require!(
             *oracle.key == oracle_pubkey,
             CrucibleError::InvalidOraclePrice
         );
-        
-        // SAFETY: Unwrap is safe here because we checked is_err() above
-        let account_data = account_data_result.unwrap();
-        
-        // SECURITY FIX: Validate oracle account owner is Pyth program
-        // This prevents using fake oracle accounts
-        let oracle_owner = oracle.owner;
-        // Note: In production, parse PYTH_PROGRAM_ID_MAINNET as Pubkey and compare
-        // For now, we rely on SDK validation which checks the account structure
-        // The SDK deserialization will fail if the account is not a valid Pyth PriceUpdateV2
-        
-        // Use Pyth SDK to validate account structure, then parse price data manually
-        // The SDK deserialization validates the account is a valid PriceUpdateV2
-        
-        // Validate account structure using SDK (ensures it's a valid PriceUpdateV2)
-        let _price_update = PriceUpdateV2::try_deserialize(&mut &account_data[..])
-            .map_err(|_| CrucibleError::InvalidOraclePrice)?;
-        
-        // After SDK validation, parse price fields manually from account data
-        // Pyth PriceUpdateV2 structure has price data at specific offsets
-        // This is safer than pure manual parsing since SDK validates the structure first
-        
-        // SECURITY FIX: Comprehensive bounds checking for all data reads
-        // Minimum required size: price (8) + expo (4) + padding (4) + publish_time (8) + confidence (8) = 132 bytes
-        const MIN_REQUIRED_SIZE: usize = 132;
-        require!(
-            account_data.len() >= MIN_REQUIRED_SIZE,
-            CrucibleError::InvalidOraclePrice
-        );
-        
-        // Parse price (i64) at offset 96
-        const PRICE_OFFSET: usize = 96;
-        const PRICE_SIZE: usize = 8;
-        require!(
-            account_data.len() >= PRICE_OFFSET + PRICE_SIZE,
-            CrucibleError::InvalidOraclePrice
-        );
-        let price_bytes = account_data[PRICE_OFFSET..PRICE_OFFSET + PRICE_SIZE]
-            .try_into()
-            .map_err(|_| CrucibleError::InvalidOraclePrice)?;
-        let price: i64 = i64::from_le_bytes(price_bytes);
-        
-        // Parse exponent (i32) at offset 104
-        const EXPO_OFFSET: usize = 104;
-        const EXPO_SIZE: usize = 4;
-        require!(
-            account_data.len() >= EXPO_OFFSET + EXPO_SIZE,
-            CrucibleError::InvalidOraclePrice
-        );
-        let expo_bytes = account_data[EXPO_OFFSET..EXPO_OFFSET + EXPO_SIZE]
-            .try_into()
-            .map_err(|_| CrucibleError::InvalidOraclePrice)?;
-        let expo: i32 = i32::from_le_bytes(expo_bytes);
-        
-        // Parse publish_time (u64) at offset 112 for staleness check
-        const PUB_TIME_OFFSET: usize = 112;
-        const PUB_TIME_SIZE: usize = 8;
-        require!(
-            account_data.len() >= PUB_TIME_OFFSET + PUB_TIME_SIZE,
-            CrucibleError::InvalidOraclePrice
-        );
-        let pub_time_bytes = account_data[PUB_TIME_OFFSET..PUB_TIME_OFFSET + PUB_TIME_SIZE]
-            .try_into()
+
+        // SECURITY FIX: Decode with the shared PriceUpdateV2 parser, which validates the Pyth
+        // receiver owner and discriminator and reads the verification level
+        let price_update = forge_core::oracle::load_price_update(oracle)
             .map_err(|_| CrucibleError::InvalidOraclePrice)?;
-        let publish_time: u64 = u64::from_le_bytes(pub_time_bytes);
-        
-        // SECURITY FIX: Parse confidence (u64) at offset 120 for confidence check
-        // Pyth PriceUpdateV2 structure: price (8) + expo (4) + padding (4) + publish_time (8) + confidence (8)
-        const CONFIDENCE_OFFSET: usize = 120;
-        const CONFIDENCE_SIZE: usize = 8;
-        if account_data.len() >= CONFIDENCE_OFFSET + CONFIDENCE_SIZE {
-            let conf_bytes = account_data[CONFIDENCE_OFFSET..CONFIDENCE_OFFSET + CONFIDENCE_SIZE]
-                .try_into()
-                .map_err(|_| CrucibleError::InvalidOraclePrice)?;
-            let confidence: u64 = u64::from_le_bytes(conf_bytes);
-            
-            // Calculate confidence as percentage of price
-            // confidence is in the same units as price (with same exponent)
-            let confidence_f64 = confidence as f64;
-            let expo_val = expo as i32;
-            let conf_price_f64 = if expo_val >= 0 {
-                confidence_f64 * (10.0_f64.powi(expo_val))
-            } else {
-                confidence_f64 / (10.0_f64.powi(-expo_val))
-            };
-            
-            // Calculate price for confidence percentage calculation
-            let price_value_f64 = price as f64;
-            let price_usd_temp = if expo_val >= 0 {
-                price_value_f64 * (10.0_f64.powi(expo_val))
-            } else {
-                price_value_f64 / (10.0_f64.powi(-expo_val))
-            };
-            
-            // Calculate confidence as basis points (percentage * 10000)
-            let confidence_bps = if price_usd_temp > 0.0 {
-                (conf_price_f64 / price_usd_temp * 10_000.0) as u64
-            } else {
-                return Err(CrucibleError::InvalidOraclePrice.into());
-            };
-            
-            // SECURITY FIX: Require confidence interval is within acceptable bounds
-            require!(
-                confidence_bps <= MAX_CONFIDENCE_BPS,
-                CrucibleError::InvalidOraclePrice
-            );
-        }
-        
-        // Get current clock for staleness check
-        let clock = Clock::get()?;
-        let current_time = clock.unix_timestamp as u64;
-        let age = current_time.saturating_sub(publish_time);
-        
-        require!(
-            age <= MAX_STALENESS_SECONDS,
-            CrucibleError::StaleOraclePrice
-        );
-        
+        // Only fully verified, fresh updates are used. The account is pinned by crucible.oracle,
+        // so its own feed is the expected feed.
+        let current_time = Clock::get()?.unix_timestamp;
+        let price_update = price_update
+            .price_no_older_than(current_time, MAX_STALENESS_SECONDS, &price_update.feed_id)
+            .ok_or(CrucibleError::StaleOraclePrice)?;
+        let expo = price_update.exponent;
+
         // Calculate actual price: price * 10^expo
-        let price_value_f64 = price as f64;
-        let expo_val = expo as i32;
-        
-        let price_usd = if expo_val >= 0 {
-            price_value_f64 * (10.0_f64.powi(expo_val))
+        let scale = |value: f64| if expo >= 0 {
+            value * (10.0_f64.powi(expo))
         } else {
-            price_value_f64 / (10.0_f64.powi(-expo_val))
+            value / (10.0_f64.powi(-expo))
         };
+        let price_usd = scale(price_update.price as f64);
+
+        // SECURITY FIX: Require confidence interval is within acceptable bounds
+        // confidence is in the same units as price, so compare it as basis points of price
+        let confidence_bps = if price_usd > 0.0 {
+            (scale(price_update.conf as f64) / price_usd * 10_000.0) as u64
+        } else {
+            return Err(CrucibleError::InvalidOraclePrice.into());
+        };
+        require!(
+            confidence_bps <= MAX_CONFIDENCE_BPS,
+            CrucibleError::InvalidOraclePrice
+        );
         
         // SECURITY FIX: Tighter price bounds per token type
         // For SOL, typical range is $1-$1000, but allow wider for other tokens
@@ -888,12 +754,86 @@ pub fn get_oracle_price(
     }
 }
 
-/// Calculate LVF exchange rate based on time and leverage
+/// Current debt of a position's lending-pool borrower account (including interest
+/// accrued up to now) and the pool's borrow rate (10 = 10% APY)
+/// `debt_position` is the position reference from lending_pool_usdc::position_borrower_account.
+fn position_debt(
+    lending_market: &AccountInfo,
+    borrower_account: &AccountInfo,
+    owner: &Pubkey,
+    debt_position: &Pubkey,
+) -> Result<(u128, u64)> {
+    let pool = lending_pool_usdc::load_pool(lending_market)?;
+    let borrower = lending_pool_usdc::load_borrower_account(
+        borrower_account,
+        &lending_market.key(),
+        owner,
+        debt_position,
+    )?;
+    let now = Clock::get()?.unix_timestamp as u64;
+    let debt = lending_pool_usdc::borrower_debt(&pool, &borrower, now)?;
+    Ok((debt as u128, pool.borrow_rate))
+}
+
+/// Debt repaid and collateral value seized by one partial liquidation, in USDC
+/// Repays just enough to bring LTV back to the target, capped by the close factor.
+fn liquidation_amounts(
+    total_debt: u128,
+    collateral_value_usdc: u128,
+    config: &LiquidationConfig,
+) -> Result<(u128, u128)> {
+    let close_factor_bps = config.close_factor_bps as u128;
+    let target_ltv_bps = config.target_ltv_bps as u128;
+
+    // Accounting for the bonus:
+    // (debt - repay) / (value - repay * (1 + bonus)) = target
+    // => repay = (debt - target * value) / (1 - target * (1 + bonus))
+    let target_debt_scaled = collateral_value_usdc
+        .checked_mul(target_ltv_bps)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let target_numerator = total_debt
+        .checked_mul(10_000)
+        .and_then(|v| v.checked_sub(target_debt_scaled))
+        .and_then(|v| v.checked_mul(10_000))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let target_denominator = (10_000u128 * 10_000)
+        .checked_sub(target_ltv_bps * (10_000 + LIQUIDATION_BONUS_BPS))
+        .filter(|d| *d > 0)
+        .ok_or(CrucibleError::InvalidLiquidationConfig)?;
+    let repay_to_target = target_numerator
+        .checked_add(target_denominator - 1)
+        .and_then(|v| v.checked_div(target_denominator))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    // Close factor caps how much debt a single liquidation may repay
+    let max_repay = total_debt
+        .checked_mul(close_factor_bps)
+        .and_then(|v| v.checked_div(10_000))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let mut debt_repaid = repay_to_target.min(max_repay);
+
+    // Seized value = repaid debt + bonus; if that exceeds the collateral, seize it all
+    let mut seized_value_usdc = debt_repaid
+        .checked_mul(10_000 + LIQUIDATION_BONUS_BPS)
+        .and_then(|v| v.checked_div(10_000))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if seized_value_usdc > collateral_value_usdc {
+        seized_value_usdc = collateral_value_usdc;
+        debt_repaid = collateral_value_usdc
+            .checked_mul(10_000)
+            .and_then(|v| v.checked_div(10_000 + LIQUIDATION_BONUS_BPS))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+    }
+    Ok((debt_repaid, seized_value_usdc))
+}
+
+/// Calculate LVF exchange rate based on time, leverage and the pool borrow rate
 fn calculate_lvf_exchange_rate(
     crucible: &Crucible,
     collateral: u64,
     borrowed_usdc: u64,
     slots_elapsed: u64,
+    borrow_rate: u64, // Lending pool borrow APY, 10 = 10% (scaled by 100)
 ) -> Result<u64> {
     // Base exchange rate starts at 1.0 (1_000_000 scaled)
     let base_rate = 1_000_000u64;
@@ -914,7 +854,7 @@ fn calculate_lvf_exchange_rate(
         .ok_or(ProgramError::ArithmeticOverflow)?;
     
     let base_apy = crucible.fee_rate as u128; // Use crucible fee rate as base APY
-    let borrow_rate = 10u128; // 10% APY (from lending-pool, scaled by 100: 10 = 10%)
+    let borrow_rate = borrow_rate as u128;
     
     // Calculate: (base_apy * leverage_multiplier) / 100
     let leveraged_apy = base_apy
@@ -1000,15 +940,75 @@ fn calculate_lvf_exchange_rate(
     Ok(exchange_rate as u64)
 }
 
+/// Migrate a legacy (nonce-less) leveraged position to a nonce-seeded PDA
+/// Permissionless so keepers can migrate unhealthy legacy positions before liquidating them;
+/// the legacy account's rent is refunded to the position owner
+pub fn migrate_legacy_leveraged_position(
+    ctx: Context<MigrateLegacyLeveragedPosition>,
+    position_nonce: u64,
+) -> Result<()> {
+    let legacy_info = ctx.accounts.legacy_position.to_account_info();
+    require_keys_eq!(*legacy_info.owner, crate::ID, CrucibleError::InvalidPosition);
+
+    let data = legacy_info.try_borrow_data()?;
+    require!(
+        data.len() == 8 + LeveragedPositionLegacy::LEN,
+        CrucibleError::InvalidPosition
+    );
+    require!(
+        data[..8] == LeveragedPosition::DISCRIMINATOR,
+        CrucibleError::InvalidPosition
+    );
+    let legacy = LeveragedPositionLegacy::deserialize(&mut &data[8..])?;
+    drop(data);
+
+    require!(legacy.is_open, CrucibleError::PositionNotOpen);
+    require_keys_eq!(
+        legacy.owner,
+        ctx.accounts.position_owner.key(),
+        CrucibleError::Unauthorized
+    );
+
+    let position = &mut ctx.accounts.position;
+    position.id = position.key();
+    position.owner = legacy.owner;
+    position.token = legacy.token;
+    position.collateral = legacy.collateral;
+    position.borrowed_usdc = legacy.borrowed_usdc;
+    position.leverage_factor = legacy.leverage_factor;
+    position.entry_price = legacy.entry_price;
+    position.entry_exchange_rate = legacy.entry_exchange_rate;
+    position.current_value = legacy.current_value;
+    position.yield_earned = legacy.yield_earned;
+    position.is_open = true;
+    position.created_at = legacy.created_at;
+    position.bump = ctx.bumps.position;
+    position.nonce = position_nonce;
+
+    // Close the legacy account - refund rent to the owner and wipe the data
+    let owner_info = ctx.accounts.position_owner.to_account_info();
+    let legacy_lamports = legacy_info.lamports();
+    **owner_info.try_borrow_mut_lamports()? = owner_info
+        .lamports()
+        .checked_add(legacy_lamports)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **legacy_info.try_borrow_mut_lamports()? = 0;
+    legacy_info.assign(&System::id());
+    legacy_info.realloc(0, false)?;
+
+    msg!("Migrated legacy leveraged position {} to {}", legacy.id, position.id);
+    Ok(())
+}
+
 /// Check position health (LTV in basis points)
 /// Returns LTV (Loan-to-Value) in basis points: (debt * 10000) / collateral_value
 /// Lower is better. If LTV > liquidation_threshold, position is liquidatable.
 pub fn health_check(
     ctx: Context<HealthCheck>,
+    _position_nonce: u64, // Used for PDA derivation in account constraints
 ) -> Result<u64> {
     let position = &ctx.accounts.position;
     let crucible = &ctx.accounts.crucible;
-    let clock = Clock::get()?;
     
     require!(position.is_open, CrucibleError::PositionNotOpen);
     
@@ -1029,34 +1029,20 @@ pub fn health_check(
         .and_then(|v| v.checked_div(1_000_000))
         .ok_or(ProgramError::ArithmeticOverflow)?;
     
-    // Calculate total debt (borrowed + accrued interest)
-    // SECURITY FIX: Validate created_at <= clock.slot before calculating slots_elapsed
-    require!(
-        position.created_at <= clock.slot,
-        CrucibleError::InvalidLeverage
-    );
-    let slots_elapsed = clock.slot
-        .checked_sub(position.created_at)
-        .ok_or(CrucibleError::InvalidLeverage)?;
-    let slots_per_year = 78_840_000u128; // Approximate slots per year (400ms per slot)
-    let borrow_rate = 10u64; // 10% APY (from lending-pool state)
-    let rate_decimal = (borrow_rate as u128) * 1_000_000 / 100; // Convert to scaled decimal
-    
-    let years_elapsed = (slots_elapsed as u128)
-        .checked_mul(1_000_000)
-        .and_then(|v| v.checked_div(slots_per_year))
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    
-    // Interest = borrowed_usdc × (rate_decimal / 1_000_000) × (years_elapsed / 1_000_000)
-    let interest = (position.borrowed_usdc as u128)
-        .checked_mul(rate_decimal)
-        .and_then(|v| v.checked_mul(years_elapsed))
-        .and_then(|v| v.checked_div(1_000_000_000_000u128))
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    
-    let total_debt = (position.borrowed_usdc as u128)
-        .checked_add(interest)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
+    // Total debt from the position's lending-pool borrower account, accrued to now
+    let (borrower_account, debt_position) = lending_pool_usdc::position_borrower_account(
+        &ctx.accounts.borrower_account,
+        ctx.accounts.legacy_borrower_account.as_ref().map(|a| a.as_ref()),
+        &ctx.accounts.lending_market.key(),
+        &position.owner,
+        &position.key(),
+    )?;
+    let (total_debt, _) = position_debt(
+        &ctx.accounts.lending_market,
+        borrower_account,
+        &position.owner,
+        &debt_position,
+    )?;
     
     // Calculate LTV in basis points: (debt * 10000) / collateral_value
     // Prevent division by zero
@@ -1077,13 +1063,16 @@ pub fn health_check(
     Ok(ltv_bps as u64)
 }
 
-/// Liquidate an undercollateralized leveraged position
-/// Liquidator receives a bonus for repaying the debt
+/// Partially liquidate an undercollateralized leveraged position
+/// Liquidator repays debt (up to the close factor) to restore the target LTV
+/// and seizes the equivalent collateral plus a bonus
 pub fn liquidate_position(
     ctx: Context<LiquidatePosition>,
+    _position_nonce: u64, // Used for PDA derivation in account constraints
 ) -> Result<()> {
-    // Check if crucible is paused
-    require!(!ctx.accounts.crucible.paused, CrucibleError::ProtocolPaused);
+    // Check if liquidations are paused
+    require_not_paused(ctx.accounts.crucible.pause_flags, PAUSE_LIQUIDATIONS)?;
+    forge_core::require_protocol_active_if_present(ctx.accounts.forge_protocol.as_deref())?;
     
     // Get base_mint before mutable borrow
     let base_mint_key = ctx.accounts.crucible.base_mint;
@@ -1108,33 +1097,21 @@ pub fn liquidate_position(
         .and_then(|v| v.checked_div(1_000_000))
         .ok_or(ProgramError::ArithmeticOverflow)?;
     
-    // Calculate total debt (borrowed + accrued interest)
-    // SECURITY FIX: Validate created_at <= clock.slot before calculating slots_elapsed
-    require!(
-        position.created_at <= clock.slot,
-        CrucibleError::InvalidLeverage
-    );
-    let slots_elapsed = clock.slot
-        .checked_sub(position.created_at)
-        .ok_or(CrucibleError::InvalidLeverage)?;
-    let slots_per_year = 78_840_000u128;
-    let borrow_rate = 10u64; // 10% APY
-    let rate_decimal = (borrow_rate as u128) * 1_000_000 / 100;
-    
-    let years_elapsed = (slots_elapsed as u128)
-        .checked_mul(1_000_000)
-        .and_then(|v| v.checked_div(slots_per_year))
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    
-    let interest = (position.borrowed_usdc as u128)
-        .checked_mul(rate_decimal)
-        .and_then(|v| v.checked_mul(years_elapsed))
-        .and_then(|v| v.checked_div(1_000_000_000_000u128))
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    
-    let total_debt = (position.borrowed_usdc as u128)
-        .checked_add(interest)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
+    // Total debt from the position's lending-pool borrower account, accrued to now -
+    // the same amount the pool will demand on repayment
+    let (borrower_account, debt_position) = lending_pool_usdc::position_borrower_account(
+        &ctx.accounts.borrower_account,
+        ctx.accounts.legacy_borrower_account.as_ref().map(|a| a.as_ref()),
+        &ctx.accounts.lending_market.key(),
+        &position.owner,
+        &position.key(),
+    )?;
+    let (total_debt, _) = position_debt(
+        &ctx.accounts.lending_market,
+        borrower_account,
+        &position.owner,
+        &debt_position,
+    )?;
     
     // Calculate LTV in basis points
     if collateral_value_usdc == 0 {
@@ -1146,117 +1123,64 @@ pub fn liquidate_position(
         .and_then(|v| v.checked_div(collateral_value_usdc))
         .ok_or(ProgramError::ArithmeticOverflow)?;
     
-    // Liquidation threshold: 85% LTV (8500 basis points)
     // Position is liquidatable if LTV > 85%
-    const LIQUIDATION_THRESHOLD_BPS: u128 = 8500;
-    
     require!(
         ltv_bps > LIQUIDATION_THRESHOLD_BPS,
         CrucibleError::PositionNotLiquidatable
     );
-    
-    // Calculate liquidation bonus: 5% of debt (500 basis points)
-    const LIQUIDATION_BONUS_BPS: u128 = 500;
-    let liquidation_bonus = total_debt
-        .checked_mul(LIQUIDATION_BONUS_BPS)
-        .and_then(|v| v.checked_div(10_000))
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    
-    // Total amount to repay: debt + bonus
-    let total_repay_amount = total_debt
-        .checked_add(liquidation_bonus)
+
+    // Close factor and target LTV come from the crucible's liquidation config
+    let (debt_repaid, seized_value_usdc) = liquidation_amounts(
+        total_debt,
+        collateral_value_usdc,
+        &crucible.liquidation_config,
+    )?;
+    let liquidation_bonus = seized_value_usdc
+        .checked_sub(debt_repaid)
         .ok_or(ProgramError::ArithmeticOverflow)?;
-    
+
+    let collateral_seized = seized_value_usdc
+        .checked_mul(1_000_000)
+        .and_then(|v| v.checked_div(current_base_token_price as u128))
+        .ok_or(ProgramError::ArithmeticOverflow)?
+        .min(position.collateral as u128);
+
     // Ensure amounts fit in u64
-    let total_repay_amount_u64 = if total_repay_amount > u64::MAX as u128 {
+    if total_debt > u64::MAX as u128 || liquidation_bonus > u64::MAX as u128 {
         return Err(ProgramError::ArithmeticOverflow.into());
-    } else {
-        total_repay_amount as u64
-    };
-    
-    let liquidation_bonus_u64 = if liquidation_bonus > u64::MAX as u128 {
-        return Err(ProgramError::ArithmeticOverflow.into());
-    } else {
-        liquidation_bonus as u64
-    };
-    
-    // SECURITY FIX: Validate borrower_account PDA derivation
-    let (expected_borrower_pda, _bump) = Pubkey::find_program_address(
-        &[b"borrower", position.owner.as_ref()],
-        &ctx.accounts.lending_program.key(),
-    );
-    require!(
-        ctx.accounts.borrower_account.key() == expected_borrower_pda,
-        CrucibleError::InvalidLendingProgram
-    );
-    
+    }
+    let debt_repaid_u64 = debt_repaid as u64;
+    let liquidation_bonus_u64 = liquidation_bonus as u64;
+    let collateral_seized_u64 = collateral_seized as u64;
+    require!(debt_repaid_u64 > 0, CrucibleError::InvalidAmount);
+
     // SECURITY FIX: Validate lending program ID matches expected constant
     require!(
         ctx.accounts.lending_program.key() == crate::LENDING_POOL_PROGRAM_ID,
         CrucibleError::InvalidLendingProgram
     );
-    
-    // Repay debt via CPI to lending pool
+
+    // Liquidator repays part of the owner's debt - the owner does not sign
     let cpi_program = ctx.accounts.lending_program.to_account_info();
-    let cpi_accounts = RepayUSDC {
+    let cpi_accounts = RepayUSDCOnBehalf {
         pool: ctx.accounts.lending_market.to_account_info(),
-        borrower: ctx.accounts.position_owner.to_account_info(), // Position owner, not liquidator
-        borrower_account: ctx.accounts.borrower_account.to_account_info(),
-        borrower_usdc_account: ctx.accounts.liquidator_usdc_account.to_account_info(), // Liquidator pays
+        payer: ctx.accounts.liquidator.to_account_info(),
+        borrower: ctx.accounts.position_owner.to_account_info(),
+        borrower_account: borrower_account.clone(),
+        payer_usdc_account: ctx.accounts.liquidator_usdc_account.to_account_info(),
         pool_vault: ctx.accounts.lending_vault.to_account_info(),
+        authorized_borrower: ctx.accounts.authorized_borrower.as_ref().map(|a| a.to_account_info()),
         token_program: ctx.accounts.token_program.to_account_info(),
+        forge_protocol: ctx.accounts.forge_protocol.as_ref().map(|p| p.to_account_info()),
     };
     let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-    
-    // Liquidator repays the debt
-    lending_pool_usdc::cpi::repay_usdc(cpi_ctx, total_repay_amount_u64)?;
-    
-    // Calculate collateral to seize: enough to cover debt repayment
-    // We seize collateral equivalent to debt value (in base tokens)
-    let collateral_to_seize = total_debt
-        .checked_mul(1_000_000)
-        .and_then(|v| v.checked_div(current_base_token_price as u128))
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    
-    // Add liquidation bonus to seized collateral
-    let bonus_collateral = liquidation_bonus
-        .checked_mul(1_000_000)
-        .and_then(|v| v.checked_div(current_base_token_price as u128))
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    
-    let total_collateral_seized = collateral_to_seize
-        .checked_add(bonus_collateral)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    
-    // Ensure we don't seize more than available
-    let total_collateral_seized_u64 = if total_collateral_seized > position.collateral as u128 {
-        position.collateral // Seize all if needed
-    } else if total_collateral_seized > u64::MAX as u128 {
-        return Err(ProgramError::ArithmeticOverflow.into());
-    } else {
-        total_collateral_seized as u64
-    };
-    
-    // SECURITY FIX (MEDIUM-006): Validate seized collateral value doesn't exceed debt + max bonus
-    let seized_value_usdc = (total_collateral_seized_u64 as u128)
-        .checked_mul(current_base_token_price as u128)
-        .and_then(|v| v.checked_div(1_000_000))
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    
-    let max_seized_value = total_debt
-        .checked_add(liquidation_bonus)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    
-    require!(
-        seized_value_usdc <= max_seized_value,
-        CrucibleError::InvalidAmount
-    );
-    
+    lending_pool_usdc::cpi::repay_usdc_on_behalf(cpi_ctx, debt_repaid_u64, debt_position)?;
+
     require!(
         ctx.accounts.token_program.key() == anchor_spl::token::ID,
         CrucibleError::InvalidProgram
     );
-    
+
     // Transfer seized collateral to liquidator
     let seeds = &[
         b"crucible",
@@ -1264,7 +1188,7 @@ pub fn liquidate_position(
         &[crucible.bump],
     ];
     let signer = &[&seeds[..]];
-    
+
     let cpi_accounts = Transfer {
         from: ctx.accounts.crucible_vault.to_account_info(),
         to: ctx.accounts.liquidator_token_account.to_account_info(),
@@ -1272,44 +1196,78 @@ pub fn liquidate_position(
     };
     let cpi_program = ctx.accounts.token_program.to_account_info();
     let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-    token::transfer(cpi_ctx, total_collateral_seized_u64)?;
-    
-    // Update position state
-    position.is_open = false;
-    // SECURITY FIX: Validate collateral >= total_collateral_seized before subtraction
-    require!(
-        position.collateral >= total_collateral_seized_u64,
-        CrucibleError::InvalidAmount
-    );
+    token::transfer(cpi_ctx, collateral_seized_u64)?;
+
+    // Update position in place - borrowed_usdc records the remaining debt (interest itself
+    // accrues on the borrower account's index), and the exchange-rate clock restarts
     position.collateral = position.collateral
-        .checked_sub(total_collateral_seized_u64)
+        .checked_sub(collateral_seized_u64)
         .ok_or(CrucibleError::InvalidAmount)?;
-    position.borrowed_usdc = 0; // Debt repaid
-    
-    // Update crucible state
-    // SECURITY FIX: Validate total_leveraged_positions > 0 before subtracting
-    require!(
-        crucible.total_leveraged_positions > 0,
-        CrucibleError::InvalidAmount
-    );
-    crucible.total_leveraged_positions = crucible.total_leveraged_positions
-        .checked_sub(1)
+    let remaining_debt = total_debt
+        .checked_sub(debt_repaid)
         .ok_or(CrucibleError::InvalidAmount)?;
-    
+    position.borrowed_usdc = remaining_debt as u64;
+    position.created_at = clock.slot;
+
+    // Close the position once all collateral has been seized - any debt left is bad debt in the pool
+    let fully_liquidated = position.collateral == 0;
+    if fully_liquidated {
+        position.is_open = false;
+
+        // Record the remaining debt so the pool authority can cover it with cover_bad_debt
+        if remaining_debt > 0 {
+            let (Some(authorized_borrower), Some(bad_debt_record), Some(system_program)) = (
+                ctx.accounts.authorized_borrower.as_ref(),
+                ctx.accounts.bad_debt_record.as_ref(),
+                ctx.accounts.system_program.as_ref(),
+            ) else {
+                return Err(CrucibleError::BadDebtAccountsRequired.into());
+            };
+            let cpi_accounts = RecordBadDebt {
+                pool: ctx.accounts.lending_market.to_account_info(),
+                borrower: ctx.accounts.position_owner.to_account_info(),
+                borrower_account: borrower_account.clone(),
+                borrow_authority: ctx.accounts.crucible_authority.to_account_info(),
+                authorized_borrower: authorized_borrower.to_account_info(),
+                bad_debt_record: bad_debt_record.to_account_info(),
+                payer: ctx.accounts.liquidator.to_account_info(),
+                system_program: system_program.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.lending_program.to_account_info(),
+                cpi_accounts,
+                signer,
+            );
+            lending_pool_usdc::cpi::record_bad_debt(cpi_ctx, debt_position)?;
+        }
+
+        // SECURITY FIX: Validate total_leveraged_positions > 0 before subtracting
+        require!(
+            crucible.total_leveraged_positions > 0,
+            CrucibleError::InvalidAmount
+        );
+        crucible.total_leveraged_positions = crucible.total_leveraged_positions
+            .checked_sub(1)
+            .ok_or(CrucibleError::InvalidAmount)?;
+    }
+
     emit!(LeveragedPositionLiquidated {
         position_id: position.id,
         owner: position.owner,
         liquidator: ctx.accounts.liquidator.key(),
-        collateral_seized: total_collateral_seized_u64,
-        debt_repaid: total_repay_amount_u64,
+        collateral_seized: collateral_seized_u64,
+        debt_repaid: debt_repaid_u64,
         liquidation_bonus: liquidation_bonus_u64,
+        remaining_collateral: position.collateral,
+        remaining_debt: position.borrowed_usdc,
+        fully_liquidated,
     });
-    
+
     Ok(())
 }
 
 #[derive(Accounts)]
-#[instruction(position_id: Pubkey)]
+#[instruction(collateral_amount: u64, leverage_factor: u64, position_nonce: u64)]
 pub struct OpenLeveragedPosition<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
@@ -1342,14 +1300,14 @@ pub struct OpenLeveragedPosition<'info> {
         init,
         payer = user,
         space = 8 + LeveragedPosition::LEN,
-        seeds = [b"position", user.key().as_ref(), crucible.key().as_ref()],
+        seeds = [b"position", user.key().as_ref(), crucible.key().as_ref(), &position_nonce.to_le_bytes()],
         bump
     )]
     pub position: Box<Account<'info, LeveragedPosition>>,
 
     /// CHECK: Position ID PDA
     #[account(
-        seeds = [b"position", user.key().as_ref(), crucible.key().as_ref()],
+        seeds = [b"position", user.key().as_ref(), crucible.key().as_ref(), &position_nonce.to_le_bytes()],
         bump,
     )]
     pub position_id: UncheckedAccount<'info>,
@@ -1365,11 +1323,16 @@ pub struct OpenLeveragedPosition<'info> {
     /// If provided, must match crucible.oracle
     pub oracle: Option<UncheckedAccount<'info>>,
     
-    /// CHECK: Lending program for borrowing USDC (USDC-only lending pool)
+    /// CHECK: Lending program for borrowing the quote asset
     #[account(
         constraint = lending_program.key() == LENDING_POOL_PROGRAM_ID @ CrucibleError::InvalidLendingProgram
     )]
     pub lending_program: UncheckedAccount<'info>,
+    /// Quote asset borrowed for leverage - must be the lending_market's mint
+    #[account(
+        constraint = quote_mint.decimals == QUOTE_MINT_DECIMALS @ CrucibleError::InvalidMint
+    )]
+    pub quote_mint: Box<Account<'info, Mint>>,
     /// CHECK: SECURITY FIX - Validate lending_market is owned by lending_program
     #[account(
         mut,
@@ -1382,30 +1345,35 @@ pub struct OpenLeveragedPosition<'info> {
         constraint = *pool_authority.owner == lending_program.key() @ CrucibleError::InvalidLendingProgram
     )]
     pub pool_authority: UncheckedAccount<'info>,
-    /// SECURITY FIX: Validate borrower_account is a PDA owned by lending_program
-    /// Note: Using UncheckedAccount with constraint since we can't derive seeds from lending program
-    /// CHECK: SECURITY FIX - Validate borrower_account is a PDA owned by lending_program
+    /// CHECK: Borrower account for this position ([b"borrower", lending_market, user, position] in the
+    /// lending program) - PDA derivation validated in instruction, created by the lending program if needed
+    #[account(mut)]
+    pub borrower_account: UncheckedAccount<'info>,
+    /// CHECK: SECURITY FIX - Lending pool vault PDA of lending_market, never another pool token account
     #[account(
         mut,
-        constraint = *borrower_account.owner == lending_program.key() @ CrucibleError::InvalidLendingProgram
+        address = lending_pool_usdc::pool_vault_address(&lending_market.key()) @ CrucibleError::InvalidLendingProgram
     )]
-    pub borrower_account: UncheckedAccount<'info>,
-    /// CHECK: SECURITY FIX - Validate lending_vault is owned by lending_program
+    pub lending_vault: UncheckedAccount<'info>,
+    /// CHECK: Lending pool registry entry for this crucible's borrow authority - validated by the lending program
     #[account(
         mut,
-        constraint = *lending_vault.owner == lending_program.key() @ CrucibleError::InvalidLendingProgram
+        constraint = *authorized_borrower.owner == lending_program.key() @ CrucibleError::InvalidLendingProgram
     )]
-    pub lending_vault: UncheckedAccount<'info>,
-    /// CHECK: User USDC account for receiving borrowed funds
+    pub authorized_borrower: UncheckedAccount<'info>,
+    /// CHECK: User quote token account for receiving borrowed funds - mint checked by the token program
     #[account(mut)]
     pub user_usdc_account: UncheckedAccount<'info>,
 
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
+
+    /// CHECK: Forge protocol PDA - entries are refused while the protocol is inactive, validated in forge_core::load_protocol
+    pub forge_protocol: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
-#[instruction(position_id: Pubkey)]
+#[instruction(position_id: Pubkey, max_slippage_bps: u64, position_nonce: u64)]
 pub struct CloseLeveragedPosition<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
@@ -1415,9 +1383,10 @@ pub struct CloseLeveragedPosition<'info> {
 
     #[account(
         mut,
-        seeds = [b"position", user.key().as_ref(), crucible.key().as_ref()],
+        seeds = [b"position", user.key().as_ref(), crucible.key().as_ref(), &position_nonce.to_le_bytes()],
         bump = position.bump,
         constraint = position.id == position_id @ CrucibleError::InvalidPosition,
+        constraint = position.nonce == position_nonce @ CrucibleError::InvalidPosition,
     )]
     pub position: Box<Account<'info, LeveragedPosition>>,
 
@@ -1460,21 +1429,22 @@ pub struct CloseLeveragedPosition<'info> {
         constraint = *lending_market.owner == lending_program.key() @ CrucibleError::InvalidLendingProgram
     )]
     pub lending_market: UncheckedAccount<'info>,
-    /// CHECK: SECURITY FIX - Validate borrower_account is a PDA owned by lending_program
-    /// Note: Using UncheckedAccount since BorrowerAccount is from another program
-    /// PDA derivation validated in instruction
-    /// CHECK: SECURITY FIX - Validate borrower_account is a PDA owned by lending_program
-    #[account(
-        mut,
-        constraint = *borrower_account.owner == lending_program.key() @ CrucibleError::InvalidLendingProgram
-    )]
+    /// CHECK: Borrower account for this position - validated by lending_pool_usdc::position_borrower_account
+    #[account(mut)]
     pub borrower_account: UncheckedAccount<'info>,
-    /// CHECK: SECURITY FIX - Validate lending_vault is owned by lending_program
+    /// CHECK: Owner's legacy borrower account - only for positions that borrowed before per-position
+    /// debt accounts, validated by lending_pool_usdc::position_borrower_account
+    #[account(mut)]
+    pub legacy_borrower_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: SECURITY FIX - Lending pool vault PDA of lending_market, never another pool token account
     #[account(
         mut,
-        constraint = *lending_vault.owner == lending_program.key() @ CrucibleError::InvalidLendingProgram
+        address = lending_pool_usdc::pool_vault_address(&lending_market.key()) @ CrucibleError::InvalidLendingProgram
     )]
     pub lending_vault: UncheckedAccount<'info>,
+    /// CHECK: Credit line the debt was drawn on - validated by the lending program
+    #[account(mut)]
+    pub authorized_borrower: Option<UncheckedAccount<'info>>,
     /// CHECK: User USDC account for repaying loan
     #[account(mut)]
     pub user_usdc_account: UncheckedAccount<'info>,
@@ -1485,6 +1455,9 @@ pub struct CloseLeveragedPosition<'info> {
     pub user_lp_token_account: Box<Account<'info, TokenAccount>>,
 
     pub token_program: Program<'info, Token>,
+
+    /// CHECK: Forge protocol PDA - supplies protocol_fee_rate and the kill switch, validated in forge_core::load_protocol
+    pub forge_protocol: UncheckedAccount<'info>,
 }
 
 #[account]
@@ -1502,10 +1475,34 @@ pub struct LeveragedPosition {
     pub is_open: bool,
     pub created_at: u64, // Slot when created
     pub bump: u8,
+    pub nonce: u64, // Nonce for allowing multiple positions per user per crucible
 }
 
 impl LeveragedPosition {
-    pub const LEN: usize = 32 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 8 + 1; // Added 8 for entry_exchange_rate
+    pub const LEN: usize = 32 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 8 + 1 + 8; // Added 8 for entry_exchange_rate, 8 for nonce
+}
+
+/// Legacy leveraged position (created before nonce was added to the PDA seeds)
+/// Same discriminator as LeveragedPosition, but without the trailing nonce field
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LeveragedPositionLegacy {
+    pub id: Pubkey,
+    pub owner: Pubkey,
+    pub token: Pubkey,
+    pub collateral: u64,
+    pub borrowed_usdc: u64,
+    pub leverage_factor: u64,
+    pub entry_price: u64,
+    pub entry_exchange_rate: u64,
+    pub current_value: u64,
+    pub yield_earned: u64,
+    pub is_open: bool,
+    pub created_at: u64,
+    pub bump: u8,
+}
+
+impl LeveragedPositionLegacy {
+    pub const LEN: usize = LeveragedPosition::LEN - 8; // No nonce
 }
 
 #[event]
@@ -1515,6 +1512,7 @@ pub struct LeveragedPositionOpened {
     pub token: Pubkey,
     pub collateral: u64,
     pub borrowed_usdc: u64,
+    pub quote_mint: Pubkey, // Asset borrowed (borrowed_usdc is denominated in it)
     pub leverage_factor: u64,
     pub entry_exchange_rate: u64, // Crucible exchange rate at open for real yield tracking
 }
@@ -1537,25 +1535,73 @@ pub struct LeveragedPositionLiquidated {
     pub collateral_seized: u64,
     pub debt_repaid: u64,
     pub liquidation_bonus: u64,
+    pub remaining_collateral: u64,
+    pub remaining_debt: u64,
+    pub fully_liquidated: bool,
 }
 
 #[derive(Accounts)]
+#[instruction(position_nonce: u64)]
+pub struct MigrateLegacyLeveragedPosition<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub crucible: Box<Account<'info, Crucible>>,
+
+    /// CHECK: Legacy position - nonce-less PDA with the old layout, validated in instruction
+    #[account(
+        mut,
+        seeds = [b"position", position_owner.key().as_ref(), crucible.key().as_ref()],
+        bump,
+    )]
+    pub legacy_position: UncheckedAccount<'info>,
+
+    /// CHECK: Position owner - validated against the legacy position, receives its rent
+    #[account(mut)]
+    pub position_owner: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + LeveragedPosition::LEN,
+        seeds = [b"position", position_owner.key().as_ref(), crucible.key().as_ref(), &position_nonce.to_le_bytes()],
+        bump
+    )]
+    pub position: Box<Account<'info, LeveragedPosition>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(position_nonce: u64)]
 pub struct HealthCheck<'info> {
     #[account(mut)]
     pub crucible: Box<Account<'info, Crucible>>,
     
     #[account(
-        seeds = [b"position", position.owner.as_ref(), crucible.key().as_ref()],
+        seeds = [b"position", position.owner.as_ref(), crucible.key().as_ref(), &position_nonce.to_le_bytes()],
         bump = position.bump,
+        constraint = position.nonce == position_nonce @ CrucibleError::InvalidPosition,
     )]
     pub position: Box<Account<'info, LeveragedPosition>>,
     
     /// CHECK: Optional oracle account for price feeds
     /// If provided, must match crucible.oracle
     pub oracle: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Lending pool - owner and PDA validated by lending_pool_usdc::load_pool
+    pub lending_market: UncheckedAccount<'info>,
+
+    /// CHECK: Borrower account for this position - validated by lending_pool_usdc::position_borrower_account
+    pub borrower_account: UncheckedAccount<'info>,
+
+    /// CHECK: Owner's legacy borrower account - only for positions that borrowed before per-position
+    /// debt accounts, validated by lending_pool_usdc::position_borrower_account
+    pub legacy_borrower_account: Option<UncheckedAccount<'info>>,
 }
 
 #[derive(Accounts)]
+#[instruction(position_nonce: u64)]
 pub struct LiquidatePosition<'info> {
     #[account(mut)]
     pub liquidator: Signer<'info>,
@@ -1565,8 +1611,9 @@ pub struct LiquidatePosition<'info> {
     
     #[account(
         mut,
-        seeds = [b"position", position.owner.as_ref(), crucible.key().as_ref()],
+        seeds = [b"position", position.owner.as_ref(), crucible.key().as_ref(), &position_nonce.to_le_bytes()],
         bump = position.bump,
+        constraint = position.nonce == position_nonce @ CrucibleError::InvalidPosition,
     )]
     pub position: Box<Account<'info, LeveragedPosition>>,
     
@@ -1612,26 +1659,81 @@ pub struct LiquidatePosition<'info> {
     )]
     pub lending_market: UncheckedAccount<'info>,
     
-    /// CHECK: SECURITY FIX - Validate borrower_account is a PDA owned by lending_program
-    /// Note: Using UncheckedAccount since BorrowerAccount is from another program
-    /// PDA derivation validated in instruction
-    #[account(
-        mut,
-        constraint = *borrower_account.owner == lending_program.key() @ CrucibleError::InvalidLendingProgram
-    )]
+    /// CHECK: Borrower account for this position - validated by lending_pool_usdc::position_borrower_account
+    #[account(mut)]
     pub borrower_account: UncheckedAccount<'info>,
     
-    /// CHECK: SECURITY FIX - Validate lending_vault is owned by lending_program
+    /// CHECK: Owner's legacy borrower account - only for positions that borrowed before per-position
+    /// debt accounts, validated by lending_pool_usdc::position_borrower_account
+    #[account(mut)]
+    pub legacy_borrower_account: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: SECURITY FIX - Lending pool vault PDA of lending_market, never another pool token account
     #[account(
         mut,
-        constraint = *lending_vault.owner == lending_program.key() @ CrucibleError::InvalidLendingProgram
+        address = lending_pool_usdc::pool_vault_address(&lending_market.key()) @ CrucibleError::InvalidLendingProgram
     )]
     pub lending_vault: UncheckedAccount<'info>,
     
+    /// CHECK: Credit line the debt was drawn on - validated by the lending program
+    #[account(mut)]
+    pub authorized_borrower: Option<UncheckedAccount<'info>>,
+    
     /// CHECK: Liquidator's USDC account for repaying debt
     #[account(mut)]
     pub liquidator_usdc_account: UncheckedAccount<'info>,
     
     pub token_program: Program<'info, Token>,
+
+    /// CHECK: Optional Forge protocol PDA - state changes are refused while the protocol is inactive
+    pub forge_protocol: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Bad debt record PDA of borrower_account - created by the lending program, required
+    /// (with system_program) when the liquidation seizes the last collateral and debt remains
+    #[account(mut)]
+    pub bad_debt_record: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Option<Program<'info, System>>,
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partial_liquidation_restores_target_ltv() {
+        // 90% LTV: 9_000 debt on 10_000 collateral value, close factor out of the way
+        let config = LiquidationConfig { close_factor_bps: 10_000, target_ltv_bps: 7_500 };
+        let (repaid, seized) = liquidation_amounts(9_000, 10_000, &config).unwrap();
+        assert_eq!(seized, repaid * 10_500 / 10_000);
+        let ltv_after = (9_000 - repaid) * 10_000 / (10_000 - seized);
+        assert!((7_490..=7_500).contains(&ltv_after), "ltv after = {}", ltv_after);
+    }
+
+    #[test]
+    fn close_factor_caps_repayment() {
+        let config = LiquidationConfig { close_factor_bps: 1_000, target_ltv_bps: 5_000 };
+        let (repaid, _) = liquidation_amounts(9_000_000, 10_000_000, &config).unwrap();
+        assert_eq!(repaid, 900_000);
+    }
+
+    #[test]
+    fn underwater_position_seizes_all_collateral() {
+        let config = LiquidationConfig { close_factor_bps: 10_000, target_ltv_bps: 7_500 };
+        let (repaid, seized) = liquidation_amounts(2_000, 1_000, &config).unwrap();
+        assert_eq!(seized, 1_000);
+        assert_eq!(repaid, 1_000 * 10_000 / 10_500);
+    }
+
+    #[test]
+    fn liquidation_config_bounds() {
+        assert!(LiquidationConfig::DEFAULT.validate().is_ok());
+        let config = |close_factor_bps, target_ltv_bps| LiquidationConfig { close_factor_bps, target_ltv_bps };
+        assert!(config(10_000, 8_000).validate().is_ok());
+        assert!(config(999, 7_500).validate().is_err());
+        assert!(config(10_001, 7_500).validate().is_err());
+        assert!(config(5_000, 4_999).validate().is_err());
+        assert!(config(5_000, 8_001).validate().is_err());
+    }
+}
@@ -4,6 +4,7 @@ use anchor_spl::metadata::{create_metadata_accounts_v3, CreateMetadataAccountsV3
 use mpl_token_metadata::types::DataV2;
 
 use crate::state::{Crucible, CrucibleError};
+use forge_core::pause::{decode_pause_flags, PAUSE_NONE};
 
 /// Create Metaplex Token Metadata for a cToken mint
 /// This instruction allows the crucible PDA (mint authority) to sign metadata creation
@@ -18,7 +19,7 @@ pub fn create_ctoken_metadata(
     let crucible = &ctx.accounts.crucible;
     
     // SECURITY FIX: Check if crucible is paused before allowing metadata creation
-    require!(!crucible.paused, CrucibleError::ProtocolPaused);
+    require!(decode_pause_flags(crucible.pause_flags) == PAUSE_NONE, CrucibleError::ProtocolPaused);
     
     // Validate that ctoken_mint matches crucible's ctoken_mint
     require!(
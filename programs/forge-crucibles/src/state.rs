@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use forge_core::pause::is_paused;
 
 /// Legacy Crucible struct (pre-LP token support)
 /// Used for backward compatibility with old on-chain accounts
@@ -14,7 +15,7 @@ pub struct LegacyCrucible {
     pub exchange_rate: u64,
     pub last_update_slot: u64,
     pub fee_rate: u64,
-    pub paused: bool,
+    pub pause_flags: u8,
     pub total_leveraged_positions: u64,
     pub total_lp_positions: u64,
     pub expected_vault_balance: u64,
@@ -35,7 +36,7 @@ impl LegacyCrucible {
         8 +  // exchange_rate
         8 +  // last_update_slot
         8 +  // fee_rate
-        1 +  // paused
+        1 +  // pause_flags
         8 +  // total_leveraged_positions
         8 +  // total_lp_positions
         8 +  // expected_vault_balance
@@ -60,13 +61,212 @@ pub struct Crucible {
     pub exchange_rate: u64, // Scaled by 1_000_000 (1.0 = 1_000_000)
     pub last_update_slot: u64,
     pub fee_rate: u64, // Fee rate (e.g., 200 = 0.2% = 2 bps)
-    pub paused: bool,
+    pub pause_flags: u8, // forge_core::pause PAUSE_* flags, stored with encode_pause_flags
     pub total_leveraged_positions: u64, // Number of open LVF positions
     pub total_lp_positions: u64, // Number of open LP positions
     pub expected_vault_balance: u64, // Track expected vault balance to prevent manipulation
     pub oracle: Option<Pubkey>, // Optional oracle account for price feeds
     pub treasury: Pubkey, // Protocol treasury account for fee collection
     pub total_fees_accrued: u64, // Total fees accrued to vault (for analytics)
+    pub fee_schedule: FeeSchedule, // Per-crucible wrap/unwrap, LP and arbitrage fees
+    pub liquidation_config: LiquidationConfig, // Per-crucible LVF partial liquidation parameters
+}
+
+/// Crucible layout before the per-crucible fee schedule was added
+/// Used by migrate_crucible_fee_schedule to upgrade existing accounts
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PreFeeScheduleCrucible {
+    pub base_mint: Pubkey,
+    pub ctoken_mint: Pubkey,
+    pub lp_token_mint: Pubkey,
+    pub vault: Pubkey,
+    pub vault_bump: u8,
+    pub bump: u8,
+    pub total_base_deposited: u64,
+    pub total_ctoken_supply: u64,
+    pub total_lp_token_supply: u64,
+    pub exchange_rate: u64,
+    pub last_update_slot: u64,
+    pub fee_rate: u64,
+    pub pause_flags: u8,
+    pub total_leveraged_positions: u64,
+    pub total_lp_positions: u64,
+    pub expected_vault_balance: u64,
+    pub oracle: Option<Pubkey>,
+    pub treasury: Pubkey,
+    pub total_fees_accrued: u64,
+}
+
+impl PreFeeScheduleCrucible {
+    pub const LEN: usize = PreLiquidationConfigCrucible::LEN - FeeSchedule::LEN;
+
+    pub fn with_fee_schedule(self, fee_schedule: FeeSchedule) -> PreLiquidationConfigCrucible {
+        PreLiquidationConfigCrucible {
+            base_mint: self.base_mint,
+            ctoken_mint: self.ctoken_mint,
+            lp_token_mint: self.lp_token_mint,
+            vault: self.vault,
+            vault_bump: self.vault_bump,
+            bump: self.bump,
+            total_base_deposited: self.total_base_deposited,
+            total_ctoken_supply: self.total_ctoken_supply,
+            total_lp_token_supply: self.total_lp_token_supply,
+            exchange_rate: self.exchange_rate,
+            last_update_slot: self.last_update_slot,
+            fee_rate: self.fee_rate,
+            pause_flags: self.pause_flags,
+            total_leveraged_positions: self.total_leveraged_positions,
+            total_lp_positions: self.total_lp_positions,
+            expected_vault_balance: self.expected_vault_balance,
+            oracle: self.oracle,
+            treasury: self.treasury,
+            total_fees_accrued: self.total_fees_accrued,
+            fee_schedule,
+        }
+    }
+}
+
+/// Crucible layout before the per-crucible liquidation config was added
+/// Used by migrate_crucible_liquidation_config to upgrade existing accounts
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PreLiquidationConfigCrucible {
+    pub base_mint: Pubkey,
+    pub ctoken_mint: Pubkey,
+    pub lp_token_mint: Pubkey,
+    pub vault: Pubkey,
+    pub vault_bump: u8,
+    pub bump: u8,
+    pub total_base_deposited: u64,
+    pub total_ctoken_supply: u64,
+    pub total_lp_token_supply: u64,
+    pub exchange_rate: u64,
+    pub last_update_slot: u64,
+    pub fee_rate: u64,
+    pub pause_flags: u8,
+    pub total_leveraged_positions: u64,
+    pub total_lp_positions: u64,
+    pub expected_vault_balance: u64,
+    pub oracle: Option<Pubkey>,
+    pub treasury: Pubkey,
+    pub total_fees_accrued: u64,
+    pub fee_schedule: FeeSchedule,
+}
+
+impl PreLiquidationConfigCrucible {
+    pub const LEN: usize = Crucible::LEN - LiquidationConfig::LEN;
+
+    pub fn with_liquidation_config(self, liquidation_config: LiquidationConfig) -> Crucible {
+        Crucible {
+            base_mint: self.base_mint,
+            ctoken_mint: self.ctoken_mint,
+            lp_token_mint: self.lp_token_mint,
+            vault: self.vault,
+            vault_bump: self.vault_bump,
+            bump: self.bump,
+            total_base_deposited: self.total_base_deposited,
+            total_ctoken_supply: self.total_ctoken_supply,
+            total_lp_token_supply: self.total_lp_token_supply,
+            exchange_rate: self.exchange_rate,
+            last_update_slot: self.last_update_slot,
+            fee_rate: self.fee_rate,
+            pause_flags: self.pause_flags,
+            total_leveraged_positions: self.total_leveraged_positions,
+            total_lp_positions: self.total_lp_positions,
+            expected_vault_balance: self.expected_vault_balance,
+            oracle: self.oracle,
+            treasury: self.treasury,
+            total_fees_accrued: self.total_fees_accrued,
+            fee_schedule: self.fee_schedule,
+            liquidation_config,
+        }
+    }
+}
+
+/// Fees charged by a crucible, in basis points
+/// The protocol share of each fee still follows the forge-core protocol_fee_rate.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeeSchedule {
+    pub wrap_fee_bps: u64, // Charged on mint_ctoken deposits
+    pub unwrap_fee_bps: u64, // Charged on burn_ctoken withdrawals
+    pub open_fee_bps: u64, // Charged on LP position value at open
+    pub close_fee_principal_bps: u64, // Charged on principal when closing LP/leveraged positions
+    pub close_fee_yield_bps: u64, // Charged on yield when closing LP/leveraged positions
+    pub arbitrage_reward_bps: u64, // cToken reward paid to arbitrageurs depositing profit
+}
+
+// Fee schedule bounds
+const MAX_WRAP_FEE_BPS: u64 = 500; // 5%
+const MAX_OPEN_CLOSE_FEE_BPS: u64 = 1_000; // 10%
+const MAX_YIELD_FEE_BPS: u64 = 5_000; // 50%
+const MAX_ARBITRAGE_REWARD_BPS: u64 = 1_000; // 10%
+
+impl FeeSchedule {
+    pub const LEN: usize = 8 * 6;
+
+    /// Fees every crucible charged before schedules were configurable
+    pub const DEFAULT: FeeSchedule = FeeSchedule {
+        wrap_fee_bps: 50, // 0.5%
+        unwrap_fee_bps: 75, // 0.75%
+        open_fee_bps: 100, // 1%
+        close_fee_principal_bps: 200, // 2%
+        close_fee_yield_bps: 1_000, // 10%
+        arbitrage_reward_bps: 100, // 1%
+    };
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.wrap_fee_bps <= MAX_WRAP_FEE_BPS && self.unwrap_fee_bps <= MAX_WRAP_FEE_BPS,
+            CrucibleError::InvalidFeeSchedule
+        );
+        require!(
+            self.open_fee_bps <= MAX_OPEN_CLOSE_FEE_BPS
+                && self.close_fee_principal_bps <= MAX_OPEN_CLOSE_FEE_BPS,
+            CrucibleError::InvalidFeeSchedule
+        );
+        require!(
+            self.close_fee_yield_bps <= MAX_YIELD_FEE_BPS,
+            CrucibleError::InvalidFeeSchedule
+        );
+        require!(
+            self.arbitrage_reward_bps <= MAX_ARBITRAGE_REWARD_BPS,
+            CrucibleError::InvalidFeeSchedule
+        );
+        Ok(())
+    }
+}
+
+/// Partial liquidation parameters for LVF positions, in basis points
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LiquidationConfig {
+    pub close_factor_bps: u64, // Max share of debt repaid per liquidation
+    pub target_ltv_bps: u64, // LTV a partial liquidation restores the position to
+}
+
+// Liquidation config bounds
+const MIN_CLOSE_FACTOR_BPS: u64 = 1_000; // 10%
+const MIN_TARGET_LTV_BPS: u64 = 5_000; // 50%
+const MAX_TARGET_LTV_BPS: u64 = 8_000; // Must stay below the 85% liquidation threshold
+
+impl LiquidationConfig {
+    pub const LEN: usize = 8 * 2;
+
+    /// Parameters every crucible used before they were configurable
+    pub const DEFAULT: LiquidationConfig = LiquidationConfig {
+        close_factor_bps: 5_000, // 50%
+        target_ltv_bps: 7_500, // 75%
+    };
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            (MIN_CLOSE_FACTOR_BPS..=10_000).contains(&self.close_factor_bps),
+            CrucibleError::InvalidLiquidationConfig
+        );
+        require!(
+            (MIN_TARGET_LTV_BPS..=MAX_TARGET_LTV_BPS).contains(&self.target_ltv_bps),
+            CrucibleError::InvalidLiquidationConfig
+        );
+        Ok(())
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
@@ -129,14 +329,52 @@ impl Crucible {
         8 +  // exchange_rate
         8 +  // last_update_slot
         8 +  // fee_rate
-        1 +  // paused
+        1 +  // pause_flags
         8 +  // total_leveraged_positions
         8 +  // total_lp_positions
         8 +  // expected_vault_balance
         1 +  // oracle Option discriminator
         32 + // oracle Pubkey (if Some)
         32 + // treasury
-        8;   // total_fees_accrued
+        8 +  // total_fees_accrued
+        FeeSchedule::LEN + // fee_schedule
+        LiquidationConfig::LEN; // liquidation_config
+}
+
+/// Refuse `operation` (forge_core::pause PAUSE_* flags) while it is paused on the crucible
+pub fn require_not_paused(pause_flags: u8, operation: u8) -> Result<()> {
+    require!(!is_paused(pause_flags, operation), CrucibleError::ProtocolPaused);
+    Ok(())
+}
+
+/// Protocol share of fees in basis points, from forge-core `protocol_fee_rate`
+/// Fee-charging instructions require the protocol account, so the kill switch always applies.
+pub fn load_protocol_fee_bps(forge_protocol: &AccountInfo) -> Result<u64> {
+    let protocol = forge_core::load_protocol(forge_protocol)?;
+    require!(protocol.is_active, CrucibleError::ProtocolInactive);
+    require!(protocol.protocol_fee_rate <= 10_000, CrucibleError::InvalidConfig);
+    Ok(protocol.protocol_fee_rate)
+}
+
+/// Split a fee into (vault share, protocol share) - rounding dust goes to the protocol
+pub fn split_fee_value(fee: u128, protocol_fee_bps: u64) -> Result<(u128, u128)> {
+    let vault_share_bps = 10_000u64
+        .checked_sub(protocol_fee_bps)
+        .ok_or(CrucibleError::InvalidConfig)?;
+    let vault_share = fee
+        .checked_mul(vault_share_bps as u128)
+        .and_then(|v| v.checked_div(10_000u128))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let protocol_share = fee
+        .checked_sub(vault_share)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    Ok((vault_share, protocol_share))
+}
+
+/// `split_fee_value` for token amounts - both shares are bounded by `fee`
+pub fn split_fee(fee: u64, protocol_fee_bps: u64) -> Result<(u64, u64)> {
+    let (vault_share, protocol_share) = split_fee_value(fee as u128, protocol_fee_bps)?;
+    Ok((vault_share as u64, protocol_share as u64))
 }
 
 #[error_code]
@@ -187,5 +425,46 @@ pub enum CrucibleError {
     InvalidAmount,
     #[msg("Invalid configuration")]
     InvalidConfig,
+    #[msg("Forge protocol is inactive")]
+    ProtocolInactive,
+    #[msg("Fee schedule out of bounds")]
+    InvalidFeeSchedule,
+    #[msg("Crucible account must be migrated first")]
+    CrucibleNotMigrated,
+    #[msg("Liquidation config out of bounds")]
+    InvalidLiquidationConfig,
+    #[msg("Liquidation leaves bad debt - credit line, bad debt record and system program are required")]
+    BadDebtAccountsRequired,
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fee_split_sends_rounding_dust_to_the_protocol() {
+        assert_eq!(split_fee_value(1_000, 2_000).unwrap(), (800, 200));
+        assert_eq!(split_fee_value(999, 2_500).unwrap(), (749, 250));
+        assert_eq!(split_fee(7, 0).unwrap(), (7, 0));
+        assert_eq!(split_fee(7, 10_000).unwrap(), (0, 7));
+        assert!(split_fee_value(1_000, 10_001).is_err());
+    }
+    #[test]
+    fn fee_schedule_bounds() {
+        assert!(FeeSchedule::DEFAULT.validate().is_ok());
+        let at_limits = FeeSchedule {
+            wrap_fee_bps: MAX_WRAP_FEE_BPS,
+            unwrap_fee_bps: MAX_WRAP_FEE_BPS,
+            open_fee_bps: MAX_OPEN_CLOSE_FEE_BPS,
+            close_fee_principal_bps: MAX_OPEN_CLOSE_FEE_BPS,
+            close_fee_yield_bps: MAX_YIELD_FEE_BPS,
+            arbitrage_reward_bps: MAX_ARBITRAGE_REWARD_BPS,
+        };
+        assert!(at_limits.validate().is_ok());
+        assert!(FeeSchedule { unwrap_fee_bps: MAX_WRAP_FEE_BPS + 1, ..at_limits }.validate().is_err());
+        assert!(FeeSchedule { close_fee_principal_bps: MAX_OPEN_CLOSE_FEE_BPS + 1, ..at_limits }.validate().is_err());
+        assert!(FeeSchedule { close_fee_yield_bps: MAX_YIELD_FEE_BPS + 1, ..at_limits }.validate().is_err());
+        assert!(FeeSchedule { arbitrage_reward_bps: MAX_ARBITRAGE_REWARD_BPS + 1, ..at_limits }.validate().is_err());
+    }
+}
@@ -1,25 +1,199 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer};
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_lang::Discriminator;
+use anchor_spl::token::{self, Burn, Mint, MintTo, Token, TokenAccount, Transfer};
+use forge_core::pause::{
+    decode_pause_flags, encode_pause_flags, is_paused, PAUSE_ALL, PAUSE_DEPOSITS, PAUSE_LEVERAGE_CLOSE, PAUSE_LEVERAGE_OPEN, PAUSE_NONE,
+    PAUSE_WITHDRAWALS,
+};
 
 declare_id!("7hwTzKPSKdio6TZdi4SY7wEuGpFha15ebsaiTPp2y3G2");
 
+// SECURITY FIX (MEDIUM-001): Minimum liquidity kept in the pool while loans are outstanding
+const MIN_LIQUIDITY_RESERVE: u64 = 1_000_000; // 1 USDC minimum reserve (1 USDC = 1_000_000 lamports for 6 decimals)
+
+pub const RATE_SCALE: u128 = 1_000_000_000u128; // 1e9 fixed point for rates and the borrow index
+const SECONDS_PER_YEAR: u128 = 31_536_000; // Exact: 365 * 24 * 60 * 60
+
 // Backward compatibility: old pool layout (no authority/paused).
 const OLD_POOL_ACCOUNT_LEN: usize = 8 + 32 + 8 + 8 + 8 + 8 + 1; // discriminator + fields
+// V1 pool layout (authority/paused, flat borrow rate, no borrow index).
+const V1_POOL_ACCOUNT_LEN: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 1 + 1; // discriminator + fields
+// V2 pool layout (interest model + borrow index, no reserve factor).
+const V2_POOL_ACCOUNT_LEN: usize = 8 + LendingPoolV2::LEN;
+// V3 pool layout (reserve factor, no supply/borrow caps).
+const V3_POOL_ACCOUNT_LEN: usize = 8 + LendingPoolV3::LEN;
+// V4 pool layout (supply/borrow caps, no pending authority).
+const V4_POOL_ACCOUNT_LEN: usize = 8 + LendingPoolV4::LEN;
 const NEW_POOL_ACCOUNT_LEN: usize = 8 + LendingPool::LEN;
+// Borrower accounts created before credit lines (no borrow_authority).
+const LEGACY_BORROWER_ACCOUNT_LEN: usize = 8 + 32 + 8 + 8;
+
+// Maximum share of accrued interest routed to the insurance fund (50%)
+const MAX_RESERVE_FACTOR_BPS: u64 = 5_000;
+// Utilization ceiling that leaves borrows bounded only by available liquidity (100%)
+const NO_UTILIZATION_CEILING_BPS: u64 = 10_000;
 
 #[derive(Clone)]
 struct LendingPoolOld {
+    usdc_mint: Pubkey,
+    total_liquidity: u64,
+    total_borrowed: u64,
+    borrow_rate: u64,
+    bump: u8,
+}
+
+#[derive(Clone)]
+struct LendingPoolV1 {
+    authority: Pubkey,
+    usdc_mint: Pubkey,
+    total_liquidity: u64,
+    total_borrowed: u64,
+    borrow_rate: u64,
+    paused: bool,
+    bump: u8,
+}
+
+#[derive(Clone, AnchorDeserialize)]
+struct LendingPoolV2 {
+    authority: Pubkey,
+    usdc_mint: Pubkey,
+    total_liquidity: u64,
+    total_borrowed: u64,
+    borrow_rate: u64,
+    lender_rate: u64,
+    pause_flags: u8,
+    bump: u8,
+    interest_model: InterestRateModelConfig,
+    borrow_index: u128,
+    last_accrued_ts: u64,
+}
+
+impl LendingPoolV2 {
+    const LEN: usize = 32 + 32 + 8 + 8 + 8 + 8 + 1 + 1 + InterestRateModelConfig::SIZE + 16 + 8;
+
+    /// Current layout with no reserve factor and no pending reserves
+    fn into_current(self) -> LendingPool {
+        LendingPoolV3 {
+            authority: self.authority,
+            usdc_mint: self.usdc_mint,
+            total_liquidity: self.total_liquidity,
+            total_borrowed: self.total_borrowed,
+            borrow_rate: self.borrow_rate,
+            lender_rate: self.lender_rate,
+            pause_flags: self.pause_flags,
+            bump: self.bump,
+            interest_model: self.interest_model,
+            borrow_index: self.borrow_index,
+            last_accrued_ts: self.last_accrued_ts,
+            reserve_factor_bps: 0,
+            pending_reserves: 0,
+        }
+        .into_current()
+    }
+}
+
+#[derive(Clone, AnchorDeserialize)]
+struct LendingPoolV3 {
+    authority: Pubkey,
+    usdc_mint: Pubkey,
+    total_liquidity: u64,
+    total_borrowed: u64,
+    borrow_rate: u64,
+    lender_rate: u64,
+    pause_flags: u8,
+    bump: u8,
+    interest_model: InterestRateModelConfig,
+    borrow_index: u128,
+    last_accrued_ts: u64,
+    reserve_factor_bps: u64,
+    pending_reserves: u64,
+}
+
+impl LendingPoolV3 {
+    const LEN: usize = LendingPoolV2::LEN + 8 + 8;
+
+    /// Current layout with no caps and no utilization ceiling
+    fn into_current(self) -> LendingPool {
+        LendingPoolV4 {
+            authority: self.authority,
+            usdc_mint: self.usdc_mint,
+            total_liquidity: self.total_liquidity,
+            total_borrowed: self.total_borrowed,
+            borrow_rate: self.borrow_rate,
+            lender_rate: self.lender_rate,
+            pause_flags: self.pause_flags,
+            bump: self.bump,
+            interest_model: self.interest_model,
+            borrow_index: self.borrow_index,
+            last_accrued_ts: self.last_accrued_ts,
+            reserve_factor_bps: self.reserve_factor_bps,
+            pending_reserves: self.pending_reserves,
+            max_utilization_bps: NO_UTILIZATION_CEILING_BPS,
+            supply_cap: None,
+            borrow_cap: None,
+            borrower_cap: None,
+        }
+        .into_current()
+    }
+}
+
+#[derive(Clone, AnchorDeserialize)]
+struct LendingPoolV4 {
+    authority: Pubkey,
     usdc_mint: Pubkey,
     total_liquidity: u64,
     total_borrowed: u64,
     borrow_rate: u64,
     lender_rate: u64,
+    pause_flags: u8,
     bump: u8,
+    interest_model: InterestRateModelConfig,
+    borrow_index: u128,
+    last_accrued_ts: u64,
+    reserve_factor_bps: u64,
+    pending_reserves: u64,
+    max_utilization_bps: u64,
+    supply_cap: Option<u64>,
+    borrow_cap: Option<u64>,
+    borrower_cap: Option<u64>,
+}
+
+impl LendingPoolV4 {
+    const LEN: usize = LendingPool::LEN - 33;
+
+    /// Current layout with no pending authority transfer
+    fn into_current(self) -> LendingPool {
+        LendingPool {
+            authority: self.authority,
+            usdc_mint: self.usdc_mint,
+            total_liquidity: self.total_liquidity,
+            total_borrowed: self.total_borrowed,
+            borrow_rate: self.borrow_rate,
+            lender_rate: self.lender_rate,
+            pause_flags: self.pause_flags,
+            bump: self.bump,
+            interest_model: self.interest_model,
+            borrow_index: self.borrow_index,
+            last_accrued_ts: self.last_accrued_ts,
+            reserve_factor_bps: self.reserve_factor_bps,
+            pending_reserves: self.pending_reserves,
+            max_utilization_bps: self.max_utilization_bps,
+            supply_cap: self.supply_cap,
+            borrow_cap: self.borrow_cap,
+            borrower_cap: self.borrower_cap,
+            pending_authority: None,
+        }
+    }
 }
 
 #[derive(Clone)]
 enum LendingPoolVersion {
     Old(LendingPoolOld),
+    V1(LendingPoolV1),
+    V2(LendingPoolV2),
+    V3(LendingPoolV3),
+    V4(LendingPoolV4),
     New(LendingPool),
 }
 
@@ -27,122 +201,105 @@ impl LendingPoolVersion {
     fn bump(&self) -> u8 {
         match self {
             LendingPoolVersion::Old(pool) => pool.bump,
+            LendingPoolVersion::V1(pool) => pool.bump,
+            LendingPoolVersion::V2(pool) => pool.bump,
+            LendingPoolVersion::V3(pool) => pool.bump,
+            LendingPoolVersion::V4(pool) => pool.bump,
             LendingPoolVersion::New(pool) => pool.bump,
         }
     }
 
-    fn usdc_mint(&self) -> Pubkey {
+    fn mint(&self) -> Pubkey {
         match self {
             LendingPoolVersion::Old(pool) => pool.usdc_mint,
+            LendingPoolVersion::V1(pool) => pool.usdc_mint,
+            LendingPoolVersion::V2(pool) => pool.usdc_mint,
+            LendingPoolVersion::V3(pool) => pool.usdc_mint,
+            LendingPoolVersion::V4(pool) => pool.usdc_mint,
             LendingPoolVersion::New(pool) => pool.usdc_mint,
         }
     }
 
-    fn total_liquidity(&self) -> u64 {
-        match self {
-            LendingPoolVersion::Old(pool) => pool.total_liquidity,
-            LendingPoolVersion::New(pool) => pool.total_liquidity,
-        }
-    }
-
-    fn total_borrowed(&self) -> u64 {
-        match self {
-            LendingPoolVersion::Old(pool) => pool.total_borrowed,
-            LendingPoolVersion::New(pool) => pool.total_borrowed,
-        }
-    }
-
-    fn borrow_rate(&self) -> u64 {
+    /// Pools must be migrated with `migrate_pool` before they can be used.
+    fn into_current(self) -> Result<LendingPool> {
         match self {
-            LendingPoolVersion::Old(pool) => pool.borrow_rate,
-            LendingPoolVersion::New(pool) => pool.borrow_rate,
+            LendingPoolVersion::New(pool) => Ok(pool),
+            _ => Err(LendingPoolError::PoolMigrationRequired.into()),
         }
     }
+}
 
-    fn lender_rate(&self) -> u64 {
-        match self {
-            LendingPoolVersion::Old(pool) => pool.lender_rate,
-            LendingPoolVersion::New(pool) => pool.lender_rate,
-        }
-    }
+fn deserialize_pool(data: &[u8]) -> Result<LendingPoolVersion> {
+    require!(data.len() >= 8, LendingPoolError::InvalidConfig);
 
-    fn paused(&self) -> bool {
-        match self {
-            LendingPoolVersion::Old(_) => false,
-            LendingPoolVersion::New(pool) => pool.paused,
-        }
+    if data.len() == NEW_POOL_ACCOUNT_LEN {
+        // Skip 8-byte discriminator - account is a PDA of this program, validated by callers
+        let pool = LendingPool::deserialize(&mut &data[8..])
+            .map_err(|_| LendingPoolError::InvalidConfig)?;
+        return Ok(LendingPoolVersion::New(pool));
     }
 
-    fn authority(&self) -> Option<Pubkey> {
-        match self {
-            LendingPoolVersion::Old(_) => None,
-            LendingPoolVersion::New(pool) => Some(pool.authority),
-        }
+    if data.len() == V4_POOL_ACCOUNT_LEN {
+        // V4 is the current layout without the trailing pending authority
+        let pool = LendingPoolV4::deserialize(&mut &data[8..])
+            .map_err(|_| LendingPoolError::InvalidConfig)?;
+        return Ok(LendingPoolVersion::V4(pool));
     }
 
-    fn set_total_liquidity(&mut self, value: u64) {
-        match self {
-            LendingPoolVersion::Old(pool) => pool.total_liquidity = value,
-            LendingPoolVersion::New(pool) => pool.total_liquidity = value,
-        }
+    if data.len() == V3_POOL_ACCOUNT_LEN {
+        // V3 is the current layout without the trailing cap fields
+        let pool = LendingPoolV3::deserialize(&mut &data[8..])
+            .map_err(|_| LendingPoolError::InvalidConfig)?;
+        return Ok(LendingPoolVersion::V3(pool));
     }
 
-    fn set_total_borrowed(&mut self, value: u64) {
-        match self {
-            LendingPoolVersion::Old(pool) => pool.total_borrowed = value,
-            LendingPoolVersion::New(pool) => pool.total_borrowed = value,
-        }
+    if data.len() == V2_POOL_ACCOUNT_LEN {
+        // V2 is the current layout without the trailing reserve fields
+        let pool = LendingPoolV2::deserialize(&mut &data[8..])
+            .map_err(|_| LendingPoolError::InvalidConfig)?;
+        return Ok(LendingPoolVersion::V2(pool));
     }
-}
-
-fn deserialize_pool(data: &[u8]) -> Result<LendingPoolVersion> {
-    require!(data.len() >= 8, LendingPoolError::InvalidConfig);
 
-    if data.len() == NEW_POOL_ACCOUNT_LEN {
-        // Manually deserialize the new pool format
+    if data.len() == V1_POOL_ACCOUNT_LEN {
+        // Manually deserialize the V1 pool format
         // Skip 8-byte discriminator and read fields directly
         let mut offset = 8;
-        
+
         let authority = Pubkey::try_from(&data[offset..offset + 32])
             .map_err(|_| LendingPoolError::InvalidConfig)?;
         offset += 32;
-        
+
         let usdc_mint = Pubkey::try_from(&data[offset..offset + 32])
             .map_err(|_| LendingPoolError::InvalidConfig)?;
         offset += 32;
-        
+
         let total_liquidity = u64::from_le_bytes(
             data[offset..offset + 8].try_into().map_err(|_| LendingPoolError::InvalidConfig)?
         );
         offset += 8;
-        
+
         let total_borrowed = u64::from_le_bytes(
             data[offset..offset + 8].try_into().map_err(|_| LendingPoolError::InvalidConfig)?
         );
         offset += 8;
-        
+
+        // Flat borrow_rate is settled into borrower accounts on migration; lender_rate is superseded
         let borrow_rate = u64::from_le_bytes(
             data[offset..offset + 8].try_into().map_err(|_| LendingPoolError::InvalidConfig)?
         );
-        offset += 8;
-        
-        let lender_rate = u64::from_le_bytes(
-            data[offset..offset + 8].try_into().map_err(|_| LendingPoolError::InvalidConfig)?
-        );
-        offset += 8;
-        
+        offset += 8 + 8;
+
         let paused = data[offset] != 0;
         offset += 1;
-        
+
         let bump = data[offset];
-        
-        return Ok(LendingPoolVersion::New(LendingPool {
+
+        return Ok(LendingPoolVersion::V1(LendingPoolV1 {
             authority,
             usdc_mint,
             total_liquidity,
             total_borrowed,
             borrow_rate,
-            lender_rate,
             paused,
             bump,
         }));
@@ -164,15 +321,11 @@ fn deserialize_pool(data: &[u8]) -> Result<LendingPoolVersion> {
         );
         offset += 8;
 
+        // Flat borrow_rate is settled into borrower accounts on migration; lender_rate is superseded
         let borrow_rate = u64::from_le_bytes(
             data[offset..offset + 8].try_into().map_err(|_| LendingPoolError::InvalidConfig)?
         );
-        offset += 8;
-
-        let lender_rate = u64::from_le_bytes(
-            data[offset..offset + 8].try_into().map_err(|_| LendingPoolError::InvalidConfig)?
-        );
-        offset += 8;
+        offset += 8 + 8;
 
         let bump = data[offset];
 
@@ -181,7 +334,6 @@ fn deserialize_pool(data: &[u8]) -> Result<LendingPoolVersion> {
             total_liquidity,
             total_borrowed,
             borrow_rate,
-            lender_rate,
             bump,
         }));
     }
@@ -189,110 +341,712 @@ fn deserialize_pool(data: &[u8]) -> Result<LendingPoolVersion> {
     Err(LendingPoolError::InvalidConfig.into())
 }
 
-fn serialize_pool(data: &mut [u8], pool: &LendingPoolVersion) -> Result<()> {
-    match pool {
-        LendingPoolVersion::New(pool) => {
-            let mut slice = &mut data[8..];
-            pool.serialize(&mut slice)?;
-        }
-        LendingPoolVersion::Old(pool) => {
-            require!(data.len() == OLD_POOL_ACCOUNT_LEN, LendingPoolError::InvalidConfig);
-            let mut offset = 8;
-            data[offset..offset + 32].copy_from_slice(pool.usdc_mint.as_ref());
-            offset += 32;
+fn serialize_pool(data: &mut [u8], pool: &LendingPool) -> Result<()> {
+    require!(data.len() == NEW_POOL_ACCOUNT_LEN, LendingPoolError::PoolMigrationRequired);
+    let mut slice = &mut data[8..];
+    pool.serialize(&mut slice)?;
+    Ok(())
+}
+
+// SECURITY FIX (AUDIT-047): Verify interest rate model parameters are reasonable
+fn validate_interest_model(model: &InterestRateModelConfig) -> Result<()> {
+    // Base rate: 0 to 1,000,000 bps
+    require!(model.base_rate_bps <= 1_000_000, LendingPoolError::InvalidConfig);
+    // Slopes: 0 to 1,000,000 bps per utilization point
+    require!(model.slope1_bps <= 1_000_000, LendingPoolError::InvalidConfig);
+    require!(model.slope2_bps <= 1_000_000, LendingPoolError::InvalidConfig);
+    // Kink: 0 to 10,000 bps (0% to 100% utilization)
+    require!(model.kink_bps <= 10_000, LendingPoolError::InvalidConfig);
+    Ok(())
+}
+
+/// Utilization of the pool (borrowed / liquidity), scaled by RATE_SCALE
+fn utilization_scaled(pool: &LendingPool) -> Result<u128> {
+    let liquidity = pool.total_liquidity.max(1);
+    let util = (pool.total_borrowed as u128)
+        .checked_mul(RATE_SCALE)
+        .and_then(|v| v.checked_div(liquidity as u128))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    Ok(util.min(RATE_SCALE))
+}
+
+/// Annualized borrow rate from the kinked model, scaled by RATE_SCALE
+fn borrow_rate_scaled(pool: &LendingPool) -> Result<u128> {
+    let model = &pool.interest_model;
+    let util_scaled = utilization_scaled(pool)?;
+    let to_scaled = |bps: u64| -> Result<u128> {
+        Ok((bps as u128)
+            .checked_mul(RATE_SCALE)
+            .and_then(|v| v.checked_div(10_000u128))
+            .ok_or(ProgramError::ArithmeticOverflow)?)
+    };
+    let kink_scaled = to_scaled(model.kink_bps)?;
+    let base_scaled = to_scaled(model.base_rate_bps)?;
+    let slope1_scaled = to_scaled(model.slope1_bps)?;
+    let slope2_scaled = to_scaled(model.slope2_bps)?;
+
+    // Below kink: base + util * slope1
+    // Above kink: base + kink * slope1 + (util - kink) * slope2
+    let below_kink = util_scaled.min(kink_scaled);
+    let above_kink = util_scaled.saturating_sub(kink_scaled);
+    let rate = base_scaled
+        .checked_add(
+            below_kink
+                .checked_mul(slope1_scaled)
+                .and_then(|v| v.checked_div(RATE_SCALE))
+                .ok_or(ProgramError::ArithmeticOverflow)?,
+        )
+        .and_then(|v| {
+            above_kink
+                .checked_mul(slope2_scaled)
+                .and_then(|p| p.checked_div(RATE_SCALE))
+                .and_then(|p| v.checked_add(p))
+        })
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    Ok(rate)
+}
+
+/// Refresh the display rates (scaled by 100) from the model at current utilization
+fn refresh_rates(pool: &mut LendingPool) -> Result<()> {
+    let rate_scaled = borrow_rate_scaled(pool)?;
+    let util_scaled = utilization_scaled(pool)?;
+    let borrow_rate = rate_scaled
+        .checked_mul(100)
+        .and_then(|v| v.checked_div(RATE_SCALE))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    // Lenders earn the borrow rate on the utilized share of liquidity, less the reserve factor
+    let lender_rate = rate_scaled
+        .checked_mul(util_scaled)
+        .and_then(|v| v.checked_mul(100))
+        .and_then(|v| v.checked_div(RATE_SCALE))
+        .and_then(|v| v.checked_mul(10_000u128 - pool.reserve_factor_bps as u128))
+        .and_then(|v| v.checked_div(10_000u128))
+        .and_then(|v| v.checked_div(RATE_SCALE))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    pool.borrow_rate = u64::try_from(borrow_rate).map_err(|_| ProgramError::ArithmeticOverflow)?;
+    pool.lender_rate = u64::try_from(lender_rate).map_err(|_| ProgramError::ArithmeticOverflow)?;
+    Ok(())
+}
+
+/// Advance the global borrow index to `now`.
+/// Accrued interest is added to total_borrowed (owed by borrowers). The reserve factor's
+/// share goes to pending_reserves (owed to the insurance fund) and the rest to
+/// total_liquidity (owed to lenders), so lender shares appreciate as it accrues.
+fn accrue_pool_interest(pool: &mut LendingPool, now: u64) -> Result<()> {
+    if now <= pool.last_accrued_ts {
+        return Ok(());
+    }
+    let seconds = (now - pool.last_accrued_ts) as u128;
+    let rate_scaled = borrow_rate_scaled(pool)?;
+
+    // index *= (1 + rate * seconds / year)
+    // Multiply all numerators first, then divide by denominator to maximize precision
+    let increment = pool.borrow_index
+        .checked_mul(rate_scaled)
+        .and_then(|v| v.checked_mul(seconds))
+        .and_then(|v| v.checked_div(RATE_SCALE))
+        .and_then(|v| v.checked_div(SECONDS_PER_YEAR))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    // interest = total_borrowed * increment / old_index
+    let interest = (pool.total_borrowed as u128)
+        .checked_mul(increment)
+        .and_then(|v| v.checked_div(pool.borrow_index.max(1)))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let interest = u64::try_from(interest).map_err(|_| ProgramError::ArithmeticOverflow)?;
+
+    pool.borrow_index = pool.borrow_index
+        .checked_add(increment)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let reserves = (interest as u128)
+        .checked_mul(pool.reserve_factor_bps as u128)
+        .and_then(|v| v.checked_div(10_000u128))
+        .ok_or(ProgramError::ArithmeticOverflow)? as u64;
+
+    pool.total_borrowed = pool.total_borrowed
+        .checked_add(interest)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    pool.total_liquidity = pool.total_liquidity
+        .checked_add(interest - reserves)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    pool.pending_reserves = pool.pending_reserves
+        .checked_add(reserves)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    pool.last_accrued_ts = now;
+
+    refresh_rates(pool)
+}
+
+/// Current debt for a borrower's index-scaled balance, rounded up in favour of the pool
+fn debt_from_scaled(scaled_debt: u64, borrow_index: u128) -> Result<u64> {
+    let debt = (scaled_debt as u128)
+        .checked_mul(borrow_index)
+        .and_then(|v| v.checked_add(RATE_SCALE - 1))
+        .and_then(|v| v.checked_div(RATE_SCALE))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if debt > u64::MAX as u128 {
+        return Err(ProgramError::ArithmeticOverflow.into());
+    }
+    Ok(debt as u64)
+}
+
+/// Index-scaled balance for `amount` of debt at the current index.
+/// Rounded up on borrow and down on repay, both in favour of the pool.
+fn scaled_from_amount(amount: u64, borrow_index: u128, round_up: bool) -> Result<u64> {
+    let numerator = (amount as u128)
+        .checked_mul(RATE_SCALE)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let index = borrow_index.max(1);
+    let scaled = if round_up {
+        numerator.checked_add(index - 1).map(|v| v / index)
+    } else {
+        Some(numerator / index)
+    }
+    .ok_or(ProgramError::ArithmeticOverflow)?;
+    if scaled > u64::MAX as u128 {
+        return Err(ProgramError::ArithmeticOverflow.into());
+    }
+    Ok(scaled as u64)
+}
+
+/// Accrue interest and apply a repayment to a borrower's scaled debt and the pool totals.
+/// Shared by repay_usdc and repay_usdc_on_behalf; the caller performs the token transfer.
+/// Returns the index-scaled debt repaid.
+fn apply_repayment(
+    pool: &mut LendingPool,
+    borrower_account: &mut BorrowerAccount,
+    amount: u64,
+    now: u64,
+) -> Result<u64> {
+    accrue_pool_interest(pool, now)?;
+
+    // Total owed = scaled debt × current borrow index
+    let total_owed = debt_from_scaled(borrower_account.amount_borrowed, pool.borrow_index)?;
+
+    require!(
+        amount <= total_owed,
+        LendingPoolError::RepayAmountExceedsDebt
+    );
+
+    // SECURITY FIX (CRITICAL-005): Reduce scaled debt by the repaid amount at the current index
+    let scaled_repaid = if amount == total_owed {
+        // Full repayment - clear all debt
+        borrower_account.amount_borrowed
+    } else {
+        scaled_from_amount(amount, pool.borrow_index, false)?
+    };
+
+    // SECURITY FIX: Validate amounts are sufficient before subtraction to detect accounting errors
+    require!(
+        borrower_account.amount_borrowed >= scaled_repaid,
+        LendingPoolError::InvalidAmount
+    );
+    borrower_account.amount_borrowed = borrower_account.amount_borrowed
+        .checked_sub(scaled_repaid)
+        .ok_or(LendingPoolError::InvalidAmount)?;
+    borrower_account.borrow_timestamp = now;
+
+    // Interest was already credited to lenders on accrual, so the whole repayment
+    // reduces total_borrowed. Per-borrower debt rounds up, so clamp rounding dust.
+    pool.total_borrowed = pool.total_borrowed
+        .checked_sub(amount.min(pool.total_borrowed))
+        .ok_or(LendingPoolError::InvalidAmount)?;
+    refresh_rates(pool)?;
+    Ok(scaled_repaid)
+}
+
+/// Refuse a borrow of `amount` beyond available liquidity, the borrow cap or the utilization ceiling
+/// Pool-wide limits are checked against debt including accrued interest, so accrue first.
+fn check_pool_borrow_limits(pool: &LendingPool, amount: u64) -> Result<()> {
+    // SECURITY FIX (MEDIUM-001): Enforce minimum liquidity reserve to prevent complete pool drainage
+    let available = pool.total_liquidity
+        .checked_sub(pool.total_borrowed)
+        .ok_or(LendingPoolError::InsufficientLiquidity)?;
+    // SECURITY FIX (MEDIUM-003): Use explicit error handling instead of unwrap_or(0)
+    let borrowable = available
+        .checked_sub(MIN_LIQUIDITY_RESERVE)
+        .ok_or(LendingPoolError::InsufficientLiquidity)?;
+    require!(
+        amount > 0 && amount <= borrowable,
+        LendingPoolError::InsufficientLiquidity
+    );
+
+    let new_total_borrowed = pool.total_borrowed
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if let Some(borrow_cap) = pool.borrow_cap {
+        require!(new_total_borrowed <= borrow_cap, LendingPoolError::BorrowCapExceeded);
+    }
+    // Keep (100% - ceiling) of liquidity withdrawable by lenders
+    let max_borrowed = (pool.total_liquidity as u128)
+        .checked_mul(pool.max_utilization_bps as u128)
+        .and_then(|v| v.checked_div(10_000))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    require!(
+        new_total_borrowed as u128 <= max_borrowed,
+        LendingPoolError::UtilizationCeilingExceeded
+    );
+    Ok(())
+}
+
+/// Charge `scaled_amount` of new debt to an active credit line, within its credit limit
+/// and the pool's per-borrower cap. Both apply to the line's debt across all positions.
+fn draw_credit(
+    authorized_borrower: &mut AuthorizedBorrower,
+    scaled_amount: u64,
+    borrow_index: u128,
+    borrower_cap: Option<u64>,
+) -> Result<()> {
+    require!(authorized_borrower.active, LendingPoolError::BorrowerNotAuthorized);
+    let credit_used = authorized_borrower.borrowed
+        .checked_add(scaled_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let debt = debt_from_scaled(credit_used, borrow_index)?;
+    if let Some(credit_limit) = authorized_borrower.credit_limit {
+        require!(debt <= credit_limit, LendingPoolError::CreditLimitExceeded);
+    }
+    if let Some(borrower_cap) = borrower_cap {
+        require!(debt <= borrower_cap, LendingPoolError::BorrowerCapExceeded);
+    }
+    authorized_borrower.borrowed = credit_used;
+    Ok(())
+}
+
+/// Who absorbs `bad_debt`, as (from_reserves, from_insurance_fund, socialized)
+/// 1. Reserves not yet collected are already in the pool vault
+/// 2. The insurance fund covers what reserves did not
+/// 3. The remainder is socialized across lenders
+fn bad_debt_waterfall(bad_debt: u64, pending_reserves: u64, insurance_fund_balance: u64) -> (u64, u64, u64) {
+    let from_reserves = bad_debt.min(pending_reserves);
+    let from_insurance_fund = (bad_debt - from_reserves).min(insurance_fund_balance);
+    (from_reserves, from_insurance_fund, bad_debt - from_reserves - from_insurance_fund)
+}
+
+/// Release repaid debt from the credit line the borrower account drew on.
+/// Debt from before credit lines (default borrow_authority) is not charged to any line.
+fn release_credit(
+    borrower_account: &BorrowerAccount,
+    authorized_borrower: Option<&mut Account<AuthorizedBorrower>>,
+    scaled_repaid: u64,
+) -> Result<()> {
+    if borrower_account.borrow_authority == Pubkey::default() {
+        return Ok(());
+    }
+    // SECURITY FIX: The credit line must be released on every repayment, otherwise
+    // repaying outside the crucible would permanently consume its credit limit
+    let authorized_borrower = authorized_borrower.ok_or(LendingPoolError::BorrowerNotAuthorized)?;
+    require_keys_eq!(
+        authorized_borrower.borrow_authority,
+        borrower_account.borrow_authority,
+        LendingPoolError::BorrowerNotAuthorized
+    );
+    // Rounding on the borrower side can leave dust, so saturate rather than fail
+    authorized_borrower.borrowed = authorized_borrower.borrowed.saturating_sub(scaled_repaid);
+    Ok(())
+}
 
-            data[offset..offset + 8].copy_from_slice(&pool.total_liquidity.to_le_bytes());
-            offset += 8;
+/// Grow a pool being migrated to the current layout, topping up rent from the authority,
+/// and write `new_pool` into it
+fn resize_and_write_pool(ctx: &Context<MigratePool>, new_pool: &LendingPool) -> Result<()> {
+    let pool_info = ctx.accounts.pool.to_account_info();
+    let required_lamports = Rent::get()?.minimum_balance(NEW_POOL_ACCOUNT_LEN);
+    let top_up = required_lamports.saturating_sub(pool_info.lamports());
+    if top_up > 0 {
+        let cpi_accounts = anchor_lang::system_program::Transfer {
+            from: ctx.accounts.authority.to_account_info(),
+            to: pool_info.clone(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        anchor_lang::system_program::transfer(cpi_ctx, top_up)?;
+    }
+    pool_info.realloc(NEW_POOL_ACCOUNT_LEN, false)?;
 
-            data[offset..offset + 8].copy_from_slice(&pool.total_borrowed.to_le_bytes());
-            offset += 8;
+    let mut pool_data = ctx.accounts.pool.try_borrow_mut_data()?;
+    serialize_pool(&mut pool_data, new_pool)?;
 
-            data[offset..offset + 8].copy_from_slice(&pool.borrow_rate.to_le_bytes());
-            offset += 8;
+    msg!("Pool migrated: {}", ctx.accounts.pool.key());
+    Ok(())
+}
 
-            data[offset..offset + 8].copy_from_slice(&pool.lender_rate.to_le_bytes());
-            offset += 8;
+/// Interest owed under the flat model used by old and V1 pools
+/// interest = principal × borrow_rate × seconds / (100 × year), with borrow_rate 10 = 10% APY.
+fn flat_interest(principal: u64, borrow_rate: u64, since: u64, now: u64) -> Result<u64> {
+    let seconds = now.saturating_sub(since) as u128;
+    let interest = (principal as u128)
+        .checked_mul(borrow_rate as u128)
+        .and_then(|v| v.checked_mul(seconds))
+        .and_then(|v| v.checked_div(100u128))
+        .and_then(|v| v.checked_div(SECONDS_PER_YEAR))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    u64::try_from(interest).map_err(|_| ProgramError::ArithmeticOverflow.into())
+}
 
-            data[offset] = pool.bump;
+/// Settle flat-model interest up to `settled_at` into borrower accounts of an old or V1 pool.
+/// Balances carry over at index 1.0, so interest since borrow_timestamp is added to the
+/// balance before the index takes over. Accounts already settled (borrow_timestamp at
+/// `settled_at`) are skipped, so a pool can be settled over several transactions.
+/// Returns the (principal, interest) settled.
+fn settle_legacy_borrowers(
+    borrower_infos: &[AccountInfo],
+    pool: &Pubkey,
+    borrow_rate: u64,
+    settled_at: u64,
+) -> Result<(u64, u64)> {
+    let mut settled: Vec<Pubkey> = Vec::with_capacity(borrower_infos.len());
+    let mut principal_settled: u64 = 0;
+    let mut interest_settled: u64 = 0;
+
+    for borrower_info in borrower_infos {
+        require_keys_eq!(*borrower_info.owner, crate::ID, LendingPoolError::InvalidBorrower);
+        require!(borrower_info.is_writable, LendingPoolError::InvalidBorrower);
+        require!(!settled.contains(borrower_info.key), LendingPoolError::InvalidBorrower);
+        settled.push(borrower_info.key());
+
+        let mut borrower_account = {
+            let data = borrower_info.try_borrow_data()?;
+            if data.len() == LEGACY_BORROWER_ACCOUNT_LEN {
+                deserialize_legacy_borrower(&data)?
+            } else {
+                BorrowerAccount::try_deserialize(&mut &data[..])?
+            }
+        };
+        // Old and V1 pools only ever lent on the per-borrower account ([b"borrower", borrower])
+        require_keys_eq!(
+            borrower_info.key(),
+            borrower_account_address(pool, &borrower_account.borrower, &Pubkey::default()),
+            LendingPoolError::InvalidBorrower
+        );
+        // Borrows and repayments are refused until migration, so only settlement moves the timestamp here
+        if borrower_account.borrow_timestamp >= settled_at {
+            continue;
         }
+
+        let interest = flat_interest(
+            borrower_account.amount_borrowed,
+            borrow_rate,
+            borrower_account.borrow_timestamp,
+            settled_at,
+        )?;
+        principal_settled = principal_settled
+            .checked_add(borrower_account.amount_borrowed)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        interest_settled = interest_settled
+            .checked_add(interest)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        borrower_account.amount_borrowed = borrower_account.amount_borrowed
+            .checked_add(interest)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        borrower_account.borrow_timestamp = settled_at;
+        write_borrower_account(borrower_info, &borrower_account)?;
+    }
+    Ok((principal_settled, interest_settled))
+}
+
+/// Write a borrower account back in the layout it was read from
+fn write_borrower_account(borrower_info: &AccountInfo, borrower_account: &BorrowerAccount) -> Result<()> {
+    let mut data = borrower_info.try_borrow_mut_data()?;
+    if data.len() == LEGACY_BORROWER_ACCOUNT_LEN {
+        let mut slice = &mut data[8..];
+        borrower_account.borrower.serialize(&mut slice)?;
+        borrower_account.amount_borrowed.serialize(&mut slice)?;
+        borrower_account.borrow_timestamp.serialize(&mut slice)?;
+        return Ok(());
     }
+    let mut writer: &mut [u8] = &mut data[..];
+    borrower_account.try_serialize(&mut writer)
+}
+
+/// Address of the lending pool for `mint` ([b"pool", mint])
+pub fn pool_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"pool", mint.as_ref()], &crate::ID).0
+}
+
+/// Address of a pool's liquidity vault ([b"vault", pool])
+pub fn pool_vault_address(pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"vault", pool.as_ref()], &crate::ID).0
+}
+
+/// Address of the original USDC pool ([b"pool"]), created before pools were keyed by mint.
+/// It keeps its address (and the old-layout migration path) rather than moving its funds.
+pub fn legacy_pool_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"pool"], &crate::ID).0
+}
+
+/// Validate `pool_key` is the canonical PDA for `pool`: [b"pool", mint], or [b"pool"] for the legacy USDC pool
+fn require_pool_pda(pool_key: &Pubkey, pool: &LendingPool) -> Result<()> {
+    let (expected_pool_pda, expected_bump) = if *pool_key == legacy_pool_address() {
+        Pubkey::find_program_address(&[b"pool"], &crate::ID)
+    } else {
+        Pubkey::find_program_address(&[b"pool", pool.usdc_mint.as_ref()], &crate::ID)
+    };
+    require_keys_eq!(*pool_key, expected_pool_pda, LendingPoolError::InvalidConfig);
+    require!(pool.bump == expected_bump, LendingPoolError::InvalidConfig);
     Ok(())
 }
 
+/// Signer seeds for the pool PDA; `bump` is `[pool.bump]`
+fn pool_signer_seeds<'a>(pool_key: &Pubkey, pool: &'a LendingPool, bump: &'a [u8]) -> Vec<&'a [u8]> {
+    if *pool_key == legacy_pool_address() {
+        vec![&b"pool"[..], bump]
+    } else {
+        vec![&b"pool"[..], pool.usdc_mint.as_ref(), bump]
+    }
+}
+
+/// Load a canonical pool from an account owned by this program.
+/// Lets integrators (crucible LVF) read pool state without a CPI.
+pub fn load_pool(pool_info: &AccountInfo) -> Result<LendingPool> {
+    require_keys_eq!(*pool_info.owner, crate::ID, LendingPoolError::InvalidConfig);
+    let data = pool_info.try_borrow_data()?;
+    let pool = deserialize_pool(&data)?.into_current()?;
+    require_pool_pda(&pool_info.key(), &pool)?;
+    Ok(pool)
+}
+
+/// Borrower account holding `borrower`'s debt to `pool` for `position`
+/// ([b"borrower", pool, borrower, position]). On the legacy USDC pool `Pubkey::default()`
+/// addresses the legacy per-borrower account ([b"borrower", borrower]), which holds debt
+/// borrowed before debt was tracked per position.
+pub fn borrower_account_address(pool: &Pubkey, borrower: &Pubkey, position: &Pubkey) -> Pubkey {
+    if *position == Pubkey::default() && *pool == legacy_pool_address() {
+        Pubkey::find_program_address(&[b"borrower", borrower.as_ref()], &crate::ID).0
+    } else {
+        Pubkey::find_program_address(
+            &[b"borrower", pool.as_ref(), borrower.as_ref(), position.as_ref()],
+            &crate::ID,
+        ).0
+    }
+}
+
+/// Resolve which borrower account holds a position's debt to `pool`, and the position
+/// reference to repay it with. Positions opened before per-position debt borrowed on the
+/// owner's legacy account (legacy USDC pool only), which is only accepted while the
+/// position's own borrower account does not exist.
+pub fn position_borrower_account<'a, 'info>(
+    borrower_account: &'a AccountInfo<'info>,
+    legacy_borrower_account: Option<&'a AccountInfo<'info>>,
+    pool: &Pubkey,
+    borrower: &Pubkey,
+    position: &Pubkey,
+) -> Result<(&'a AccountInfo<'info>, Pubkey)> {
+    require_keys_eq!(
+        borrower_account.key(),
+        borrower_account_address(pool, borrower, position),
+        LendingPoolError::InvalidBorrower
+    );
+    if *borrower_account.owner == crate::ID {
+        return Ok((borrower_account, *position));
+    }
+    require_keys_eq!(*pool, legacy_pool_address(), LendingPoolError::InvalidBorrower);
+    let legacy_borrower_account = legacy_borrower_account.ok_or(LendingPoolError::InvalidBorrower)?;
+    require_keys_eq!(
+        legacy_borrower_account.key(),
+        borrower_account_address(pool, borrower, &Pubkey::default()),
+        LendingPoolError::InvalidBorrower
+    );
+    Ok((legacy_borrower_account, Pubkey::default()))
+}
+
+/// Load `borrower`'s borrower account on `pool` for `position`, validating owner and PDA derivation
+/// Accounts not yet migrated to the credit-line layout read with a default borrow_authority.
+pub fn load_borrower_account(
+    borrower_info: &AccountInfo,
+    pool: &Pubkey,
+    borrower: &Pubkey,
+    position: &Pubkey,
+) -> Result<BorrowerAccount> {
+    require_keys_eq!(*borrower_info.owner, crate::ID, LendingPoolError::InvalidBorrower);
+    let expected = borrower_account_address(pool, borrower, position);
+    require_keys_eq!(expected, borrower_info.key(), LendingPoolError::InvalidBorrower);
+    let data = borrower_info.try_borrow_data()?;
+    if data.len() == LEGACY_BORROWER_ACCOUNT_LEN {
+        return deserialize_legacy_borrower(&data);
+    }
+    BorrowerAccount::try_deserialize(&mut &data[..])
+}
+
+fn deserialize_legacy_borrower(data: &[u8]) -> Result<BorrowerAccount> {
+    require!(
+        data.len() == LEGACY_BORROWER_ACCOUNT_LEN && data[..8] == BorrowerAccount::DISCRIMINATOR,
+        LendingPoolError::InvalidBorrower
+    );
+    let mut slice = &data[8..];
+    Ok(BorrowerAccount {
+        borrower: Pubkey::deserialize(&mut slice)?,
+        amount_borrowed: u64::deserialize(&mut slice)?,
+        borrow_timestamp: u64::deserialize(&mut slice)?,
+        borrow_authority: Pubkey::default(),
+    })
+}
+
+/// Debt a borrower would owe at `now` - projects the borrow index forward exactly as
+/// the next accrual will, so it matches what repay_usdc will demand
+pub fn borrower_debt(pool: &LendingPool, borrower_account: &BorrowerAccount, now: u64) -> Result<u64> {
+    let mut pool = pool.clone();
+    accrue_pool_interest(&mut pool, now)?;
+    debt_from_scaled(borrower_account.amount_borrowed, pool.borrow_index)
+}
+
+/// Shares minted for a deposit of `amount` USDC, priced against pool liquidity.
+/// Only a pool with neither shares nor liquidity prices 1:1 - pools created by initialize
+/// always hold dead shares backed by their initial liquidity.
+fn shares_for_deposit(amount: u64, total_liquidity: u64, share_supply: u64) -> Result<u64> {
+    if share_supply == 0 && total_liquidity == 0 {
+        return Ok(amount);
+    }
+    // Shares without liquidity (or liquidity without shares) can't be priced
+    require!(share_supply > 0 && total_liquidity > 0, LendingPoolError::InvalidConfig);
+    let shares = (amount as u128)
+        .checked_mul(share_supply as u128)
+        .and_then(|v| v.checked_div(total_liquidity as u128))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if shares > u64::MAX as u128 {
+        return Err(ProgramError::ArithmeticOverflow.into());
+    }
+    Ok(shares as u64)
+}
+
+/// USDC redeemable for `shares`, rounded down in favour of the pool.
+fn usdc_for_shares(shares: u64, total_liquidity: u64, share_supply: u64) -> Result<u64> {
+    require!(share_supply > 0, LendingPoolError::InsufficientShares);
+    let amount = (shares as u128)
+        .checked_mul(total_liquidity as u128)
+        .and_then(|v| v.checked_div(share_supply as u128))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if amount > u64::MAX as u128 {
+        return Err(ProgramError::ArithmeticOverflow.into());
+    }
+    Ok(amount as u64)
+}
+
 #[program]
 pub mod lending_pool_usdc {
     use super::*;
 
-    /// Initialize the lending pool with USDC
-    pub fn initialize(ctx: Context<Initialize>, initial_liquidity: u64) -> Result<()> {
+    /// Initialize the lending pool for `usdc_mint` (USDC, USDT, PYUSD, ...) at [b"pool", mint]
+    /// Only the program upgrade authority can create pools, so a mint's pool cannot be squatted.
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        initial_liquidity: u64,
+        interest_model: InterestRateModelConfig,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.program_data.upgrade_authority_address == Some(ctx.accounts.authority.key()),
+            LendingPoolError::Unauthorized
+        );
+
+        // The legacy USDC pool keeps its [b"pool"] address, so refuse a second pool for its mint
+        let legacy_pool = ctx.accounts.legacy_pool.to_account_info();
+        if *legacy_pool.owner == crate::ID {
+            let legacy_mint = deserialize_pool(&legacy_pool.try_borrow_data()?)?.mint();
+            require_keys_neq!(legacy_mint, ctx.accounts.usdc_mint.key(), LendingPoolError::InvalidConfig);
+        }
+
         // SECURITY FIX (AUDIT-064): Validate initial_liquidity is reasonable
-        // Allow zero initial liquidity (pool can start empty)
+        // At least the minimum reserve seeds the pool, so its dead shares pin the share price
         // Maximum: 1 billion USDC (1_000_000_000 * 10^6 lamports for 6 decimals)
         const MAX_INITIAL_LIQUIDITY: u64 = 1_000_000_000_000_000; // 1 billion USDC with 6 decimals
         require!(
-            initial_liquidity <= MAX_INITIAL_LIQUIDITY,
+            initial_liquidity >= MIN_LIQUIDITY_RESERVE && initial_liquidity <= MAX_INITIAL_LIQUIDITY,
             LendingPoolError::InvalidAmount
         );
-        
-        // SECURITY FIX (AUDIT-065): Verify interest rates are reasonable
-        // Rates are hardcoded below, but we validate they're within bounds
-        const MAX_BORROW_RATE: u64 = 1000; // 1000% APY max (scaled by 100)
-        const MAX_LENDER_RATE: u64 = 500; // 500% APY max (scaled by 100)
-        const BORROW_RATE: u64 = 10; // 10% APY (scaled by 100)
-        const LENDER_RATE: u64 = 5; // 5% APY (scaled by 100)
-        
-        require!(
-            BORROW_RATE <= MAX_BORROW_RATE && LENDER_RATE <= MAX_LENDER_RATE,
-            LendingPoolError::InvalidConfig
-        );
-        require!(
-            LENDER_RATE <= BORROW_RATE, // Lender rate should not exceed borrow rate
-            LendingPoolError::InvalidConfig
-        );
-        
+
+        // SECURITY FIX (AUDIT-065): Verify interest rate model is within bounds
+        validate_interest_model(&interest_model)?;
+
         let pool = &mut ctx.accounts.pool;
         pool.authority = ctx.accounts.authority.key();
         pool.usdc_mint = ctx.accounts.usdc_mint.key();
         pool.total_liquidity = initial_liquidity;
         pool.total_borrowed = 0;
-        pool.borrow_rate = BORROW_RATE;
-        pool.lender_rate = LENDER_RATE;
-        pool.paused = false;
+        pool.pause_flags = PAUSE_NONE;
         pool.bump = ctx.bumps.pool;
-        
-        // The pool vault is already initialized as a token account via Anchor's init constraint
-        // in the Initialize struct (using token::authority = pool)
-        
+        pool.interest_model = interest_model;
+        pool.borrow_index = RATE_SCALE; // start at 1.0
+        pool.last_accrued_ts = Clock::get()?.unix_timestamp as u64;
+        pool.reserve_factor_bps = 0;
+        pool.pending_reserves = 0;
+        pool.max_utilization_bps = NO_UTILIZATION_CEILING_BPS;
+        pool.supply_cap = None;
+        pool.borrow_cap = None;
+        pool.borrower_cap = None;
+        pool.pending_authority = None;
+        refresh_rates(pool)?;
+
+        // The pool vault and insurance fund are already initialized as token accounts via
+        // Anchor's init constraint in the Initialize struct (using token::authority = pool)
+
+        // SECURITY FIX: Initial liquidity is real - the authority funds the vault with it
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.authority_usdc_account.to_account_info(),
+            to: ctx.accounts.pool_vault.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, initial_liquidity)?;
+
+        // Its shares are minted 1:1 into the dead share escrow, which nothing withdraws from,
+        // so the share supply never returns to zero and deposits are always priced from liquidity
+        let pool_key = pool.key();
+        let bump = [pool.bump];
+        let seeds = pool_signer_seeds(&pool_key, pool, &bump);
+        let signer = &[&seeds[..]];
+        let cpi_accounts = MintTo {
+            mint: ctx.accounts.share_mint.to_account_info(),
+            to: ctx.accounts.dead_share_escrow.to_account_info(),
+            authority: pool.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+        token::mint_to(cpi_ctx, initial_liquidity)?;
+
         Ok(())
     }
 
-    /// Deposit USDC to the lending pool (lenders)
-    pub fn deposit_usdc(ctx: Context<DepositUSDC>, amount: u64) -> Result<()> {
-        msg!("deposit_usdc: starting with amount {}", amount);
-        
-        // SECURITY FIX: Explicit zero amount validation
-        require!(amount > 0, LendingPoolError::InvalidAmount);
-        
-        // SECURITY FIX: Maximum deposit amount to prevent overflow (1 billion USDC)
-        const MAX_DEPOSIT_AMOUNT: u64 = 1_000_000_000_000_000; // 1 billion USDC with 6 decimals
-        require!(amount <= MAX_DEPOSIT_AMOUNT, LendingPoolError::InvalidAmount);
-        
-        msg!("deposit_usdc: deserializing pool, data len = {}", ctx.accounts.pool.try_borrow_data()?.len());
-        
-        // Manually deserialize pool account to handle old and new formats
+    /// Migrate an old, V1, V2, V3 or V4 pool account to the current layout
+    /// V1 through V4 pools are migrated by the pool authority. Old pools have no stored authority,
+    /// so the program upgrade authority migrates them and becomes the pool authority.
+    /// Old and V1 pools with outstanding debt must first settle flat-model interest into every
+    /// borrower account with settle_legacy_debt; balances then carry over at index 1.0, which
+    /// starts at the settlement timestamp. V2 and later pools keep their
+    /// borrow index and settle interest under their previous model. V2 pools start with a zero reserve
+    /// factor, and every migrated pool starts uncapped.
+    pub fn migrate_pool(
+        ctx: Context<MigratePool>,
+        interest_model: InterestRateModelConfig,
+    ) -> Result<()> {
+        validate_interest_model(&interest_model)?;
+
         let pool_data = ctx.accounts.pool.try_borrow_data()?;
-        let mut pool = deserialize_pool(&pool_data)?;
+        let pool = deserialize_pool(&pool_data)?;
         drop(pool_data);
-        
-        msg!("deposit_usdc: pool deserialized, bump = {}", pool.bump());
-        
+
+        let authority_key = ctx.accounts.authority.key();
+        let now = Clock::get()?.unix_timestamp as u64;
+
+        // V2, V3 and V4 pools already have a borrow index and only lack trailing fields
+        let indexed_pool = match &pool {
+            LendingPoolVersion::V2(pool) => Some(pool.clone().into_current()),
+            LendingPoolVersion::V3(pool) => Some(pool.clone().into_current()),
+            LendingPoolVersion::V4(pool) => Some(pool.clone().into_current()),
+            _ => None,
+        };
+        if let Some(mut new_pool) = indexed_pool {
+            require_keys_eq!(new_pool.authority, authority_key, LendingPoolError::Unauthorized);
+            // SECURITY FIX: Explicitly validate pool is a PDA with correct seeds
+            require_pool_pda(&ctx.accounts.pool.key(), &new_pool)?;
+            accrue_pool_interest(&mut new_pool, now)?;
+            new_pool.interest_model = interest_model;
+            refresh_rates(&mut new_pool)?;
+            return resize_and_write_pool(&ctx, &new_pool);
+        }
+
         // SECURITY FIX: Explicitly validate pool is a PDA with correct seeds
+        // Old and V1 layouts predate per-mint pools, so only the legacy USDC pool can hold them
         let (expected_pool_pda, expected_bump) = Pubkey::find_program_address(
             &[b"pool"],
             ctx.program_id,
         );
-        msg!("deposit_usdc: expected_pool_pda = {}, expected_bump = {}", expected_pool_pda, expected_bump);
-        msg!("deposit_usdc: actual pool key = {}", ctx.accounts.pool.key());
         require!(
             ctx.accounts.pool.key() == expected_pool_pda,
             LendingPoolError::InvalidConfig
@@ -301,105 +1055,309 @@ pub mod lending_pool_usdc {
             pool.bump() == expected_bump,
             LendingPoolError::InvalidConfig
         );
-        
-        msg!("deposit_usdc: PDA validation passed");
-        
-        // SECURITY FIX: Check if pool is paused
-        require!(!pool.paused(), LendingPoolError::PoolPaused);
-        
-        msg!("deposit_usdc: pool not paused");
-        
-        // Validate pool vault mint matches pool's USDC mint
-        msg!("deposit_usdc: vault mint = {}, pool usdc_mint = {}", ctx.accounts.pool_vault.mint, pool.usdc_mint());
-        require!(
-            ctx.accounts.pool_vault.mint == pool.usdc_mint(),
-            LendingPoolError::InvalidConfig
-        );
-        
-        // Transfer USDC from user to pool vault
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.user_usdc_account.to_account_info(),
-            to: ctx.accounts.pool_vault.to_account_info(),
-            authority: ctx.accounts.user.to_account_info(),
+
+        let (usdc_mint, total_liquidity, total_borrowed, pause_flags, bump) = match pool {
+            LendingPoolVersion::New(_)
+            | LendingPoolVersion::V2(_)
+            | LendingPoolVersion::V3(_)
+            | LendingPoolVersion::V4(_) => {
+                return Err(LendingPoolError::InvalidConfig.into())
+            }
+            LendingPoolVersion::V1(pool) => {
+                require_keys_eq!(pool.authority, authority_key, LendingPoolError::Unauthorized);
+                // A V1 pause blocked every operation, so it carries over as PAUSE_ALL
+                let pause_flags = encode_pause_flags(if pool.paused { PAUSE_ALL } else { PAUSE_NONE });
+                (pool.usdc_mint, pool.total_liquidity, pool.total_borrowed, pause_flags, pool.bump)
+            }
+            LendingPoolVersion::Old(pool) => {
+                let program_data = ctx.accounts.program_data
+                    .as_ref()
+                    .ok_or(LendingPoolError::Unauthorized)?;
+                require!(
+                    program_data.upgrade_authority_address == Some(authority_key),
+                    LendingPoolError::Unauthorized
+                );
+                (pool.usdc_mint, pool.total_liquidity, pool.total_borrowed, PAUSE_NONE, pool.bump)
+            }
         };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        token::transfer(cpi_ctx, amount)?;
 
-        // Update pool state
-        let new_total_liquidity = pool.total_liquidity()
-            .checked_add(amount)
+        // Interest accrued under the flat model is owed by borrowers and earned by lenders.
+        // SECURITY FIX: Every outstanding loan must be settled, otherwise its accrued interest is lost
+        let (legacy_interest, last_accrued_ts) = match &ctx.accounts.legacy_settlement {
+            Some(settlement) => {
+                require!(
+                    settlement.principal_settled == total_borrowed,
+                    LendingPoolError::UnsettledLegacyDebt
+                );
+                (settlement.interest_settled, settlement.settled_at)
+            }
+            None => {
+                require!(total_borrowed == 0, LendingPoolError::UnsettledLegacyDebt);
+                (0, now)
+            }
+        };
+        let total_borrowed = total_borrowed
+            .checked_add(legacy_interest)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let total_liquidity = total_liquidity
+            .checked_add(legacy_interest)
             .ok_or(ProgramError::ArithmeticOverflow)?;
-        pool.set_total_liquidity(new_total_liquidity);
 
-        // Serialize pool back to account data
-        let mut pool_data = ctx.accounts.pool.try_borrow_mut_data()?;
-        serialize_pool(&mut pool_data, &pool)?;
+        let mut new_pool = LendingPool {
+            authority: authority_key,
+            usdc_mint,
+            total_liquidity,
+            total_borrowed,
+            borrow_rate: 0,
+            lender_rate: 0,
+            pause_flags,
+            bump,
+            interest_model,
+            borrow_index: RATE_SCALE,
+            last_accrued_ts,
+            reserve_factor_bps: 0,
+            pending_reserves: 0,
+            max_utilization_bps: NO_UTILIZATION_CEILING_BPS,
+            supply_cap: None,
+            borrow_cap: None,
+            borrower_cap: None,
+            pending_authority: None,
+        };
+        refresh_rates(&mut new_pool)?;
 
-        emit!(USDCDeposited {
-            lender: ctx.accounts.user.key(),
-            amount,
-            total_liquidity: pool.total_liquidity(),
+        resize_and_write_pool(&ctx, &new_pool)
+    }
+
+    /// Settle flat-model interest into a batch of an old or V1 pool's borrower accounts, passed as
+    /// remaining accounts (same authority as migrate_pool). Progress is kept in the pool's
+    /// LegacySettlement account, so any number of borrowers can be settled over several
+    /// transactions; migrate_pool then requires the settled principal to equal total_borrowed.
+    pub fn settle_legacy_debt(ctx: Context<SettleLegacyDebt>) -> Result<()> {
+        let pool_data = ctx.accounts.pool.try_borrow_data()?;
+        let pool = deserialize_pool(&pool_data)?;
+        drop(pool_data);
+
+        // SECURITY FIX: Explicitly validate pool is a PDA with correct seeds
+        // Old and V1 layouts predate per-mint pools, so only the legacy USDC pool can hold them
+        let (expected_pool_pda, expected_bump) = Pubkey::find_program_address(&[b"pool"], ctx.program_id);
+        require_keys_eq!(ctx.accounts.pool.key(), expected_pool_pda, LendingPoolError::InvalidConfig);
+        require!(pool.bump() == expected_bump, LendingPoolError::InvalidConfig);
+
+        let authority_key = ctx.accounts.authority.key();
+        let (total_borrowed, borrow_rate) = match &pool {
+            LendingPoolVersion::V1(pool) => {
+                require_keys_eq!(pool.authority, authority_key, LendingPoolError::Unauthorized);
+                (pool.total_borrowed, pool.borrow_rate)
+            }
+            LendingPoolVersion::Old(pool) => {
+                let program_data = ctx.accounts.program_data
+                    .as_ref()
+                    .ok_or(LendingPoolError::Unauthorized)?;
+                require!(
+                    program_data.upgrade_authority_address == Some(authority_key),
+                    LendingPoolError::Unauthorized
+                );
+                (pool.total_borrowed, pool.borrow_rate)
+            }
+            _ => return Err(LendingPoolError::InvalidConfig.into()),
+        };
+
+        // Every batch settles up to the timestamp of the first, where the borrow index starts
+        let settlement = &mut ctx.accounts.legacy_settlement;
+        if settlement.pool == Pubkey::default() {
+            settlement.pool = ctx.accounts.pool.key();
+            settlement.settled_at = Clock::get()?.unix_timestamp as u64;
+            settlement.bump = ctx.bumps.legacy_settlement;
+        }
+
+        let (principal, interest) = settle_legacy_borrowers(
+            ctx.remaining_accounts,
+            &ctx.accounts.pool.key(),
+            borrow_rate,
+            settlement.settled_at,
+        )?;
+        settlement.principal_settled = settlement.principal_settled
+            .checked_add(principal)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        settlement.interest_settled = settlement.interest_settled
+            .checked_add(interest)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        // Borrower accounts can't hold more principal than the pool lent
+        require!(
+            settlement.principal_settled <= total_borrowed,
+            LendingPoolError::InvalidBorrower
+        );
+
+        emit!(LegacyDebtSettled {
+            pool: ctx.accounts.pool.key(),
+            principal_settled: settlement.principal_settled,
+            interest_settled: settlement.interest_settled,
+            total_borrowed,
         });
+        Ok(())
+    }
+
+    /// Accrue interest on the pool (permissionless)
+    pub fn accrue_interest(ctx: Context<AccrueInterest>) -> Result<()> {
+        let pool_data = ctx.accounts.pool.try_borrow_data()?;
+        let mut pool = deserialize_pool(&pool_data)?.into_current()?;
+        drop(pool_data);
+
+        // SECURITY FIX: Explicitly validate pool is a PDA with correct seeds
+        require_pool_pda(&ctx.accounts.pool.key(), &pool)?;
+
+        accrue_pool_interest(&mut pool, Clock::get()?.unix_timestamp as u64)?;
 
+        let mut pool_data = ctx.accounts.pool.try_borrow_mut_data()?;
+        serialize_pool(&mut pool_data, &pool)?;
         Ok(())
     }
 
-    /// Borrow USDC from the lending pool
-    pub fn borrow_usdc(ctx: Context<BorrowUSDC>, amount: u64) -> Result<()> {
+    /// Deposit USDC to the lending pool (lenders)
+    pub fn deposit_usdc(ctx: Context<DepositUSDC>, amount: u64) -> Result<()> {
+        // SECURITY FIX: Explicit zero amount validation
+        require!(amount > 0, LendingPoolError::InvalidAmount);
+
+        // SECURITY FIX: Maximum deposit amount to prevent overflow (1 billion USDC)
+        const MAX_DEPOSIT_AMOUNT: u64 = 1_000_000_000_000_000; // 1 billion USDC with 6 decimals
+        require!(amount <= MAX_DEPOSIT_AMOUNT, LendingPoolError::InvalidAmount);
+
         // Manually deserialize pool account to handle old and new formats
         let pool_data = ctx.accounts.pool.try_borrow_data()?;
-        let mut pool = deserialize_pool(&pool_data)?;
+        let mut pool = deserialize_pool(&pool_data)?.into_current()?;
         drop(pool_data);
-        
+
         // SECURITY FIX: Explicitly validate pool is a PDA with correct seeds
-        let (expected_pool_pda, expected_bump) = Pubkey::find_program_address(
-            &[b"pool"],
-            ctx.program_id,
-        );
-        require!(
-            ctx.accounts.pool.key() == expected_pool_pda,
-            LendingPoolError::InvalidConfig
-        );
+        require_pool_pda(&ctx.accounts.pool.key(), &pool)?;
+
+        // SECURITY FIX: Check if deposits are paused
+        require!(!is_paused(pool.pause_flags, PAUSE_DEPOSITS), LendingPoolError::PoolPaused);
+        forge_core::require_protocol_active(&ctx.accounts.forge_protocol)?;
+
+        // Validate pool vault mint matches pool's USDC mint
         require!(
-            pool.bump() == expected_bump,
+            ctx.accounts.pool_vault.mint == pool.usdc_mint,
             LendingPoolError::InvalidConfig
         );
-        
-        // SECURITY FIX: Check if pool is paused
-        require!(!pool.paused(), LendingPoolError::PoolPaused);
-        
+
+        // Accrue before pricing shares so the depositor does not capture pending interest
+        accrue_pool_interest(&mut pool, Clock::get()?.unix_timestamp as u64)?;
+
+        // Supply cap bounds pool liquidity, including interest already accrued to lenders
+        if let Some(supply_cap) = pool.supply_cap {
+            let new_liquidity = pool.total_liquidity
+                .checked_add(amount)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            require!(new_liquidity <= supply_cap, LendingPoolError::SupplyCapExceeded);
+        }
+
+        // Price the deposit against current pool value before it changes
+        let shares = shares_for_deposit(
+            amount,
+            pool.total_liquidity,
+            ctx.accounts.share_mint.supply,
+        )?;
+        require!(shares > 0, LendingPoolError::InvalidAmount);
+
+        // Transfer USDC from user to pool vault
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.user_usdc_account.to_account_info(),
+            to: ctx.accounts.pool_vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        // Mint lender shares - pool PDA is the share mint authority
+        let bump = [pool.bump];
+        let seeds = pool_signer_seeds(&ctx.accounts.pool.key(), &pool, &bump);
+        let signer = &[&seeds[..]];
+        let cpi_accounts = MintTo {
+            mint: ctx.accounts.share_mint.to_account_info(),
+            to: ctx.accounts.user_share_account.to_account_info(),
+            authority: ctx.accounts.pool.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::mint_to(cpi_ctx, shares)?;
+
+        // Update pool state
+        pool.total_liquidity = pool.total_liquidity
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        refresh_rates(&mut pool)?;
+
+        // Serialize pool back to account data
+        let mut pool_data = ctx.accounts.pool.try_borrow_mut_data()?;
+        serialize_pool(&mut pool_data, &pool)?;
+
+        emit!(USDCDeposited {
+            pool: ctx.accounts.pool.key(),
+            lender: ctx.accounts.user.key(),
+            amount,
+            shares_minted: shares,
+            total_liquidity: pool.total_liquidity,
+        });
+
+        Ok(())
+    }
+
+    /// Borrow USDC from the lending pool against `position` (e.g. a leveraged position PDA)
+    /// Only callable with a registered borrow authority (e.g. a crucible PDA signing via CPI),
+    /// so borrows always go through a program that holds the borrower's collateral.
+    /// Each (borrower, position) pair has its own borrower account, so debts never blend.
+    pub fn borrow_usdc(ctx: Context<BorrowUSDC>, amount: u64, position: Pubkey) -> Result<()> {
+        // Manually deserialize pool account to handle old and new formats
+        let pool_data = ctx.accounts.pool.try_borrow_data()?;
+        let mut pool = deserialize_pool(&pool_data)?.into_current()?;
+        drop(pool_data);
+
+        // SECURITY FIX: Explicitly validate pool is a PDA with correct seeds
+        require_pool_pda(&ctx.accounts.pool.key(), &pool)?;
+
+        // SECURITY FIX: Check if borrows are paused
+        require!(!is_paused(pool.pause_flags, PAUSE_LEVERAGE_OPEN), LendingPoolError::PoolPaused);
+        forge_core::require_protocol_active(&ctx.accounts.forge_protocol)?;
+
         // SECURITY FIX: Explicit zero amount validation
         require!(amount > 0, LendingPoolError::InvalidAmount);
-        
+        // The default position addresses the legacy per-borrower account, which only takes repayments
+        require!(position != Pubkey::default(), LendingPoolError::InvalidBorrower);
+
         // SECURITY FIX: Maximum borrow amount to prevent overflow (1 billion USDC)
         const MAX_BORROW_AMOUNT: u64 = 1_000_000_000_000_000; // 1 billion USDC with 6 decimals
         require!(amount <= MAX_BORROW_AMOUNT, LendingPoolError::InvalidAmount);
 
-        // SECURITY FIX (MEDIUM-001): Enforce minimum liquidity reserve to prevent complete pool drainage
-        const MIN_LIQUIDITY_RESERVE: u64 = 1_000_000; // 1 USDC minimum reserve (1 USDC = 1_000_000 lamports for 6 decimals)
-        let available = pool.total_liquidity()
-            .checked_sub(pool.total_borrowed())
-            .ok_or(LendingPoolError::InsufficientLiquidity)?;
-        // SECURITY FIX (MEDIUM-003): Use explicit error handling instead of unwrap_or(0)
-        let borrowable = available
-            .checked_sub(MIN_LIQUIDITY_RESERVE)
-            .ok_or(LendingPoolError::InsufficientLiquidity)?;
-        require!(
-            amount > 0 && amount <= borrowable,
-            LendingPoolError::InsufficientLiquidity
-        );
-        
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        accrue_pool_interest(&mut pool, current_timestamp)?;
+
+        check_pool_borrow_limits(&pool, amount)?;
+
+        // SECURITY FIX: Only registered borrow authorities can draw on the pool. The authority
+        // signs as a PDA, so only its program can borrow, and only within its credit limit.
+        let borrow_authority = ctx.accounts.borrow_authority.key();
+        let authorized_borrower = &mut ctx.accounts.authorized_borrower;
+
+        // Debt is stored scaled by the global borrow index, so interest on earlier
+        // borrows keeps compounding from their own start without per-borrower timestamps
+        let scaled_amount = scaled_from_amount(amount, pool.borrow_index, true)?;
+        // SECURITY FIX: The per-borrower cap covers the credit line's aggregate debt, so it can't
+        // be sidestepped by spreading borrows over many per-position borrower accounts
+        draw_credit(authorized_borrower, scaled_amount, pool.borrow_index, pool.borrower_cap)?;
+
         // Validate pool vault mint matches pool's USDC mint
         require!(
-            ctx.accounts.pool_vault.mint == pool.usdc_mint(),
+            ctx.accounts.pool_vault.mint == pool.usdc_mint,
             LendingPoolError::InvalidConfig
         );
-        
+
         // Transfer USDC from pool vault to borrower
         // SECURITY FIX: Pool is a PDA and signs transfers
-        let seeds: &[&[u8]] = &[b"pool", &[pool.bump()]];
-        let signer = &[seeds];
+        let bump = [pool.bump];
+        let seeds = pool_signer_seeds(&ctx.accounts.pool.key(), &pool, &bump);
+        let signer = &[&seeds[..]];
 
         let cpi_accounts = Transfer {
             from: ctx.accounts.pool_vault.to_account_info(),
@@ -411,175 +1369,99 @@ pub mod lending_pool_usdc {
         token::transfer(cpi_ctx, amount)?;
 
         // Update pool state
-        let new_total_borrowed = pool.total_borrowed()
+        pool.total_borrowed = pool.total_borrowed
             .checked_add(amount)
             .ok_or(ProgramError::ArithmeticOverflow)?;
-        pool.set_total_borrowed(new_total_borrowed);
+        refresh_rates(&mut pool)?;
 
         // Record borrower debt
         // SECURITY FIX: init_if_needed handles account creation, but we need to validate
         // that the borrower field matches the signer after initialization
         let borrower_account = &mut ctx.accounts.borrower_account;
-        
-        let clock = Clock::get()?;
-        
-        // SECURITY FIX (HIGH-003): Use Unix timestamp instead of slot for accurate time measurement
-        let current_timestamp = clock.unix_timestamp as u64;
-        
+
         // Check if account was just initialized by checking if borrower is default
         // If so, initialize it properly
         if borrower_account.borrower == Pubkey::default() {
             borrower_account.borrower = ctx.accounts.borrower.key();
             borrower_account.amount_borrowed = 0;
-            borrower_account.borrow_timestamp = current_timestamp; // Track creation timestamp for new borrowers
         } else {
             // SECURITY FIX: Validate borrower matches signer to prevent account hijacking
             require!(
                 borrower_account.borrower == ctx.accounts.borrower.key(),
                 LendingPoolError::InvalidBorrower
             );
-            // SECURITY FIX (HIGH-003 + CRITICAL-002): Calculate weighted average borrow timestamp
-            // When adding to existing debt, calculate: (old_amount * old_timestamp + new_amount * current_timestamp) / (old_amount + new_amount)
-            // This ensures each borrow's interest is calculated from its own borrow time
-            let old_amount = borrower_account.amount_borrowed;
-            let old_timestamp = borrower_account.borrow_timestamp;
-            let new_amount = old_amount
-                .checked_add(amount)
-                .ok_or(ProgramError::ArithmeticOverflow)?;
-            
-            if old_amount > 0 {
-                // Calculate weighted average: (old_amount * old_timestamp + new_amount * current_timestamp) / new_amount
-                let old_weighted = (old_amount as u128)
-                    .checked_mul(old_timestamp as u128)
-                    .ok_or(ProgramError::ArithmeticOverflow)?;
-                let new_weighted = (amount as u128)
-                    .checked_mul(current_timestamp as u128)
-                    .ok_or(ProgramError::ArithmeticOverflow)?;
-                let total_weighted = old_weighted
-                    .checked_add(new_weighted)
-                    .ok_or(ProgramError::ArithmeticOverflow)?;
-                borrower_account.borrow_timestamp = (total_weighted
-                    .checked_div(new_amount as u128)
-                    .ok_or(ProgramError::ArithmeticOverflow)?) as u64;
-            } else {
-                // No existing amount, use current timestamp
-                borrower_account.borrow_timestamp = current_timestamp;
-            }
         }
-        
+
+        // A borrower account draws on one credit line at a time, so repayments know
+        // which line to release. Outstanding debt keeps the account on its current line.
+        if borrower_account.amount_borrowed > 0 {
+            require_keys_eq!(
+                borrower_account.borrow_authority,
+                borrow_authority,
+                LendingPoolError::BorrowerNotAuthorized
+            );
+        }
+        borrower_account.borrow_authority = borrow_authority;
+
         borrower_account.amount_borrowed = borrower_account.amount_borrowed
-            .checked_add(amount)
+            .checked_add(scaled_amount)
             .ok_or(ProgramError::ArithmeticOverflow)?;
+        borrower_account.borrow_timestamp = current_timestamp;
 
         // Serialize pool back to account data
         let mut pool_data = ctx.accounts.pool.try_borrow_mut_data()?;
         serialize_pool(&mut pool_data, &pool)?;
 
         emit!(USDCBorrowed {
+            pool: ctx.accounts.pool.key(),
             borrower: ctx.accounts.borrower.key(),
+            position,
+            borrow_authority,
             amount,
-            total_borrowed: pool.total_borrowed(),
+            total_borrowed: pool.total_borrowed,
         });
 
         Ok(())
     }
 
-    /// Repay borrowed USDC
-    pub fn repay_usdc(ctx: Context<RepayUSDC>, amount: u64) -> Result<()> {
+    /// Repay borrowed USDC on `position` (Pubkey::default() for the legacy per-borrower account)
+    pub fn repay_usdc(ctx: Context<RepayUSDC>, amount: u64, position: Pubkey) -> Result<()> {
         // Manually deserialize pool account to handle old and new formats
         let pool_data = ctx.accounts.pool.try_borrow_data()?;
-        let mut pool = deserialize_pool(&pool_data)?;
+        let mut pool = deserialize_pool(&pool_data)?.into_current()?;
         drop(pool_data);
-        
+
         // SECURITY FIX: Explicitly validate pool is a PDA with correct seeds
-        let (expected_pool_pda, expected_bump) = Pubkey::find_program_address(
-            &[b"pool"],
-            ctx.program_id,
-        );
-        require!(
-            ctx.accounts.pool.key() == expected_pool_pda,
-            LendingPoolError::InvalidConfig
-        );
-        require!(
-            pool.bump() == expected_bump,
-            LendingPoolError::InvalidConfig
-        );
-        
-        // SECURITY FIX: Check if pool is paused
-        require!(!pool.paused(), LendingPoolError::PoolPaused);
-        
+        require_pool_pda(&ctx.accounts.pool.key(), &pool)?;
+
+        // SECURITY FIX: Check if repayments are paused
+        require!(!is_paused(pool.pause_flags, PAUSE_LEVERAGE_CLOSE), LendingPoolError::PoolPaused);
+        forge_core::require_protocol_active_if_present(ctx.accounts.forge_protocol.as_deref())?;
+
         // SECURITY FIX: Explicit zero amount validation
         require!(amount > 0, LendingPoolError::InvalidAmount);
-        
+
         let borrower_account = &mut ctx.accounts.borrower_account;
-        
+
         // SECURITY FIX (LOW-002): Validate borrower account is initialized
         require!(
             borrower_account.borrower != Pubkey::default(),
             LendingPoolError::InvalidBorrower
         );
-        
+
         // SECURITY FIX: Validate borrower matches signer
         require!(
             borrower_account.borrower == ctx.accounts.borrower.key(),
             LendingPoolError::InvalidBorrower
         );
-        
-        let clock = Clock::get()?;
-
-        // SECURITY FIX (HIGH-003): Calculate accrued interest using Unix timestamp for accurate time measurement
-        // Interest calculation: borrowedAmount × (borrowRate / 100) × (timeElapsed / timePerYear)
-        // borrow_rate is stored as 10 = 10% APY (scaled by 100)
-        // Use Unix timestamp-based calculation for accuracy (not affected by slot timing variations)
-        const SECONDS_PER_YEAR: u64 = 31_536_000; // Exact: 365 * 24 * 60 * 60
-        
-        // SECURITY FIX: Use borrow_timestamp (tracked when loan was created) for accurate time measurement
-        // SECURITY FIX: Validate borrow_timestamp <= current_timestamp to prevent invalid time calculations
-        let current_timestamp = clock.unix_timestamp as u64;
-        require!(
-            borrower_account.borrow_timestamp <= current_timestamp,
-            LendingPoolError::InvalidConfig
-        );
-        let seconds_elapsed = current_timestamp
-            .checked_sub(borrower_account.borrow_timestamp)
-            .ok_or(LendingPoolError::InvalidConfig)?;
-        
-        // SECURITY FIX: Proper interest calculation with optimized precision
-        // Multiply numerators first, then divide to maximize precision
-        let borrow_rate = pool.borrow_rate(); // Fetch from pool state (10 = 10% APY, scaled by 100)
-        let principal_u128 = borrower_account.amount_borrowed as u128;
-        let borrow_rate_u128 = borrow_rate as u128;
-        let seconds_elapsed_u128 = seconds_elapsed as u128;
-        
-        // Interest = (principal × borrow_rate × seconds_elapsed) / (100 × SECONDS_PER_YEAR)
-        // Multiply all numerators first, then divide by denominator to maximize precision
-        // SECURITY FIX: Return error on overflow instead of silently defaulting to 0
-        // Overflow indicates extremely large values that could indicate an attack or bug
-        let interest_accrued = principal_u128
-            .checked_mul(borrow_rate_u128)
-            .and_then(|v| v.checked_mul(seconds_elapsed_u128))
-            .and_then(|v| v.checked_div(100u128))
-            .and_then(|v| v.checked_div(SECONDS_PER_YEAR as u128))
-            .ok_or(LendingPoolError::InvalidAmount)?;
-        
-        let total_owed = (borrower_account.amount_borrowed as u128)
-            .checked_add(interest_accrued)
-            .ok_or(ProgramError::ArithmeticOverflow)?;
-        
-        let total_owed_u64 = if total_owed > u64::MAX as u128 {
-            return Err(ProgramError::ArithmeticOverflow.into());
-        } else {
-            total_owed as u64
-        };
 
-        require!(
-            amount <= total_owed_u64,
-            LendingPoolError::RepayAmountExceedsDebt
-        );
+        let current_timestamp = Clock::get()?.unix_timestamp as u64;
+        let scaled_repaid = apply_repayment(&mut pool, borrower_account, amount, current_timestamp)?;
+        release_credit(borrower_account, ctx.accounts.authorized_borrower.as_mut(), scaled_repaid)?;
 
         // Validate pool vault mint matches pool's USDC mint
         require!(
-            ctx.accounts.pool_vault.mint == pool.usdc_mint(),
+            ctx.accounts.pool_vault.mint == pool.usdc_mint,
             LendingPoolError::InvalidConfig
         );
 
@@ -593,186 +1475,1321 @@ pub mod lending_pool_usdc {
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
         token::transfer(cpi_ctx, amount)?;
 
-        // SECURITY FIX (CRITICAL-005): Properly calculate principal vs interest portions
-        // Calculate how much principal was repaid (amount may include interest)
-        let principal_repaid = if amount >= total_owed_u64 {
-            // Full repayment - repay all principal
-            borrower_account.amount_borrowed
-        } else {
-            // Partial repayment - calculate principal portion
-            // principal_repaid = amount × (principal / total_owed)
-            // This ensures interest is paid first, then principal
-            let principal_portion = (amount as u128)
-                .checked_mul(borrower_account.amount_borrowed as u128)
-                .and_then(|v| v.checked_div(total_owed_u64 as u128))
-                .ok_or(LendingPoolError::InvalidAmount)? as u64;
-            
-            // Update borrow_timestamp proportionally for remaining debt
-            // New timestamp = old_timestamp + (elapsed × principal_repaid / principal)
-            let remaining_principal = borrower_account.amount_borrowed
-                .checked_sub(principal_portion)
-                .ok_or(LendingPoolError::InvalidAmount)?;
-            
-            if remaining_principal > 0 {
-                // Update timestamp: move forward proportionally
-                let principal_repaid_u128 = principal_portion as u128;
-                let total_principal_u128 = borrower_account.amount_borrowed as u128;
-                let time_advance = (seconds_elapsed as u128)
-                    .checked_mul(principal_repaid_u128)
-                    .and_then(|v| v.checked_div(total_principal_u128))
-                    .ok_or(LendingPoolError::InvalidAmount)?;
-                borrower_account.borrow_timestamp = borrower_account.borrow_timestamp
-                    .checked_add(time_advance as u64)
-                    .ok_or(LendingPoolError::InvalidAmount)?;
-            } else {
-                // All principal repaid, reset timestamp
-                borrower_account.borrow_timestamp = current_timestamp;
-            }
-            
-            principal_portion
-        };
-        
-        // SECURITY FIX: Validate amounts are sufficient before subtraction to detect accounting errors
-        require!(
-            pool.total_borrowed() >= principal_repaid,
-            LendingPoolError::InvalidAmount
-        );
-        require!(
-            borrower_account.amount_borrowed >= principal_repaid,
-            LendingPoolError::InvalidAmount
-        );
-        
-        let new_total_borrowed = pool.total_borrowed()
-            .checked_sub(principal_repaid)
-            .ok_or(LendingPoolError::InvalidAmount)?;
-        pool.set_total_borrowed(new_total_borrowed);
-        
         // Serialize pool back to account data
         let mut pool_data = ctx.accounts.pool.try_borrow_mut_data()?;
         serialize_pool(&mut pool_data, &pool)?;
-        borrower_account.amount_borrowed = borrower_account.amount_borrowed
-            .checked_sub(principal_repaid)
-            .ok_or(LendingPoolError::InvalidAmount)?;
-        
-        // SECURITY FIX (CRITICAL-005): Reset timestamp if all debt is repaid
-        if borrower_account.amount_borrowed == 0 {
-            borrower_account.borrow_timestamp = current_timestamp;
-        }
 
         emit!(USDCRepaid {
+            pool: ctx.accounts.pool.key(),
             borrower: ctx.accounts.borrower.key(),
+            position,
             amount,
-            remaining_debt: borrower_account.amount_borrowed,
+            remaining_debt: debt_from_scaled(borrower_account.amount_borrowed, pool.borrow_index)?,
         });
 
         Ok(())
     }
 
-    /// Initialize the pool vault (for existing pools that don't have a vault)
-    /// The vault token account is automatically created by Anchor's init constraint
-    pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
+    /// Repay USDC on behalf of another borrower (e.g. a keeper liquidating a leveraged position)
+    /// The payer supplies the USDC; the borrower does not need to sign
+    pub fn repay_usdc_on_behalf(ctx: Context<RepayUSDCOnBehalf>, amount: u64, position: Pubkey) -> Result<()> {
         // Manually deserialize pool account to handle old and new formats
         let pool_data = ctx.accounts.pool.try_borrow_data()?;
-        let pool = deserialize_pool(&pool_data)?;
+        let mut pool = deserialize_pool(&pool_data)?.into_current()?;
         drop(pool_data);
-        
-        // Validate pool authority (new format only)
-        if let Some(authority) = pool.authority() {
-            require_keys_eq!(
-                authority,
-                ctx.accounts.authority.key(),
-                LendingPoolError::Unauthorized
-            );
-        }
 
-        // Validate USDC mint matches pool's mint (old and new)
+        // SECURITY FIX: Explicitly validate pool is a PDA with correct seeds
+        require_pool_pda(&ctx.accounts.pool.key(), &pool)?;
+
+        // SECURITY FIX: Check if repayments are paused
+        require!(!is_paused(pool.pause_flags, PAUSE_LEVERAGE_CLOSE), LendingPoolError::PoolPaused);
+        forge_core::require_protocol_active_if_present(ctx.accounts.forge_protocol.as_deref())?;
+
+        // SECURITY FIX: Explicit zero amount validation
+        require!(amount > 0, LendingPoolError::InvalidAmount);
+
+        let borrower_account = &mut ctx.accounts.borrower_account;
+
+        // SECURITY FIX (LOW-002): Validate borrower account is initialized
+        require!(
+            borrower_account.borrower != Pubkey::default(),
+            LendingPoolError::InvalidBorrower
+        );
+        require!(
+            borrower_account.borrower == ctx.accounts.borrower.key(),
+            LendingPoolError::InvalidBorrower
+        );
+
+        let current_timestamp = Clock::get()?.unix_timestamp as u64;
+        let scaled_repaid = apply_repayment(&mut pool, borrower_account, amount, current_timestamp)?;
+        release_credit(borrower_account, ctx.accounts.authorized_borrower.as_mut(), scaled_repaid)?;
+
+        // Validate pool vault mint matches pool's USDC mint
         require!(
-            ctx.accounts.usdc_mint.key() == pool.usdc_mint(),
+            ctx.accounts.pool_vault.mint == pool.usdc_mint,
             LendingPoolError::InvalidConfig
         );
-        
-        // Vault is automatically initialized by Anchor's init constraint in InitializeVault struct
-        msg!("Pool vault initialized for pool: {}", ctx.accounts.pool.key());
-        
+
+        // Transfer USDC from payer to pool vault
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.payer_usdc_account.to_account_info(),
+            to: ctx.accounts.pool_vault.to_account_info(),
+            authority: ctx.accounts.payer.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        // Serialize pool back to account data
+        let mut pool_data = ctx.accounts.pool.try_borrow_mut_data()?;
+        serialize_pool(&mut pool_data, &pool)?;
+
+        emit!(USDCRepaid {
+            pool: ctx.accounts.pool.key(),
+            borrower: ctx.accounts.borrower.key(),
+            position,
+            amount,
+            remaining_debt: debt_from_scaled(borrower_account.amount_borrowed, pool.borrow_index)?,
+        });
+
         Ok(())
     }
 
-    /// Get available liquidity (view function simulation)
-    pub fn get_available_liquidity(ctx: Context<GetAvailableLiquidity>) -> Result<u64> {
-        // Manually deserialize pool account to handle old and new formats
-        let pool_data = ctx.accounts.pool.try_borrow_data()?;
-        let pool = deserialize_pool(&pool_data)?;
-        
-        let available = pool.total_liquidity()
-            .checked_sub(pool.total_borrowed())
-            .ok_or(LendingPoolError::InsufficientLiquidity)?;
-        Ok(available)
-    }
-    
-    /// Pause/Resume the lending pool (emergency function)
-    pub fn set_pool_status(
-        ctx: Context<SetPoolStatus>,
-        paused: bool,
-    ) -> Result<()> {
+    /// Withdraw USDC from the lending pool by redeeming lender shares
+    pub fn withdraw_usdc(ctx: Context<WithdrawUSDC>, shares: u64) -> Result<()> {
+        // SECURITY FIX: Explicit zero amount validation
+        require!(shares > 0, LendingPoolError::InvalidAmount);
+        require!(
+            shares <= ctx.accounts.user_share_account.amount,
+            LendingPoolError::InsufficientShares
+        );
+
         // Manually deserialize pool account to handle old and new formats
         let pool_data = ctx.accounts.pool.try_borrow_data()?;
-        let pool = deserialize_pool(&pool_data)?;
+        let mut pool = deserialize_pool(&pool_data)?.into_current()?;
         drop(pool_data);
-        
-        // Old format doesn't support paused/authority updates
-        let mut new_pool = match pool {
-            LendingPoolVersion::New(pool) => pool,
-            LendingPoolVersion::Old(_) => return Err(LendingPoolError::InvalidConfig.into()),
-        };
-        
-        // SECURITY FIX: Explicitly verify authority matches pool authority
-        require_keys_eq!(
-            new_pool.authority,
-            ctx.accounts.authority.key(),
-            LendingPoolError::Unauthorized
-        );
-        
-        // SECURITY FIX: Prevent redundant state changes
+
+        // SECURITY FIX: Explicitly validate pool is a PDA with correct seeds
+        require_pool_pda(&ctx.accounts.pool.key(), &pool)?;
+
+        // SECURITY FIX: Check if withdrawals are paused
+        require!(!is_paused(pool.pause_flags, PAUSE_WITHDRAWALS), LendingPoolError::PoolPaused);
+        forge_core::require_protocol_active_if_present(ctx.accounts.forge_protocol.as_deref())?;
+
+        // Validate pool vault mint matches pool's USDC mint
         require!(
-            new_pool.paused != paused,
+            ctx.accounts.pool_vault.mint == pool.usdc_mint,
             LendingPoolError::InvalidConfig
         );
-        
-        new_pool.paused = paused;
-        
-        // Serialize pool back to account data
-        let mut pool_data = ctx.accounts.pool.try_borrow_mut_data()?;
-        let mut pool_slice = &mut pool_data[8..]; // Skip discriminator
-        new_pool.serialize(&mut pool_slice)?;
-        
-        msg!("Pool status set to: {}", if paused { "paused" } else { "active" });
-        Ok(())
-    }
-}
+
+        // Accrue first so the redemption includes interest earned up to now
+        accrue_pool_interest(&mut pool, Clock::get()?.unix_timestamp as u64)?;
+
+        let amount = usdc_for_shares(
+            shares,
+            pool.total_liquidity,
+            ctx.accounts.share_mint.supply,
+        )?;
+        require!(amount > 0, LendingPoolError::InvalidAmount);
+
+        // Lent-out USDC cannot be withdrawn. While loans are outstanding the
+        // minimum reserve must also stay in the pool.
+        let available = pool.total_liquidity
+            .checked_sub(pool.total_borrowed)
+            .ok_or(LendingPoolError::InsufficientLiquidity)?;
+        let withdrawable = if pool.total_borrowed > 0 {
+            available
+                .checked_sub(MIN_LIQUIDITY_RESERVE)
+                .ok_or(LendingPoolError::InsufficientLiquidity)?
+        } else {
+            available
+        };
+        require!(
+            amount <= withdrawable && amount <= ctx.accounts.pool_vault.amount,
+            LendingPoolError::InsufficientLiquidity
+        );
+
+        // Burn shares first so the redemption cannot be replayed
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.share_mint.to_account_info(),
+            from: ctx.accounts.user_share_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::burn(cpi_ctx, shares)?;
+
+        // Transfer USDC from pool vault to lender
+        // SECURITY FIX: Pool is a PDA and signs transfers
+        let bump = [pool.bump];
+        let seeds = pool_signer_seeds(&ctx.accounts.pool.key(), &pool, &bump);
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.pool_vault.to_account_info(),
+            to: ctx.accounts.user_usdc_account.to_account_info(),
+            authority: ctx.accounts.pool.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+
+        // Update pool state
+        pool.total_liquidity = pool.total_liquidity
+            .checked_sub(amount)
+            .ok_or(LendingPoolError::InsufficientLiquidity)?;
+        refresh_rates(&mut pool)?;
+
+        // Serialize pool back to account data
+        let mut pool_data = ctx.accounts.pool.try_borrow_mut_data()?;
+        serialize_pool(&mut pool_data, &pool)?;
+
+        emit!(USDCWithdrawn {
+            pool: ctx.accounts.pool.key(),
+            lender: ctx.accounts.user.key(),
+            amount,
+            shares_burned: shares,
+            total_liquidity: pool.total_liquidity,
+        });
+
+        Ok(())
+    }
+
+    /// Initialize the lender share mint (for existing pools created before shares existed)
+    /// Liquidity already in the pool is represented by shares minted 1:1 into the bootstrap
+    /// escrow, so later depositors cannot claim it. Earlier lenders claim them with
+    /// claim_legacy_deposit once the authority registers their deposits.
+    pub fn initialize_share_mint(ctx: Context<InitializeShareMint>) -> Result<()> {
+        // Manually deserialize pool account to handle old and new formats
+        let pool_data = ctx.accounts.pool.try_borrow_data()?;
+        let pool = deserialize_pool(&pool_data)?.into_current()?;
+        drop(pool_data);
+
+        require_keys_eq!(
+            pool.authority,
+            ctx.accounts.authority.key(),
+            LendingPoolError::Unauthorized
+        );
+        require!(
+            ctx.accounts.usdc_mint.key() == pool.usdc_mint,
+            LendingPoolError::InvalidConfig
+        );
+
+        let bootstrap_shares = pool.total_liquidity;
+        if bootstrap_shares > 0 {
+            let bump = [pool.bump];
+            let seeds = pool_signer_seeds(&ctx.accounts.pool.key(), &pool, &bump);
+            let signer = &[&seeds[..]];
+            let cpi_accounts = MintTo {
+                mint: ctx.accounts.share_mint.to_account_info(),
+                to: ctx.accounts.bootstrap_share_escrow.to_account_info(),
+                authority: ctx.accounts.pool.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token::mint_to(cpi_ctx, bootstrap_shares)?;
+        }
+
+        msg!("Share mint initialized for pool: {}, bootstrap shares: {}", ctx.accounts.pool.key(), bootstrap_shares);
+
+        Ok(())
+    }
+
+    /// Record a deposit made before the share mint existed (only pool authority)
+    /// Lenders then claim `shares` from the bootstrap escrow with claim_legacy_deposit.
+    pub fn register_legacy_deposit(
+        ctx: Context<RegisterLegacyDeposit>,
+        lender: Pubkey,
+        shares: u64,
+    ) -> Result<()> {
+        let pool = load_pool(&ctx.accounts.pool)?;
+        require_keys_eq!(pool.authority, ctx.accounts.authority.key(), LendingPoolError::Unauthorized);
+        require!(shares > 0, LendingPoolError::InvalidAmount);
+        require!(lender != Pubkey::default(), LendingPoolError::InvalidConfig);
+
+        let legacy_deposit = &mut ctx.accounts.legacy_deposit;
+        legacy_deposit.pool = ctx.accounts.pool.key();
+        legacy_deposit.lender = lender;
+        legacy_deposit.shares = shares;
+        legacy_deposit.claimed = false;
+        legacy_deposit.bump = ctx.bumps.legacy_deposit;
+
+        emit!(LegacyDepositRegistered {
+            pool: legacy_deposit.pool,
+            lender,
+            shares,
+        });
+        Ok(())
+    }
+
+    /// Claim the shares for a deposit made before the share mint existed (lender)
+    pub fn claim_legacy_deposit(ctx: Context<ClaimLegacyDeposit>) -> Result<()> {
+        let pool = load_pool(&ctx.accounts.pool)?;
+        // SECURITY FIX: Explicitly validate pool is a PDA with correct seeds
+        require_pool_pda(&ctx.accounts.pool.key(), &pool)?;
+
+        let legacy_deposit = &mut ctx.accounts.legacy_deposit;
+        require!(!legacy_deposit.claimed, LendingPoolError::LegacyDepositAlreadyClaimed);
+        require!(
+            ctx.accounts.bootstrap_share_escrow.amount >= legacy_deposit.shares,
+            LendingPoolError::InsufficientShares
+        );
+
+        let bump = [pool.bump];
+        let seeds = pool_signer_seeds(&ctx.accounts.pool.key(), &pool, &bump);
+        let signer = &[&seeds[..]];
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.bootstrap_share_escrow.to_account_info(),
+            to: ctx.accounts.lender_share_account.to_account_info(),
+            authority: ctx.accounts.pool.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, legacy_deposit.shares)?;
+
+        legacy_deposit.claimed = true;
+
+        emit!(LegacyDepositClaimed {
+            pool: legacy_deposit.pool,
+            lender: legacy_deposit.lender,
+            shares: legacy_deposit.shares,
+        });
+        Ok(())
+    }
+
+    /// Initialize the pool vault (for existing pools that don't have a vault)
+    /// The vault token account is automatically created by Anchor's init constraint
+    pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
+        // Manually deserialize pool account to handle old and new formats
+        let pool_data = ctx.accounts.pool.try_borrow_data()?;
+        let pool = deserialize_pool(&pool_data)?;
+        drop(pool_data);
+
+        // Validate pool authority (old format has none) and USDC mint
+        let (authority, usdc_mint) = match &pool {
+            LendingPoolVersion::Old(pool) => (None, pool.usdc_mint),
+            LendingPoolVersion::V1(pool) => (Some(pool.authority), pool.usdc_mint),
+            LendingPoolVersion::V2(pool) => (Some(pool.authority), pool.usdc_mint),
+            LendingPoolVersion::V3(pool) => (Some(pool.authority), pool.usdc_mint),
+            LendingPoolVersion::V4(pool) => (Some(pool.authority), pool.usdc_mint),
+            LendingPoolVersion::New(pool) => (Some(pool.authority), pool.usdc_mint),
+        };
+        if let Some(authority) = authority {
+            require_keys_eq!(
+                authority,
+                ctx.accounts.authority.key(),
+                LendingPoolError::Unauthorized
+            );
+        }
+
+        require!(
+            ctx.accounts.usdc_mint.key() == usdc_mint,
+            LendingPoolError::InvalidConfig
+        );
+
+        // Vault is automatically initialized by Anchor's init constraint in InitializeVault struct
+        msg!("Pool vault initialized for pool: {}", ctx.accounts.pool.key());
+
+        Ok(())
+    }
+
+    /// Get available liquidity (view function simulation)
+    pub fn get_available_liquidity(ctx: Context<GetAvailableLiquidity>) -> Result<u64> {
+        // Manually deserialize pool account to handle old and new formats
+        let pool_data = ctx.accounts.pool.try_borrow_data()?;
+        let pool = deserialize_pool(&pool_data)?.into_current()?;
+
+        // Accrual adds the same interest to liquidity and borrows, so this is exact without it
+        let available = pool.total_liquidity
+            .checked_sub(pool.total_borrowed)
+            .ok_or(LendingPoolError::InsufficientLiquidity)?;
+        Ok(available)
+    }
+
+    /// Set which pool operations are paused (emergency function)
+    /// `pause_flags` is a set of forge_core::pause PAUSE_* flags, e.g. PAUSE_WIND_DOWN for withdraw-only.
+    pub fn set_pool_status(
+        ctx: Context<SetPoolStatus>,
+        pause_flags: u8,
+    ) -> Result<()> {
+        // Manually deserialize pool account to handle old and new formats
+        // Old formats must be migrated before pause/authority updates
+        let pool_data = ctx.accounts.pool.try_borrow_data()?;
+        let mut new_pool = deserialize_pool(&pool_data)?.into_current()?;
+        drop(pool_data);
+
+        // SECURITY FIX: Explicitly verify authority matches pool authority
+        require_keys_eq!(
+            new_pool.authority,
+            ctx.accounts.authority.key(),
+            LendingPoolError::Unauthorized
+        );
+
+        // SECURITY FIX: Prevent redundant state changes
+        require!(
+            decode_pause_flags(new_pool.pause_flags) != pause_flags,
+            LendingPoolError::InvalidConfig
+        );
+
+        new_pool.pause_flags = encode_pause_flags(pause_flags);
+
+        // Serialize pool back to account data
+        let mut pool_data = ctx.accounts.pool.try_borrow_mut_data()?;
+        serialize_pool(&mut pool_data, &new_pool)?;
+
+        msg!("Pool pause flags set to: {:#010b}", pause_flags);
+        Ok(())
+    }
+
+    /// Register a borrow authority (a PDA of `program_id`) allowed to call borrow_usdc
+    /// `credit_limit` caps its outstanding debt including interest (None = no limit).
+    pub fn authorize_borrower(
+        ctx: Context<AuthorizeBorrower>,
+        program_id: Pubkey,
+        credit_limit: Option<u64>,
+    ) -> Result<()> {
+        let pool = load_pool(&ctx.accounts.pool)?;
+        require_keys_eq!(pool.authority, ctx.accounts.authority.key(), LendingPoolError::Unauthorized);
+        require!(program_id != Pubkey::default(), LendingPoolError::InvalidConfig);
+
+        let authorized_borrower = &mut ctx.accounts.authorized_borrower;
+        authorized_borrower.pool = ctx.accounts.pool.key();
+        authorized_borrower.borrow_authority = ctx.accounts.borrow_authority.key();
+        authorized_borrower.program_id = program_id;
+        authorized_borrower.credit_limit = credit_limit;
+        authorized_borrower.borrowed = 0;
+        authorized_borrower.active = true;
+        authorized_borrower.bump = ctx.bumps.authorized_borrower;
+
+        emit!(AuthorizedBorrowerUpdated {
+            pool: authorized_borrower.pool,
+            borrow_authority: authorized_borrower.borrow_authority,
+            program_id,
+            credit_limit,
+            active: true,
+        });
+        Ok(())
+    }
+
+    /// Update a borrow authority's credit limit, or deactivate it (only pool authority)
+    /// Deactivated borrowers can no longer borrow, but their debt can still be repaid.
+    pub fn update_authorized_borrower(
+        ctx: Context<UpdateAuthorizedBorrower>,
+        credit_limit: Option<u64>,
+        active: bool,
+    ) -> Result<()> {
+        let pool = load_pool(&ctx.accounts.pool)?;
+        require_keys_eq!(pool.authority, ctx.accounts.authority.key(), LendingPoolError::Unauthorized);
+
+        let authorized_borrower = &mut ctx.accounts.authorized_borrower;
+        authorized_borrower.credit_limit = credit_limit;
+        authorized_borrower.active = active;
+
+        emit!(AuthorizedBorrowerUpdated {
+            pool: authorized_borrower.pool,
+            borrow_authority: authorized_borrower.borrow_authority,
+            program_id: authorized_borrower.program_id,
+            credit_limit,
+            active,
+        });
+        Ok(())
+    }
+
+    /// Migrate a borrower account to the credit-line layout (permissionless, payer tops up rent)
+    /// Existing debt is not charged to any credit line.
+    pub fn migrate_borrower_account(ctx: Context<MigrateBorrowerAccount>) -> Result<()> {
+        let account_info = ctx.accounts.borrower_account.to_account_info();
+        require_keys_eq!(*account_info.owner, crate::ID, LendingPoolError::InvalidBorrower);
+        let borrower_account = deserialize_legacy_borrower(&account_info.try_borrow_data()?)?;
+
+        const NEW_BORROWER_ACCOUNT_LEN: usize = 8 + BorrowerAccount::LEN;
+        let required_lamports = Rent::get()?.minimum_balance(NEW_BORROWER_ACCOUNT_LEN);
+        let top_up = required_lamports.saturating_sub(account_info.lamports());
+        if top_up > 0 {
+            let cpi_accounts = anchor_lang::system_program::Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: account_info.clone(),
+            };
+            let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+            anchor_lang::system_program::transfer(cpi_ctx, top_up)?;
+        }
+        account_info.realloc(NEW_BORROWER_ACCOUNT_LEN, false)?;
+
+        let mut data = account_info.try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut data[..];
+        borrower_account.try_serialize(&mut writer)?;
+
+        msg!("Borrower account migrated: {}", account_info.key());
+        Ok(())
+    }
+
+    /// Initialize the insurance fund vault (for existing pools created before the insurance fund)
+    pub fn initialize_insurance_fund(ctx: Context<InitializeInsuranceFund>) -> Result<()> {
+        let pool = load_pool(&ctx.accounts.pool)?;
+        require_keys_eq!(pool.authority, ctx.accounts.authority.key(), LendingPoolError::Unauthorized);
+        require!(
+            ctx.accounts.usdc_mint.key() == pool.usdc_mint,
+            LendingPoolError::InvalidConfig
+        );
+
+        // Vault is automatically initialized by Anchor's init constraint in InitializeInsuranceFund struct
+        msg!("Insurance fund initialized for pool: {}", ctx.accounts.pool.key());
+        Ok(())
+    }
+
+    /// Set the share of accrued interest routed to the insurance fund (only pool authority)
+    /// Interest accrued so far is settled at the previous reserve factor.
+    pub fn set_reserve_factor(ctx: Context<SetPoolConfig>, reserve_factor_bps: u64) -> Result<()> {
+        let pool_data = ctx.accounts.pool.try_borrow_data()?;
+        let mut pool = deserialize_pool(&pool_data)?.into_current()?;
+        drop(pool_data);
+
+        // SECURITY FIX: Explicitly validate pool is a PDA with correct seeds
+        require_pool_pda(&ctx.accounts.pool.key(), &pool)?;
+        require_keys_eq!(pool.authority, ctx.accounts.authority.key(), LendingPoolError::Unauthorized);
+        require!(
+            reserve_factor_bps <= MAX_RESERVE_FACTOR_BPS,
+            LendingPoolError::InvalidConfig
+        );
+
+        accrue_pool_interest(&mut pool, Clock::get()?.unix_timestamp as u64)?;
+        pool.reserve_factor_bps = reserve_factor_bps;
+        refresh_rates(&mut pool)?;
+
+        let mut pool_data = ctx.accounts.pool.try_borrow_mut_data()?;
+        serialize_pool(&mut pool_data, &pool)?;
+
+        emit!(ReserveFactorUpdated {
+            pool: ctx.accounts.pool.key(),
+            reserve_factor_bps,
+        });
+        Ok(())
+    }
+
+    /// Set the pool's supply cap, borrow caps and utilization ceiling (only pool authority)
+    /// `None` leaves a cap unset. Caps only gate new deposits and borrows; positions already
+    /// above a lowered cap are not affected.
+    pub fn set_pool_caps(
+        ctx: Context<SetPoolConfig>,
+        supply_cap: Option<u64>,
+        borrow_cap: Option<u64>,
+        borrower_cap: Option<u64>,
+        max_utilization_bps: u64,
+    ) -> Result<()> {
+        let pool_data = ctx.accounts.pool.try_borrow_data()?;
+        let mut pool = deserialize_pool(&pool_data)?.into_current()?;
+        drop(pool_data);
+
+        // SECURITY FIX: Explicitly validate pool is a PDA with correct seeds
+        require_pool_pda(&ctx.accounts.pool.key(), &pool)?;
+        require_keys_eq!(pool.authority, ctx.accounts.authority.key(), LendingPoolError::Unauthorized);
+        require!(
+            max_utilization_bps > 0 && max_utilization_bps <= NO_UTILIZATION_CEILING_BPS,
+            LendingPoolError::InvalidConfig
+        );
+
+        pool.supply_cap = supply_cap;
+        pool.borrow_cap = borrow_cap;
+        pool.borrower_cap = borrower_cap;
+        pool.max_utilization_bps = max_utilization_bps;
+
+        let mut pool_data = ctx.accounts.pool.try_borrow_mut_data()?;
+        serialize_pool(&mut pool_data, &pool)?;
+
+        emit!(PoolCapsUpdated {
+            pool: ctx.accounts.pool.key(),
+            supply_cap,
+            borrow_cap,
+            borrower_cap,
+            max_utilization_bps,
+        });
+        Ok(())
+    }
+
+    /// Propose a new pool authority (only pool authority) - takes effect once the new authority accepts
+    /// Lets the pool authority move to the forge-core timelock or multisig signer PDA.
+    pub fn propose_authority(ctx: Context<SetPoolConfig>, new_authority: Pubkey) -> Result<()> {
+        let pool_data = ctx.accounts.pool.try_borrow_data()?;
+        let mut pool = deserialize_pool(&pool_data)?.into_current()?;
+        drop(pool_data);
+
+        // SECURITY FIX: Explicitly validate pool is a PDA with correct seeds
+        require_pool_pda(&ctx.accounts.pool.key(), &pool)?;
+        require_keys_eq!(pool.authority, ctx.accounts.authority.key(), LendingPoolError::Unauthorized);
+        require!(
+            new_authority != Pubkey::default() && new_authority != pool.authority,
+            LendingPoolError::InvalidConfig
+        );
+        pool.pending_authority = Some(new_authority);
+
+        let mut pool_data = ctx.accounts.pool.try_borrow_mut_data()?;
+        serialize_pool(&mut pool_data, &pool)?;
+
+        emit!(PoolAuthorityProposed {
+            pool: ctx.accounts.pool.key(),
+            current_authority: pool.authority,
+            proposed_authority: new_authority,
+        });
+        Ok(())
+    }
+
+    /// Accept a pending pool authority transfer - must be signed by the proposed authority
+    pub fn accept_authority(ctx: Context<AcceptPoolAuthority>) -> Result<()> {
+        let pool_data = ctx.accounts.pool.try_borrow_data()?;
+        let mut pool = deserialize_pool(&pool_data)?.into_current()?;
+        drop(pool_data);
+
+        require_pool_pda(&ctx.accounts.pool.key(), &pool)?;
+        let pending = pool.pending_authority.ok_or(LendingPoolError::NoPendingAuthority)?;
+        require_keys_eq!(pending, ctx.accounts.new_authority.key(), LendingPoolError::Unauthorized);
+
+        let previous_authority = pool.authority;
+        pool.authority = pending;
+        pool.pending_authority = None;
+
+        let mut pool_data = ctx.accounts.pool.try_borrow_mut_data()?;
+        serialize_pool(&mut pool_data, &pool)?;
+
+        emit!(PoolAuthorityTransferred {
+            pool: ctx.accounts.pool.key(),
+            previous_authority,
+            new_authority: pending,
+        });
+        Ok(())
+    }
+
+    /// Move accrued reserves from the pool vault into the insurance fund (permissionless)
+    pub fn collect_reserves(ctx: Context<CollectReserves>) -> Result<()> {
+        let pool_data = ctx.accounts.pool.try_borrow_data()?;
+        let mut pool = deserialize_pool(&pool_data)?.into_current()?;
+        drop(pool_data);
+
+        // SECURITY FIX: Explicitly validate pool is a PDA with correct seeds
+        require_pool_pda(&ctx.accounts.pool.key(), &pool)?;
+
+        accrue_pool_interest(&mut pool, Clock::get()?.unix_timestamp as u64)?;
+
+        // Reserves are interest paid on top of lender liquidity, so they are in the vault
+        // unless lent out - only sweep what the vault holds
+        let amount = pool.pending_reserves.min(ctx.accounts.pool_vault.amount);
+        require!(amount > 0, LendingPoolError::InvalidAmount);
+
+        let bump = [pool.bump];
+        let seeds = pool_signer_seeds(&ctx.accounts.pool.key(), &pool, &bump);
+        let signer = &[&seeds[..]];
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.pool_vault.to_account_info(),
+            to: ctx.accounts.insurance_fund.to_account_info(),
+            authority: ctx.accounts.pool.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+
+        pool.pending_reserves = pool.pending_reserves
+            .checked_sub(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        let mut pool_data = ctx.accounts.pool.try_borrow_mut_data()?;
+        serialize_pool(&mut pool_data, &pool)?;
+
+        emit!(ReservesCollected {
+            pool: ctx.accounts.pool.key(),
+            amount,
+            insurance_fund_balance: ctx.accounts.insurance_fund.amount
+                .checked_add(amount)
+                .ok_or(ProgramError::ArithmeticOverflow)?,
+        });
+        Ok(())
+    }
+
+    /// Record that the debt left on `position` is bad debt (only a registered borrow
+    /// authority, e.g. a crucible liquidating the position's last collateral).
+    /// cover_bad_debt only writes off recorded debt.
+    pub fn record_bad_debt(ctx: Context<RecordBadDebt>, position: Pubkey) -> Result<()> {
+        let pool_data = ctx.accounts.pool.try_borrow_data()?;
+        let pool = deserialize_pool(&pool_data)?.into_current()?;
+        drop(pool_data);
+        require_pool_pda(&ctx.accounts.pool.key(), &pool)?;
+
+        let borrower_account = load_borrower_account(
+            &ctx.accounts.borrower_account,
+            &ctx.accounts.pool.key(),
+            &ctx.accounts.borrower.key(),
+            &position,
+        )?;
+        require!(borrower_account.amount_borrowed > 0, LendingPoolError::InvalidAmount);
+        // Debt drawn on a credit line can only be recorded by that credit line's authority
+        require!(
+            borrower_account.borrow_authority == Pubkey::default()
+                || borrower_account.borrow_authority == ctx.accounts.borrow_authority.key(),
+            LendingPoolError::BorrowerNotAuthorized
+        );
+
+        let record = &mut ctx.accounts.bad_debt_record;
+        record.pool = ctx.accounts.pool.key();
+        record.borrower = ctx.accounts.borrower.key();
+        record.position = position;
+        record.payer = ctx.accounts.payer.key();
+        record.bump = ctx.bumps.bad_debt_record;
+
+        emit!(BadDebtRecorded {
+            pool: ctx.accounts.pool.key(),
+            borrower: ctx.accounts.borrower.key(),
+            position,
+            borrow_authority: ctx.accounts.borrow_authority.key(),
+        });
+        Ok(())
+    }
+
+    /// Write off a borrower's remaining debt on `position` once its collateral is gone
+    /// (only pool authority, and only debt recorded by record_bad_debt). Reserves and the
+    /// insurance fund absorb the loss first; only the remainder is socialized across
+    /// lenders by reducing total_liquidity, which lowers the lender share price.
+    pub fn cover_bad_debt(ctx: Context<CoverBadDebt>, position: Pubkey) -> Result<()> {
+        let pool_data = ctx.accounts.pool.try_borrow_data()?;
+        let mut pool = deserialize_pool(&pool_data)?.into_current()?;
+        drop(pool_data);
+
+        // SECURITY FIX: Explicitly validate pool is a PDA with correct seeds
+        require_pool_pda(&ctx.accounts.pool.key(), &pool)?;
+        require_keys_eq!(pool.authority, ctx.accounts.authority.key(), LendingPoolError::Unauthorized);
+
+        let current_timestamp = Clock::get()?.unix_timestamp as u64;
+        accrue_pool_interest(&mut pool, current_timestamp)?;
+
+        let borrower_account = &mut ctx.accounts.borrower_account;
+        require!(
+            borrower_account.borrower == ctx.accounts.borrower.key(),
+            LendingPoolError::InvalidBorrower
+        );
+        let bad_debt = debt_from_scaled(borrower_account.amount_borrowed, pool.borrow_index)?;
+        require!(bad_debt > 0, LendingPoolError::InvalidAmount);
+
+        // Clear the debt and release it from the credit line it was drawn on
+        let scaled_written_off = borrower_account.amount_borrowed;
+        borrower_account.amount_borrowed = 0;
+        borrower_account.borrow_timestamp = current_timestamp;
+        release_credit(borrower_account, ctx.accounts.authorized_borrower.as_mut(), scaled_written_off)?;
+        // Per-borrower debt rounds up, so clamp rounding dust as in repayments
+        pool.total_borrowed = pool.total_borrowed
+            .checked_sub(bad_debt.min(pool.total_borrowed))
+            .ok_or(LendingPoolError::InvalidAmount)?;
+
+        let (from_reserves, from_insurance_fund, socialized) = bad_debt_waterfall(
+            bad_debt,
+            pool.pending_reserves,
+            ctx.accounts.insurance_fund.amount,
+        );
+        pool.pending_reserves -= from_reserves;
+
+        // The insurance fund pays the pool vault for what reserves did not cover
+        if from_insurance_fund > 0 {
+            let bump = [pool.bump];
+            let seeds = pool_signer_seeds(&ctx.accounts.pool.key(), &pool, &bump);
+            let signer = &[&seeds[..]];
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.insurance_fund.to_account_info(),
+                to: ctx.accounts.pool_vault.to_account_info(),
+                authority: ctx.accounts.pool.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token::transfer(cpi_ctx, from_insurance_fund)?;
+        }
+        if from_reserves > 0 || from_insurance_fund > 0 {
+            emit!(BadDebtCovered {
+                pool: ctx.accounts.pool.key(),
+                borrower: ctx.accounts.borrower.key(),
+                position,
+                from_reserves,
+                from_insurance_fund,
+                insurance_fund_balance: ctx.accounts.insurance_fund.amount - from_insurance_fund,
+            });
+        }
+
+        // Only the loss the fund could not absorb is socialized across lenders
+        if socialized > 0 {
+            pool.total_liquidity = pool.total_liquidity.saturating_sub(socialized);
+            emit!(BadDebtSocialized {
+                pool: ctx.accounts.pool.key(),
+                borrower: ctx.accounts.borrower.key(),
+                position,
+                amount: socialized,
+                total_liquidity: pool.total_liquidity,
+            });
+        }
+        refresh_rates(&mut pool)?;
+
+        let mut pool_data = ctx.accounts.pool.try_borrow_mut_data()?;
+        serialize_pool(&mut pool_data, &pool)?;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + LendingPool::LEN,
+        seeds = [b"pool", usdc_mint.key().as_ref()],
+        bump,
+    )]
+    pub pool: Account<'info, LendingPool>,
+
+    /// Asset lent by the pool
+    pub usdc_mint: Account<'info, Mint>,
+
+    /// CHECK: Legacy USDC pool address ([b"pool"]) - read to refuse a duplicate pool for its mint
+    #[account(address = legacy_pool_address() @ LendingPoolError::InvalidConfig)]
+    pub legacy_pool: UncheckedAccount<'info>,
+
+    /// Pool vault - initialized as a token account with pool as authority
+    #[account(
+        init,
+        payer = authority,
+        token::mint = usdc_mint,
+        token::authority = pool,
+        seeds = [b"vault", pool.key().as_ref()],
+        bump,
+    )]
+    pub pool_vault: Account<'info, TokenAccount>,
+
+    /// Lender share mint - pool PDA is the mint authority
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = usdc_mint.decimals,
+        mint::authority = pool,
+        seeds = [b"share_mint", pool.key().as_ref()],
+        bump,
+    )]
+    pub share_mint: Account<'info, Mint>,
+
+    /// Insurance fund - receives the reserve factor's share of interest, pool is the authority
+    #[account(
+        init,
+        payer = authority,
+        token::mint = usdc_mint,
+        token::authority = pool,
+        seeds = [b"insurance_fund", pool.key().as_ref()],
+        bump,
+    )]
+    pub insurance_fund: Account<'info, TokenAccount>,
+
+    /// Holds the shares of the initial liquidity forever - no instruction transfers out of it
+    #[account(
+        init,
+        payer = authority,
+        token::mint = share_mint,
+        token::authority = pool,
+        seeds = [b"dead_shares", pool.key().as_ref()],
+        bump,
+    )]
+    pub dead_share_escrow: Account<'info, TokenAccount>,
+
+    /// Funds the initial liquidity
+    #[account(
+        mut,
+        constraint = authority_usdc_account.mint == usdc_mint.key() @ LendingPoolError::InvalidConfig,
+        constraint = authority_usdc_account.owner == authority.key() @ LendingPoolError::Unauthorized
+    )]
+    pub authority_usdc_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Program data account - pools can only be created by the program upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigratePool<'info> {
+    /// CHECK: Pool account - using UncheckedAccount to handle old account formats
+    #[account(mut)]
+    pub pool: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Program data account - only required to migrate old-format pools (no stored authority)
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+    )]
+    pub program_data: Option<Account<'info, ProgramData>>,
+
+    /// Borrower settlement of an old or V1 pool - required while it has outstanding debt
+    #[account(
+        mut,
+        seeds = [b"legacy_settlement", pool.key().as_ref()],
+        bump = legacy_settlement.bump,
+        close = authority,
+    )]
+    pub legacy_settlement: Option<Account<'info, LegacySettlement>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettleLegacyDebt<'info> {
+    /// CHECK: Pool account - old or V1 layout, validated and deserialized manually in the instruction
+    pub pool: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + LegacySettlement::LEN,
+        seeds = [b"legacy_settlement", pool.key().as_ref()],
+        bump,
+    )]
+    pub legacy_settlement: Account<'info, LegacySettlement>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Program data account - only required for old-format pools (no stored authority)
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+    )]
+    pub program_data: Option<Account<'info, ProgramData>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AccrueInterest<'info> {
+    /// CHECK: Pool account - validated and deserialized manually in the instruction
+    #[account(mut)]
+    pub pool: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DepositUSDC<'info> {
+    /// CHECK: Pool account - using UncheckedAccount to handle old account formats
+    /// We manually deserialize it in the instruction to handle backward compatibility
+    #[account(mut)]
+    pub pool: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub user_usdc_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", pool.key().as_ref()],
+        bump,
+    )]
+    pub pool_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"share_mint", pool.key().as_ref()],
+        bump,
+    )]
+    pub share_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_share_account.mint == share_mint.key() @ LendingPoolError::InvalidConfig,
+        constraint = user_share_account.owner == user.key() @ LendingPoolError::Unauthorized,
+    )]
+    pub user_share_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: Forge protocol PDA - entries are refused while the protocol is inactive, validated in forge_core::load_protocol
+    pub forge_protocol: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawUSDC<'info> {
+    /// CHECK: Pool account - using UncheckedAccount to handle old account formats
+    #[account(mut)]
+    pub pool: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub user_usdc_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", pool.key().as_ref()],
+        bump,
+    )]
+    pub pool_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"share_mint", pool.key().as_ref()],
+        bump,
+    )]
+    pub share_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_share_account.mint == share_mint.key() @ LendingPoolError::InvalidConfig,
+        constraint = user_share_account.owner == user.key() @ LendingPoolError::Unauthorized,
+    )]
+    pub user_share_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: Optional Forge protocol PDA - state changes are refused while the protocol is inactive
+    pub forge_protocol: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, position: Pubkey)]
+pub struct BorrowUSDC<'info> {
+    /// CHECK: Pool account - using UncheckedAccount to handle old account formats
+    #[account(mut)]
+    pub pool: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub borrower: Signer<'info>,
+
+    /// SECURITY FIX: Borrower account - auto-initializes if doesn't exist  
+    #[account(
+        init_if_needed,
+        payer = borrower,
+        space = 8 + BorrowerAccount::LEN,
+        seeds = [b"borrower", pool.key().as_ref(), borrower.key().as_ref(), position.as_ref()],
+        bump
+    )]
+    pub borrower_account: Account<'info, BorrowerAccount>,
+
+    /// Registered borrow authority - a PDA signed for by the borrowing program (e.g. a crucible)
+    pub borrow_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"authorized_borrower", pool.key().as_ref(), borrow_authority.key().as_ref()],
+        bump = authorized_borrower.bump,
+    )]
+    pub authorized_borrower: Account<'info, AuthorizedBorrower>,
+
+    // SECURITY FIX: Pin the vault so borrows can't be paid out of the insurance fund
+    #[account(
+        mut,
+        seeds = [b"vault", pool.key().as_ref()],
+        bump,
+    )]
+    pub pool_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub borrower_usdc_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Forge protocol PDA - entries are refused while the protocol is inactive, validated in forge_core::load_protocol
+    pub forge_protocol: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, position: Pubkey)]
+pub struct RepayUSDC<'info> {
+    /// CHECK: Pool account - using UncheckedAccount to handle old account formats
+    #[account(mut)]
+    pub pool: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub borrower: Signer<'info>,
+
+    #[account(
+        mut,
+        address = borrower_account_address(&pool.key(), &borrower.key(), &position) @ LendingPoolError::InvalidBorrower,
+    )]
+    pub borrower_account: Account<'info, BorrowerAccount>,
+
+    #[account(mut)]
+    pub borrower_usdc_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", pool.key().as_ref()],
+        bump,
+    )]
+    pub pool_vault: Account<'info, TokenAccount>,
+
+    /// Credit line the debt was drawn on - required unless the debt predates credit lines
+    #[account(
+        mut,
+        seeds = [b"authorized_borrower", pool.key().as_ref(), authorized_borrower.borrow_authority.as_ref()],
+        bump = authorized_borrower.bump,
+    )]
+    pub authorized_borrower: Option<Account<'info, AuthorizedBorrower>>,
+
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: Optional Forge protocol PDA - state changes are refused while the protocol is inactive
+    pub forge_protocol: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, position: Pubkey)]
+pub struct RepayUSDCOnBehalf<'info> {
+    /// CHECK: Pool account - using UncheckedAccount to handle old account formats
+    #[account(mut)]
+    pub pool: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Borrower whose debt is repaid - only used to derive borrower_account
+    pub borrower: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = borrower_account_address(&pool.key(), &borrower.key(), &position) @ LendingPoolError::InvalidBorrower,
+    )]
+    pub borrower_account: Account<'info, BorrowerAccount>,
+
+    #[account(mut)]
+    pub payer_usdc_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", pool.key().as_ref()],
+        bump,
+    )]
+    pub pool_vault: Account<'info, TokenAccount>,
+
+    /// Credit line the debt was drawn on - required unless the debt predates credit lines
+    #[account(
+        mut,
+        seeds = [b"authorized_borrower", pool.key().as_ref(), authorized_borrower.borrow_authority.as_ref()],
+        bump = authorized_borrower.bump,
+    )]
+    pub authorized_borrower: Option<Account<'info, AuthorizedBorrower>>,
+
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: Optional Forge protocol PDA - state changes are refused while the protocol is inactive
+    pub forge_protocol: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeVault<'info> {
+    /// CHECK: Pool account - using UncheckedAccount to handle old account formats
+    pub pool: UncheckedAccount<'info>,
+    
+    pub usdc_mint: Account<'info, Mint>,
+    
+    /// Pool vault - initialized as a token account with pool as authority
+    #[account(
+        init,
+        payer = authority,
+        token::mint = usdc_mint,
+        token::authority = pool,
+        seeds = [b"vault", pool.key().as_ref()],
+        bump,
+    )]
+    pub pool_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeShareMint<'info> {
+    /// CHECK: Pool account - using UncheckedAccount to handle old account formats
+    pub pool: UncheckedAccount<'info>,
+
+    pub usdc_mint: Account<'info, Mint>,
+
+    /// Lender share mint - initialized with pool as mint authority
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = usdc_mint.decimals,
+        mint::authority = pool,
+        seeds = [b"share_mint", pool.key().as_ref()],
+        bump,
+    )]
+    pub share_mint: Account<'info, Mint>,
+
+    /// Holds shares for liquidity deposited before the share mint existed until lenders claim them
+    #[account(
+        init,
+        payer = authority,
+        token::mint = share_mint,
+        token::authority = pool,
+        seeds = [b"bootstrap_shares", pool.key().as_ref()],
+        bump,
+    )]
+    pub bootstrap_share_escrow: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(lender: Pubkey)]
+pub struct RegisterLegacyDeposit<'info> {
+    /// CHECK: Pool account - validated and deserialized manually in the instruction
+    pub pool: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + LegacyDeposit::LEN,
+        seeds = [b"legacy_deposit", pool.key().as_ref(), lender.as_ref()],
+        bump
+    )]
+    pub legacy_deposit: Account<'info, LegacyDeposit>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimLegacyDeposit<'info> {
+    /// CHECK: Pool account - validated and deserialized manually in the instruction
+    pub pool: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"legacy_deposit", pool.key().as_ref(), lender.key().as_ref()],
+        bump = legacy_deposit.bump,
+        has_one = pool @ LendingPoolError::InvalidConfig,
+        has_one = lender @ LendingPoolError::Unauthorized,
+    )]
+    pub legacy_deposit: Account<'info, LegacyDeposit>,
+
+    #[account(
+        mut,
+        seeds = [b"bootstrap_shares", pool.key().as_ref()],
+        bump,
+    )]
+    pub bootstrap_share_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = lender_share_account.mint == bootstrap_share_escrow.mint @ LendingPoolError::InvalidConfig,
+        constraint = lender_share_account.owner == lender.key() @ LendingPoolError::Unauthorized,
+    )]
+    pub lender_share_account: Account<'info, TokenAccount>,
+
+    pub lender: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct GetAvailableLiquidity<'info> {
+    /// CHECK: Pool account - using UncheckedAccount to handle old account formats
+    pub pool: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPoolStatus<'info> {
+    /// CHECK: Pool account - using UncheckedAccount to handle old account formats
+    #[account(mut)]
+    pub pool: UncheckedAccount<'info>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AuthorizeBorrower<'info> {
+    /// CHECK: Pool account - validated and deserialized manually in the instruction
+    pub pool: UncheckedAccount<'info>,
+
+    /// CHECK: Borrow authority PDA being registered - only used as a seed and signer key
+    pub borrow_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuthorizedBorrower::LEN,
+        seeds = [b"authorized_borrower", pool.key().as_ref(), borrow_authority.key().as_ref()],
+        bump
+    )]
+    pub authorized_borrower: Account<'info, AuthorizedBorrower>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateAuthorizedBorrower<'info> {
+    /// CHECK: Pool account - validated and deserialized manually in the instruction
+    pub pool: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"authorized_borrower", pool.key().as_ref(), authorized_borrower.borrow_authority.as_ref()],
+        bump = authorized_borrower.bump,
+    )]
+    pub authorized_borrower: Account<'info, AuthorizedBorrower>,
+
+    pub authority: Signer<'info>,
+}
 
 #[derive(Accounts)]
-pub struct Initialize<'info> {
+pub struct MigrateBorrowerAccount<'info> {
+    /// CHECK: Borrower account in the legacy layout - validated and migrated manually
     #[account(
-        init,
-        payer = authority,
-        space = 8 + LendingPool::LEN,
-        seeds = [b"pool"],
+        mut,
+        seeds = [b"borrower", borrower.key().as_ref()],
         bump,
     )]
-    pub pool: Account<'info, LendingPool>,
+    pub borrower_account: UncheckedAccount<'info>,
+
+    /// CHECK: Borrower - only used to derive borrower_account
+    pub borrower: UncheckedAccount<'info>,
+
+    /// Pays the rent for the larger account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeInsuranceFund<'info> {
+    /// CHECK: Pool account - validated and deserialized manually in the instruction
+    pub pool: UncheckedAccount<'info>,
 
     pub usdc_mint: Account<'info, Mint>,
 
-    /// Pool vault - initialized as a token account with pool as authority
+    /// Insurance fund - initialized as a token account with pool as authority
     #[account(
         init,
         payer = authority,
         token::mint = usdc_mint,
         token::authority = pool,
-        seeds = [b"vault", pool.key().as_ref()],
+        seeds = [b"insurance_fund", pool.key().as_ref()],
         bump,
     )]
-    pub pool_vault: Account<'info, TokenAccount>,
+    pub insurance_fund: Account<'info, TokenAccount>,
 
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -782,17 +2799,26 @@ pub struct Initialize<'info> {
 }
 
 #[derive(Accounts)]
-pub struct DepositUSDC<'info> {
-    /// CHECK: Pool account - using UncheckedAccount to handle old account formats
-    /// We manually deserialize it in the instruction to handle backward compatibility
+pub struct SetPoolConfig<'info> {
+    /// CHECK: Pool account - validated and deserialized manually in the instruction
     #[account(mut)]
     pub pool: UncheckedAccount<'info>,
+    pub authority: Signer<'info>,
+}
 
+#[derive(Accounts)]
+pub struct AcceptPoolAuthority<'info> {
+    /// CHECK: Pool account - validated and deserialized manually in the instruction
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub pool: UncheckedAccount<'info>,
+    pub new_authority: Signer<'info>,
+}
 
+#[derive(Accounts)]
+pub struct CollectReserves<'info> {
+    /// CHECK: Pool account - validated and deserialized manually in the instruction
     #[account(mut)]
-    pub user_usdc_account: Account<'info, TokenAccount>,
+    pub pool: UncheckedAccount<'info>,
 
     #[account(
         mut,
@@ -801,57 +2827,34 @@ pub struct DepositUSDC<'info> {
     )]
     pub pool_vault: Account<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
-}
-
-#[derive(Accounts)]
-pub struct BorrowUSDC<'info> {
-    /// CHECK: Pool account - using UncheckedAccount to handle old account formats
-    #[account(mut)]
-    pub pool: UncheckedAccount<'info>,
-
-    #[account(mut)]
-    pub borrower: Signer<'info>,
-
-    /// SECURITY FIX: Borrower account - auto-initializes if doesn't exist  
     #[account(
-        init_if_needed,
-        payer = borrower,
-        space = 8 + BorrowerAccount::LEN,
-        seeds = [b"borrower", borrower.key().as_ref()],
-        bump
+        mut,
+        seeds = [b"insurance_fund", pool.key().as_ref()],
+        bump,
     )]
-    pub borrower_account: Account<'info, BorrowerAccount>,
-
-    #[account(mut)]
-    pub pool_vault: Account<'info, TokenAccount>,
-
-    #[account(mut)]
-    pub borrower_usdc_account: Account<'info, TokenAccount>,
+    pub insurance_fund: Account<'info, TokenAccount>,
 
     pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct RepayUSDC<'info> {
-    /// CHECK: Pool account - using UncheckedAccount to handle old account formats
+#[instruction(position: Pubkey)]
+pub struct CoverBadDebt<'info> {
+    /// CHECK: Pool account - validated and deserialized manually in the instruction
     #[account(mut)]
     pub pool: UncheckedAccount<'info>,
 
-    #[account(mut)]
-    pub borrower: Signer<'info>,
+    pub authority: Signer<'info>,
+
+    /// CHECK: Borrower whose debt is written off - only used to derive borrower_account
+    pub borrower: UncheckedAccount<'info>,
 
     #[account(
         mut,
-        seeds = [b"borrower", borrower.key().as_ref()],
-        bump,
+        address = borrower_account_address(&pool.key(), &borrower.key(), &position) @ LendingPoolError::InvalidBorrower,
     )]
     pub borrower_account: Account<'info, BorrowerAccount>,
 
-    #[account(mut)]
-    pub borrower_usdc_account: Account<'info, TokenAccount>,
-
     #[account(
         mut,
         seeds = [b"vault", pool.key().as_ref()],
@@ -859,96 +2862,357 @@ pub struct RepayUSDC<'info> {
     )]
     pub pool_vault: Account<'info, TokenAccount>,
 
+    #[account(
+        mut,
+        seeds = [b"insurance_fund", pool.key().as_ref()],
+        bump,
+    )]
+    pub insurance_fund: Account<'info, TokenAccount>,
+
+    /// Credit line the debt was drawn on - required unless the debt predates credit lines
+    #[account(
+        mut,
+        seeds = [b"authorized_borrower", pool.key().as_ref(), authorized_borrower.borrow_authority.as_ref()],
+        bump = authorized_borrower.bump,
+    )]
+    pub authorized_borrower: Option<Account<'info, AuthorizedBorrower>>,
+
     pub token_program: Program<'info, Token>,
+
+    /// SECURITY FIX: Only debt the liquidation path recorded as bad can be written off
+    #[account(
+        mut,
+        seeds = [b"bad_debt", borrower_account.key().as_ref()],
+        bump = bad_debt_record.bump,
+        close = rent_receiver,
+    )]
+    pub bad_debt_record: Account<'info, BadDebtRecord>,
+
+    /// CHECK: Receives the bad debt record's rent - must be the account that paid it
+    #[account(mut, address = bad_debt_record.payer @ LendingPoolError::InvalidConfig)]
+    pub rent_receiver: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
-pub struct InitializeVault<'info> {
-    /// CHECK: Pool account - using UncheckedAccount to handle old account formats
+#[instruction(position: Pubkey)]
+pub struct RecordBadDebt<'info> {
+    /// CHECK: Pool account - validated and deserialized manually in the instruction
     pub pool: UncheckedAccount<'info>,
-    
-    pub usdc_mint: Account<'info, Mint>,
-    
-    /// Pool vault - initialized as a token account with pool as authority
+
+    /// CHECK: Borrower whose debt is recorded - only used to derive borrower_account
+    pub borrower: UncheckedAccount<'info>,
+
+    /// CHECK: Borrower account holding the debt - validated by load_borrower_account
+    pub borrower_account: UncheckedAccount<'info>,
+
+    /// Registered borrow authority - a PDA signed for by the borrowing program (e.g. a crucible)
+    pub borrow_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"authorized_borrower", pool.key().as_ref(), borrow_authority.key().as_ref()],
+        bump = authorized_borrower.bump,
+    )]
+    pub authorized_borrower: Account<'info, AuthorizedBorrower>,
+
     #[account(
         init,
-        payer = authority,
-        token::mint = usdc_mint,
-        token::authority = pool,
-        seeds = [b"vault", pool.key().as_ref()],
+        payer = payer,
+        space = 8 + BadDebtRecord::LEN,
+        seeds = [b"bad_debt", borrower_account.key().as_ref()],
         bump,
     )]
-    pub pool_vault: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-pub struct GetAvailableLiquidity<'info> {
-    /// CHECK: Pool account - using UncheckedAccount to handle old account formats
-    pub pool: UncheckedAccount<'info>,
-}
+    pub bad_debt_record: Account<'info, BadDebtRecord>,
 
-#[derive(Accounts)]
-pub struct SetPoolStatus<'info> {
-    /// CHECK: Pool account - using UncheckedAccount to handle old account formats
     #[account(mut)]
-    pub pool: UncheckedAccount<'info>,
-    pub authority: Signer<'info>,
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
+/// Lending pool for one asset ([b"pool", mint]; the original USDC pool lives at [b"pool"])
 #[account]
 pub struct LendingPool {
     pub authority: Pubkey,
-    pub usdc_mint: Pubkey,
-    pub total_liquidity: u64,
-    pub total_borrowed: u64,
-    pub borrow_rate: u64, // 10 = 10% APY (scaled by 100)
-    pub lender_rate: u64, // 5 = 5% APY (scaled by 100)
-    pub paused: bool,
+    pub usdc_mint: Pubkey, // Asset lent by the pool (named for the original USDC pool)
+    pub total_liquidity: u64, // Deposits plus accrued interest owed to lenders
+    pub total_borrowed: u64, // Outstanding debt including accrued interest
+    pub borrow_rate: u64, // Current borrow APY from the interest model, 10 = 10% APY (scaled by 100)
+    pub lender_rate: u64, // Current supply APY, 5 = 5% APY (scaled by 100)
+    pub pause_flags: u8, // forge_core::pause PAUSE_* flags, stored with encode_pause_flags
     pub bump: u8,
+    pub interest_model: InterestRateModelConfig,
+    pub borrow_index: u128, // Global borrow index, RATE_SCALE = 1.0
+    pub last_accrued_ts: u64,
+    pub reserve_factor_bps: u64, // Share of accrued interest routed to the insurance fund
+    pub pending_reserves: u64, // Reserves accrued but not yet moved to the insurance fund
+    pub max_utilization_bps: u64, // Borrows may not push utilization above this, 10_000 = no ceiling
+    pub supply_cap: Option<u64>, // Max total liquidity accepted from deposits
+    pub borrow_cap: Option<u64>, // Max total borrowed across all borrowers
+    pub borrower_cap: Option<u64>, // Max debt of a single borrow authority across all its positions
+    pub pending_authority: Option<Pubkey>, // Proposed authority awaiting accept_authority
 }
 
 impl LendingPool {
-    pub const LEN: usize = 32 + 32 + 8 + 8 + 8 + 8 + 1 + 1; // authority (32) + usdc_mint (32) + total_liquidity (8) + total_borrowed (8) + borrow_rate (8) + lender_rate (8) + paused (1) + bump (1)
+    pub const LEN: usize = 32 + // authority
+        32 + // usdc_mint
+        8 +  // total_liquidity
+        8 +  // total_borrowed
+        8 +  // borrow_rate
+        8 +  // lender_rate
+        1 +  // pause_flags
+        1 +  // bump
+        InterestRateModelConfig::SIZE +
+        16 + // borrow_index
+        8 +  // last_accrued_ts
+        8 +  // reserve_factor_bps
+        8 +  // pending_reserves
+        8 +  // max_utilization_bps
+        9 +  // supply_cap
+        9 +  // borrow_cap
+        9 +  // borrower_cap
+        1 + 32; // pending_authority
+}
+
+/// Kinked utilization model, matching the `lending` program's market config
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct InterestRateModelConfig {
+    pub base_rate_bps: u64,
+    pub slope1_bps: u64,
+    pub slope2_bps: u64,
+    pub kink_bps: u64,
 }
 
+impl InterestRateModelConfig {
+    pub const SIZE: usize = 8 * 4;
+}
+
+/// Debt of one borrower to one pool on one position ([b"borrower", pool, borrower, position]), or
+/// the legacy per-borrower account ([b"borrower", borrower]) holding legacy USDC pool debt from
+/// before per-position tracking
 #[account]
 pub struct BorrowerAccount {
     pub borrower: Pubkey,
-    pub amount_borrowed: u64,
-    pub borrow_timestamp: u64, // Unix timestamp when the loan was created (for interest calculation)
+    pub amount_borrowed: u64, // Debt scaled by the pool borrow index (debt = amount_borrowed × borrow_index / RATE_SCALE)
+    pub borrow_timestamp: u64, // Unix timestamp of the last borrow or repayment
+    pub borrow_authority: Pubkey, // Credit line the debt was drawn on (default = debt predates credit lines)
 }
 
 impl BorrowerAccount {
-    pub const LEN: usize = 32 + 8 + 8; // borrower (32) + amount_borrowed (8) + borrow_timestamp (8)
+    pub const LEN: usize = 32 + 8 + 8 + 32; // borrower (32) + amount_borrowed (8) + borrow_timestamp (8) + borrow_authority (32)
+}
+
+/// Registry entry for a program allowed to borrow from the pool
+/// ([b"authorized_borrower", pool, borrow_authority]). The borrow authority is a PDA of
+/// `program_id` (e.g. a crucible PDA) that must sign every borrow_usdc.
+#[account]
+pub struct AuthorizedBorrower {
+    pub pool: Pubkey,
+    pub borrow_authority: Pubkey,
+    pub program_id: Pubkey, // Program that signs for borrow_authority
+    pub credit_limit: Option<u64>, // Max outstanding debt including interest (None = no limit)
+    pub borrowed: u64, // Outstanding debt scaled by the pool borrow index
+    pub active: bool, // Inactive borrowers can only repay
+    pub bump: u8,
+}
+
+impl AuthorizedBorrower {
+    pub const LEN: usize = 32 + // pool
+        32 + // borrow_authority
+        32 + // program_id
+        1 + 8 + // credit_limit
+        8 +  // borrowed
+        1 +  // active
+        1;   // bump
 }
 
 #[event]
 pub struct USDCDeposited {
+    pub pool: Pubkey,
+    pub lender: Pubkey,
+    pub amount: u64,
+    pub shares_minted: u64,
+    pub total_liquidity: u64,
+}
+
+/// Progress of settling an old or V1 pool's borrowers ([b"legacy_settlement", pool]),
+/// consumed by migrate_pool
+#[account]
+pub struct LegacySettlement {
+    pub pool: Pubkey,
+    pub settled_at: u64, // Flat-model interest is settled up to this timestamp
+    pub principal_settled: u64, // Principal of the borrower accounts settled so far
+    pub interest_settled: u64,
+    pub bump: u8,
+}
+
+impl LegacySettlement {
+    pub const LEN: usize = 32 + // pool
+        8 +  // settled_at
+        8 +  // principal_settled
+        8 +  // interest_settled
+        1;   // bump
+}
+
+#[event]
+pub struct LegacyDebtSettled {
+    pub pool: Pubkey,
+    pub principal_settled: u64,
+    pub interest_settled: u64,
+    pub total_borrowed: u64,
+}
+
+/// Deposit made before the share mint existed ([b"legacy_deposit", pool, lender]),
+/// registered by the pool authority and claimed from the bootstrap share escrow
+#[account]
+pub struct LegacyDeposit {
+    pub pool: Pubkey,
+    pub lender: Pubkey,
+    pub shares: u64, // Bootstrap shares owed to the lender
+    pub claimed: bool,
+    pub bump: u8,
+}
+
+impl LegacyDeposit {
+    pub const LEN: usize = 32 + // pool
+        32 + // lender
+        8 +  // shares
+        1 +  // claimed
+        1;   // bump
+}
+
+#[event]
+pub struct LegacyDepositRegistered {
+    pub pool: Pubkey,
+    pub lender: Pubkey,
+    pub shares: u64,
+}
+
+#[event]
+pub struct LegacyDepositClaimed {
+    pub pool: Pubkey,
+    pub lender: Pubkey,
+    pub shares: u64,
+}
+
+#[event]
+pub struct USDCWithdrawn {
+    pub pool: Pubkey,
     pub lender: Pubkey,
     pub amount: u64,
+    pub shares_burned: u64,
     pub total_liquidity: u64,
 }
 
 #[event]
 pub struct USDCBorrowed {
+    pub pool: Pubkey,
     pub borrower: Pubkey,
+    pub position: Pubkey,
+    pub borrow_authority: Pubkey,
     pub amount: u64,
     pub total_borrowed: u64,
 }
 
 #[event]
 pub struct USDCRepaid {
+    pub pool: Pubkey,
     pub borrower: Pubkey,
+    pub position: Pubkey,
     pub amount: u64,
     pub remaining_debt: u64,
 }
 
+#[event]
+pub struct AuthorizedBorrowerUpdated {
+    pub pool: Pubkey,
+    pub borrow_authority: Pubkey,
+    pub program_id: Pubkey,
+    pub credit_limit: Option<u64>,
+    pub active: bool,
+}
+
+#[event]
+pub struct PoolAuthorityProposed {
+    pub pool: Pubkey,
+    pub current_authority: Pubkey,
+    pub proposed_authority: Pubkey,
+}
+
+#[event]
+pub struct PoolAuthorityTransferred {
+    pub pool: Pubkey,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct ReserveFactorUpdated {
+    pub pool: Pubkey,
+    pub reserve_factor_bps: u64,
+}
+
+#[event]
+pub struct PoolCapsUpdated {
+    pub pool: Pubkey,
+    pub supply_cap: Option<u64>,
+    pub borrow_cap: Option<u64>,
+    pub borrower_cap: Option<u64>,
+    pub max_utilization_bps: u64,
+}
+
+#[event]
+pub struct ReservesCollected {
+    pub pool: Pubkey,
+    pub amount: u64,
+    pub insurance_fund_balance: u64,
+}
+
+/// Debt left on a position after its collateral was liquidated ([b"bad_debt", borrower_account]),
+/// recorded by the borrow authority and closed by cover_bad_debt
+#[account]
+pub struct BadDebtRecord {
+    pub pool: Pubkey,
+    pub borrower: Pubkey,
+    pub position: Pubkey,
+    pub payer: Pubkey, // Paid the record's rent, refunded when the debt is covered
+    pub bump: u8,
+}
+
+impl BadDebtRecord {
+    pub const LEN: usize = 32 + // pool
+        32 + // borrower
+        32 + // position
+        32 + // payer
+        1;   // bump
+}
+
+#[event]
+pub struct BadDebtRecorded {
+    pub pool: Pubkey,
+    pub borrower: Pubkey,
+    pub position: Pubkey,
+    pub borrow_authority: Pubkey,
+}
+
+#[event]
+pub struct BadDebtCovered {
+    pub pool: Pubkey,
+    pub borrower: Pubkey,
+    pub position: Pubkey,
+    pub from_reserves: u64,
+    pub from_insurance_fund: u64,
+    pub insurance_fund_balance: u64,
+}
+
+#[event]
+pub struct BadDebtSocialized {
+    pub pool: Pubkey,
+    pub borrower: Pubkey,
+    pub position: Pubkey,
+    pub amount: u64,
+    pub total_liquidity: u64,
+}
+
 #[error_code]
 pub enum LendingPoolError {
     #[msg("Insufficient liquidity in pool")]
@@ -965,5 +3229,276 @@ pub enum LendingPoolError {
     Unauthorized,
     #[msg("Invalid configuration")]
     InvalidConfig,
+    #[msg("Insufficient lender shares")]
+    InsufficientShares,
+    #[msg("Pool account must be migrated to the current layout")]
+    PoolMigrationRequired,
+    #[msg("Forge protocol is inactive")]
+    ProtocolInactive,
+    #[msg("Borrow authority is not authorized for this pool or borrower account")]
+    BorrowerNotAuthorized,
+    #[msg("Borrow exceeds the borrower's credit limit")]
+    CreditLimitExceeded,
+    #[msg("Deposit exceeds the pool supply cap")]
+    SupplyCapExceeded,
+    #[msg("Borrow exceeds the pool borrow cap")]
+    BorrowCapExceeded,
+    #[msg("Borrow exceeds the per-borrower cap")]
+    BorrowerCapExceeded,
+    #[msg("Borrow exceeds the pool utilization ceiling")]
+    UtilizationCeilingExceeded,
+    #[msg("No pending authority transfer")]
+    NoPendingAuthority,
+    #[msg("Legacy borrower accounts do not cover the pool's outstanding debt")]
+    UnsettledLegacyDebt,
+    #[msg("Legacy deposit already claimed")]
+    LegacyDepositAlreadyClaimed,
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const YEAR: u64 = SECONDS_PER_YEAR as u64;
+
+    fn test_pool(total_liquidity: u64, total_borrowed: u64) -> LendingPool {
+        let mut pool = LendingPool {
+            authority: Pubkey::new_unique(),
+            usdc_mint: Pubkey::new_unique(),
+            total_liquidity,
+            total_borrowed,
+            borrow_rate: 0,
+            lender_rate: 0,
+            pause_flags: PAUSE_NONE,
+            bump: 255,
+            interest_model: InterestRateModelConfig {
+                base_rate_bps: 1_000,
+                slope1_bps: 0,
+                slope2_bps: 0,
+                kink_bps: 8_000,
+            },
+            borrow_index: RATE_SCALE,
+            last_accrued_ts: 0,
+            reserve_factor_bps: 0,
+            pending_reserves: 0,
+            max_utilization_bps: NO_UTILIZATION_CEILING_BPS,
+            supply_cap: None,
+            borrow_cap: None,
+            borrower_cap: None,
+            pending_authority: None,
+        };
+        refresh_rates(&mut pool).unwrap();
+        pool
+    }
+
+    #[test]
+    fn borrow_index_accrues_simple_interest_to_lenders() {
+        // Flat 10% base rate for one year on 500_000 borrowed
+        let mut pool = test_pool(1_000_000, 500_000);
+        accrue_pool_interest(&mut pool, YEAR).unwrap();
+        assert_eq!(pool.borrow_index, RATE_SCALE * 11 / 10);
+        assert_eq!(pool.total_borrowed, 550_000);
+        assert_eq!(pool.total_liquidity, 1_050_000);
+        assert_eq!(pool.last_accrued_ts, YEAR);
+
+        // Accruing again at the same timestamp is a no-op
+        accrue_pool_interest(&mut pool, YEAR).unwrap();
+        assert_eq!(pool.total_borrowed, 550_000);
+    }
+
+    #[test]
+    fn scaled_debt_round_trips_in_favour_of_the_pool() {
+        let index = RATE_SCALE * 11 / 10;
+        let scaled = scaled_from_amount(1_000, index, true).unwrap();
+        assert!(debt_from_scaled(scaled, index).unwrap() >= 1_000);
+        let scaled = scaled_from_amount(1_000, index, false).unwrap();
+        assert!(debt_from_scaled(scaled, index).unwrap() <= 1_000);
+        assert_eq!(debt_from_scaled(1, RATE_SCALE + 1).unwrap(), 2);
+    }
+
+    #[test]
+    fn borrower_debt_matches_the_next_accrual() {
+        let pool = test_pool(1_000_000, 500_000);
+        let borrower_account = BorrowerAccount {
+            borrower: Pubkey::new_unique(),
+            amount_borrowed: 500_000,
+            borrow_timestamp: 0,
+            borrow_authority: Pubkey::default(),
+        };
+        assert_eq!(borrower_debt(&pool, &borrower_account, YEAR).unwrap(), 550_000);
+    }
+
+    #[test]
+    fn borrower_debt_reads_the_live_rate_without_an_accrual() {
+        // 90% utilization, past a 80% kink with a steep second slope
+        let mut pool = test_pool(1_000_000, 900_000);
+        pool.interest_model.slope2_bps = 50_000;
+        refresh_rates(&mut pool).unwrap();
+        let borrower_account = BorrowerAccount {
+            borrower: Pubkey::new_unique(),
+            amount_borrowed: 900_000,
+            borrow_timestamp: 0,
+            borrow_authority: Pubkey::default(),
+        };
+
+        // 10% base + 10% over the kink at 500% = 60% APR
+        let debt = borrower_debt(&pool, &borrower_account, YEAR).unwrap();
+        assert_eq!(debt, 1_440_000);
+        // The stored pool is untouched, and a later accrual charges the same debt
+        assert_eq!(pool.borrow_index, RATE_SCALE);
+        accrue_pool_interest(&mut pool, YEAR).unwrap();
+        assert_eq!(debt_from_scaled(900_000, pool.borrow_index).unwrap(), debt);
+    }
+
+    #[test]
+    fn credit_line_draws_stay_within_the_limit_including_interest() {
+        let mut authorized_borrower = AuthorizedBorrower {
+            pool: Pubkey::new_unique(),
+            borrow_authority: Pubkey::new_unique(),
+            program_id: Pubkey::new_unique(),
+            credit_limit: Some(1_100),
+            borrowed: 0,
+            active: true,
+            bump: 255,
+        };
+        let index = RATE_SCALE * 11 / 10;
+        draw_credit(&mut authorized_borrower, 900, index, None).unwrap();
+        assert_eq!(authorized_borrower.borrowed, 900);
+        // 1_001 scaled is 1_102 of debt at a 1.1 index
+        assert!(draw_credit(&mut authorized_borrower, 101, index, None).is_err());
+        draw_credit(&mut authorized_borrower, 100, index, None).unwrap();
+
+        authorized_borrower.credit_limit = None;
+        draw_credit(&mut authorized_borrower, 1_000_000, index, None).unwrap();
+        authorized_borrower.active = false;
+        assert!(draw_credit(&mut authorized_borrower, 1, index, None).is_err());
+    }
+
+    #[test]
+    fn borrower_cap_applies_across_positions() {
+        let mut authorized_borrower = AuthorizedBorrower {
+            pool: Pubkey::new_unique(),
+            borrow_authority: Pubkey::new_unique(),
+            program_id: Pubkey::new_unique(),
+            credit_limit: None,
+            borrowed: 0,
+            active: true,
+            bump: 255,
+        };
+        // Two positions of 600 each stay under a 1_000 cap individually, not together
+        draw_credit(&mut authorized_borrower, 600, RATE_SCALE, Some(1_000)).unwrap();
+        assert_eq!(
+            draw_credit(&mut authorized_borrower, 600, RATE_SCALE, Some(1_000)).unwrap_err(),
+            LendingPoolError::BorrowerCapExceeded.into()
+        );
+        draw_credit(&mut authorized_borrower, 400, RATE_SCALE, Some(1_000)).unwrap();
+        assert_eq!(authorized_borrower.borrowed, 1_000);
+    }
+
+    #[test]
+    fn each_position_gets_its_own_borrower_account() {
+        let pool = pool_address(&Pubkey::new_unique());
+        let borrower = Pubkey::new_unique();
+        let (position_a, position_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        assert_ne!(
+            borrower_account_address(&pool, &borrower, &position_a),
+            borrower_account_address(&pool, &borrower, &position_b)
+        );
+        assert_ne!(
+            borrower_account_address(&pool, &borrower, &position_a),
+            borrower_account_address(&legacy_pool_address(), &borrower, &position_a)
+        );
+
+        // Only the legacy USDC pool maps the default position to the per-borrower account
+        let legacy = Pubkey::find_program_address(&[b"borrower", borrower.as_ref()], &crate::ID).0;
+        assert_eq!(borrower_account_address(&legacy_pool_address(), &borrower, &Pubkey::default()), legacy);
+        assert_ne!(borrower_account_address(&pool, &borrower, &Pubkey::default()), legacy);
+    }
+
+    #[test]
+    fn legacy_borrower_accounts_read_without_a_credit_line() {
+        let borrower = Pubkey::new_unique();
+        let mut data = BorrowerAccount::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(borrower.as_ref());
+        data.extend_from_slice(&500u64.to_le_bytes());
+        data.extend_from_slice(&42u64.to_le_bytes());
+        let borrower_account = deserialize_legacy_borrower(&data).unwrap();
+        assert_eq!(borrower_account.borrower, borrower);
+        assert_eq!(borrower_account.amount_borrowed, 500);
+        assert_eq!(borrower_account.borrow_timestamp, 42);
+        assert_eq!(borrower_account.borrow_authority, Pubkey::default());
+
+        data[0] ^= 1;
+        assert!(deserialize_legacy_borrower(&data).is_err());
+    }
+
+    #[test]
+    fn reserve_factor_routes_its_share_of_interest_to_reserves() {
+        let mut pool = test_pool(1_000_000, 500_000);
+        pool.reserve_factor_bps = 2_000;
+        accrue_pool_interest(&mut pool, YEAR).unwrap();
+        // 50_000 of interest: 10_000 to reserves, 40_000 to lenders
+        assert_eq!(pool.total_borrowed, 550_000);
+        assert_eq!(pool.pending_reserves, 10_000);
+        assert_eq!(pool.total_liquidity, 1_040_000);
+    }
+
+    #[test]
+    fn bad_debt_is_absorbed_by_reserves_then_the_fund_then_lenders() {
+        assert_eq!(bad_debt_waterfall(100, 150, 1_000), (100, 0, 0));
+        assert_eq!(bad_debt_waterfall(100, 30, 50), (30, 50, 20));
+        assert_eq!(bad_debt_waterfall(100, 0, 0), (0, 0, 100));
+    }
+
+    #[test]
+    fn borrows_respect_caps_and_the_utilization_ceiling() {
+        let mut pool = test_pool(100_000_000, 0);
+        pool.borrow_cap = Some(50_000_000);
+        check_pool_borrow_limits(&pool, 50_000_000).unwrap();
+        assert_eq!(
+            check_pool_borrow_limits(&pool, 50_000_001).unwrap_err(),
+            LendingPoolError::BorrowCapExceeded.into()
+        );
+
+        pool.borrow_cap = None;
+        pool.max_utilization_bps = 8_000;
+        check_pool_borrow_limits(&pool, 80_000_000).unwrap();
+        assert_eq!(
+            check_pool_borrow_limits(&pool, 80_000_001).unwrap_err(),
+            LendingPoolError::UtilizationCeilingExceeded.into()
+        );
+
+        // The minimum liquidity reserve applies even without a ceiling
+        pool.max_utilization_bps = NO_UTILIZATION_CEILING_BPS;
+        assert_eq!(
+            check_pool_borrow_limits(&pool, 100_000_000 - MIN_LIQUIDITY_RESERVE + 1).unwrap_err(),
+            LendingPoolError::InsufficientLiquidity.into()
+        );
+        assert!(check_pool_borrow_limits(&pool, 0).is_err());
+    }
+
+    #[test]
+    fn shares_price_deposits_and_withdrawals_against_liquidity() {
+        // Empty pool prices 1:1
+        assert_eq!(shares_for_deposit(1_000, 0, 0).unwrap(), 1_000);
+        // Liquidity without shares, or shares without liquidity, is never priced 1:1
+        assert!(shares_for_deposit(1_000, 5_000, 0).is_err());
+        assert!(shares_for_deposit(1_000, 0, 5_000).is_err());
+        // Liquidity grew 10% from interest: a new deposit gets fewer shares
+        assert_eq!(shares_for_deposit(1_100, 1_100_000, 1_000_000).unwrap(), 1_000);
+        assert_eq!(usdc_for_shares(1_000, 1_100_000, 1_000_000).unwrap(), 1_100);
+        // Rounding never pays out more than was deposited
+        let shares = shares_for_deposit(999, 1_100_000, 1_000_000).unwrap();
+        assert!(usdc_for_shares(shares, 1_100_999, 1_000_000 + shares).unwrap() <= 999);
+        assert!(usdc_for_shares(1, 1_000, 0).is_err());
+    }
+
+    #[test]
+    fn flat_interest_matches_the_legacy_model() {
+        // borrow_rate 10 = 10% APY
+        assert_eq!(flat_interest(1_000_000, 10, 0, YEAR).unwrap(), 100_000);
+        assert_eq!(flat_interest(1_000_000, 10, YEAR, YEAR / 2).unwrap(), 0);
+        assert_eq!(flat_interest(0, 10, 0, YEAR).unwrap(), 0);
+    }
+}
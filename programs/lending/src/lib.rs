@@ -1,12 +1,15 @@
 // Summary: Anchor program implementing isolated lending markets with supply, borrow,
 // repay, withdraw and interest accrual. Uses u128 fixed-point RATE_SCALE = 1e9
 // to match existing crucible cToken rate scale. Includes pause and admin hooks.
+// Borrowing is backed by per-user collateral deposits priced with Pyth, and
+// undercollateralized borrowers can be liquidated at the market's liquidation threshold.
 //
 // NOTE: For crucibles leverage, only USDC lending markets are used.
 // This allows crucible positions to borrow USDC for leveraged LP positions.
 
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer, MintTo, Burn};
+use forge_core::CrucibleSupplySnapshot;
 
 pub mod state;
 use state::*;
@@ -14,6 +17,176 @@ use state::*;
 declare_id!("5SXKQRhg6eEXKqqBUnCEVTToVK1YbvXKbE67FCnKzj8c");
 
 pub const RATE_SCALE: u128 = 1_000_000_000u128; // 1e9 fixed point for rates
+const CRUCIBLE_RATE_SCALE: u128 = 1_000_000; // Crucible exchange rates are scaled by 1e6
+const MAX_PRICE_AGE_SECS: u64 = 60; // Reject Pyth prices older than this
+pub const MAX_LIQUIDATION_BONUS_BPS: u64 = 2_000; // 20% max liquidation bonus
+
+// Forge crucibles program - owner of crucible accounts backing cToken collateral
+// Program ID: B9qek9NaR3xmBro8pdxixaA2SHzDUExB5KaBt9Kb4fry
+pub const FORGE_CRUCIBLES_PROGRAM_ID: Pubkey = Pubkey::new_from_array([
+    150, 216, 134, 123, 123, 148, 18, 249, 18, 209, 13, 216, 78, 46, 231, 165,
+    56, 71, 113, 197, 131, 86, 95, 20, 197, 57, 5, 164, 68, 157, 196, 2
+]);
+const CRUCIBLE_DISCRIMINATOR: [u8; 8] = [47, 127, 119, 76, 137, 77, 50, 185]; // sha256("account:Crucible")[..8]
+const LEGACY_CRUCIBLE_LEN: usize = 244; // Pre-LP token crucible layout, lacks lp_token_mint
+
+/// Leading fields of a forge-crucibles crucible, read without depending on that program
+/// Legacy (pre-LP token) crucibles use a different layout and must be migrated first.
+pub fn load_crucible(source: &AccountInfo) -> Result<CrucibleSupplySnapshot> {
+    require_keys_eq!(*source.owner, FORGE_CRUCIBLES_PROGRAM_ID, LendingError::InvalidCollateral);
+    let data = source.try_borrow_data()?;
+    require!(
+        data.len() > LEGACY_CRUCIBLE_LEN && data[..8] == CRUCIBLE_DISCRIMINATOR,
+        LendingError::InvalidCollateral
+    );
+    Ok(CrucibleSupplySnapshot::deserialize(&mut &data[8..])?)
+}
+
+/// Current debt of a borrower including accrued interest: principal × (current_index / borrow_index)
+fn borrower_debt(borrower_account: &BorrowerAccount, market: &Market) -> Result<u128> {
+    Ok(borrower_account.principal
+        .checked_mul(market.accumulated_index)
+        .and_then(|v| v.checked_div(borrower_account.borrow_index.max(1))) // Prevent division by zero
+        .ok_or(LendingError::InvalidAmount)?)
+}
+
+/// Debt for a borrower PDA that may not exist yet (user never borrowed)
+fn load_borrower_debt(borrower_info: &AccountInfo, market: &Market) -> Result<u128> {
+    if borrower_info.data_is_empty() {
+        return Ok(0);
+    }
+    require_keys_eq!(*borrower_info.owner, crate::ID, LendingError::InvalidCollateral);
+    let data = borrower_info.try_borrow_data()?;
+    let borrower_account = BorrowerAccount::try_deserialize(&mut &data[..])?;
+    borrower_debt(&borrower_account, market)
+}
+
+/// Reduce a borrower's principal for a repayment of `amount` against `total_owed`
+fn apply_repayment(market: &mut Market, borrower_account: &mut BorrowerAccount, amount: u64, total_owed: u64) -> Result<()> {
+    // SECURITY FIX: Calculate principal repaid based on repayment amount
+    // For partial repayments, calculate the principal portion
+    // principal_repaid = amount × (borrow_index / current_index)
+    let principal_repaid_u128 = if amount >= total_owed {
+        // Full repayment - repay all principal
+        borrower_account.principal
+    } else {
+        // Partial repayment - calculate principal portion
+        // principal_repaid = amount × (borrow_index / accumulated_index)
+        // SECURITY FIX: Return error on calculation failure instead of silently defaulting to 0
+        (amount as u128)
+            .checked_mul(borrower_account.borrow_index)
+            .and_then(|v| v.checked_div(market.accumulated_index.max(1)))
+            .ok_or(LendingError::InvalidAmount)?
+    };
+
+    // SECURITY FIX: Validate amounts are sufficient before subtraction to detect accounting errors
+    require!(
+        borrower_account.principal >= principal_repaid_u128,
+        LendingError::InvalidAmount
+    );
+    require!(
+        market.total_borrowed >= principal_repaid_u128,
+        LendingError::InvalidAmount
+    );
+
+    // Update borrower account
+    borrower_account.principal = borrower_account.principal
+        .checked_sub(principal_repaid_u128)
+        .ok_or(LendingError::InvalidAmount)?;
+
+    // Update market borrowed amount
+    market.total_borrowed = market.total_borrowed
+        .checked_sub(principal_repaid_u128)
+        .ok_or(LendingError::InvalidAmount)?;
+    Ok(())
+}
+
+/// Convert collateral tokens into the underlying asset priced by the collateral feed
+fn underlying_amount(config: &CollateralConfig, amount: u64, source: Option<&AccountInfo>) -> Result<u128> {
+    match config.kind {
+        CollateralKind::Token => Ok(amount as u128),
+        CollateralKind::CrucibleCToken => {
+            let source = source.ok_or(LendingError::InvalidCollateral)?;
+            require_keys_eq!(source.key(), config.source, LendingError::InvalidCollateral);
+            let crucible = load_crucible(source)?;
+            Ok((amount as u128)
+                .checked_mul(crucible.exchange_rate as u128)
+                .and_then(|v| v.checked_div(CRUCIBLE_RATE_SCALE))
+                .ok_or(LendingError::InvalidAmount)?)
+        }
+        CollateralKind::MarketReceipt => {
+            let source = source.ok_or(LendingError::InvalidCollateral)?;
+            require_keys_eq!(source.key(), config.source, LendingError::InvalidCollateral);
+            require_keys_eq!(*source.owner, crate::ID, LendingError::InvalidCollateral);
+            let data = source.try_borrow_data()?;
+            let source_market = Market::try_deserialize(&mut &data[..])?;
+            // Same conversion as withdraw: base = receipt × accumulated_index / RATE_SCALE
+            Ok((amount as u128)
+                .checked_mul(source_market.accumulated_index)
+                .and_then(|v| v.checked_div(RATE_SCALE))
+                .ok_or(LendingError::InvalidAmount)?)
+        }
+    }
+}
+
+/// Fresh, fully verified Pyth price for `feed_id` as (price, exponent)
+fn read_price(price_update: &AccountInfo, feed_id: &[u8; 32]) -> Result<(u128, i32)> {
+    let price_update = forge_core::oracle::load_price_update(price_update)
+        .map_err(|_| LendingError::InvalidOraclePrice)?;
+    let price = price_update
+        .price_no_older_than(Clock::get()?.unix_timestamp, MAX_PRICE_AGE_SECS, feed_id)
+        .ok_or(LendingError::InvalidOraclePrice)?;
+    require!(price.price > 0, LendingError::InvalidOraclePrice);
+    Ok((price.price as u128, price.exponent))
+}
+
+/// Value of `amount` collateral tokens in market base token units
+/// value = underlying × collateral_price × 10^base_decimals / (base_price × 10^underlying_decimals)
+pub fn collateral_value(
+    config: &CollateralConfig,
+    amount: u64,
+    source: Option<&AccountInfo>,
+    collateral_price_update: &AccountInfo,
+    base_price_update: &AccountInfo,
+) -> Result<u128> {
+    let underlying = underlying_amount(config, amount, source)?;
+    let (collateral_price, collateral_expo) = read_price(collateral_price_update, &config.collateral_price_feed)?;
+    let (base_price, base_expo) = read_price(base_price_update, &config.base_price_feed)?;
+
+    let pow10 = |exp: u32| 10u128.checked_pow(exp).ok_or(LendingError::InvalidOraclePrice);
+    let expo_diff = collateral_expo
+        .checked_sub(base_expo)
+        .ok_or(LendingError::InvalidOraclePrice)?;
+    let mut numerator = underlying
+        .checked_mul(collateral_price)
+        .and_then(|v| v.checked_mul(10u128.pow(config.base_decimals as u32)))
+        .ok_or(LendingError::InvalidAmount)?;
+    let mut denominator = base_price
+        .checked_mul(10u128.pow(config.underlying_decimals as u32))
+        .ok_or(LendingError::InvalidAmount)?;
+    if expo_diff >= 0 {
+        numerator = numerator
+            .checked_mul(pow10(expo_diff.unsigned_abs())?)
+            .ok_or(LendingError::InvalidAmount)?;
+    } else {
+        denominator = denominator
+            .checked_mul(pow10(expo_diff.unsigned_abs())?)
+            .ok_or(LendingError::InvalidAmount)?;
+    }
+    Ok(numerator
+        .checked_div(denominator)
+        .ok_or(LendingError::InvalidAmount)?)
+}
+
+/// Require `debt` to be within `max_ltv_bps` of `value`
+fn require_within_ltv(debt: u128, value: u128, max_ltv_bps: u64) -> Result<()> {
+    let max_debt = value
+        .checked_mul(max_ltv_bps as u128)
+        .and_then(|v| v.checked_div(10_000))
+        .ok_or(LendingError::InvalidAmount)?;
+    require!(debt <= max_debt, LendingError::InsufficientCollateral);
+    Ok(())
+}
 
 /// Helper function to accrue interest on a market - can be called internally
 fn do_accrue_interest(market: &mut Market) -> Result<()> {
@@ -218,6 +391,7 @@ pub mod lending {
     pub fn supply(ctx: Context<Supply>, amount: u64) -> Result<()> {
         let market = &mut ctx.accounts.market;
         require!(!market.paused, LendingError::Paused);
+        forge_core::require_protocol_active(&ctx.accounts.forge_protocol)?;
         require!(amount > 0, LendingError::InvalidAmount);
 
         // Accrue before state changes
@@ -259,6 +433,7 @@ pub mod lending {
     pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
         let market = &mut ctx.accounts.market;
         require!(!market.paused, LendingError::Paused);
+        forge_core::require_protocol_active_if_present(ctx.accounts.forge_protocol.as_deref())?;
         require!(amount > 0, LendingError::InvalidAmount);
 
         // SECURITY FIX: Accrue interest before state changes to ensure accurate exchange rates
@@ -301,6 +476,7 @@ pub mod lending {
     pub fn borrow(ctx: Context<BorrowAccounts>, amount: u64) -> Result<()> {
         let market = &mut ctx.accounts.market;
         require!(!market.paused, LendingError::Paused);
+        forge_core::require_protocol_active(&ctx.accounts.forge_protocol)?;
         require!(amount > 0, LendingError::InvalidAmount);
 
         // Accrue interest before state changes
@@ -362,6 +538,18 @@ pub mod lending {
             .checked_add(amount_u128)
             .ok_or(LendingError::InvalidAmount)?;
 
+        // Debt after this borrow must stay within the collateral's max LTV
+        let debt = borrower_debt(borrower_account, market)?;
+        let collateral_config = &ctx.accounts.collateral_config;
+        let value = collateral_value(
+            collateral_config,
+            ctx.accounts.user_collateral.amount,
+            ctx.accounts.collateral_source.as_ref().map(|s| s.as_ref()),
+            &ctx.accounts.collateral_price_update,
+            &ctx.accounts.base_price_update,
+        )?;
+        require_within_ltv(debt, value, collateral_config.max_ltv_bps)?;
+
         // Update borrowed amount
         market.total_borrowed = market.total_borrowed
             .checked_add(amount_u128)
@@ -387,6 +575,7 @@ pub mod lending {
     pub fn repay(ctx: Context<Repay>, amount: u64) -> Result<()> {
         let market = &mut ctx.accounts.market;
         require!(!market.paused, LendingError::Paused);
+        forge_core::require_protocol_active_if_present(ctx.accounts.forge_protocol.as_deref())?;
         require!(amount > 0, LendingError::InvalidAmount);
 
         // SECURITY FIX: Accrue interest before state changes
@@ -404,10 +593,7 @@ pub mod lending {
         
         // Calculate total owed: principal × (current_index / borrow_index)
         // Multiply first, then divide to maximize precision
-        let total_owed_u128 = (borrower_account.principal as u128)
-            .checked_mul(market.accumulated_index)
-            .and_then(|v| v.checked_div(borrower_account.borrow_index.max(1))) // Prevent division by zero
-            .ok_or(LendingError::InvalidAmount)?;
+        let total_owed_u128 = borrower_debt(borrower_account, market)?;
         
         // Ensure total_owed fits in u64
         let total_owed_u64 = if total_owed_u128 > u64::MAX as u128 {
@@ -433,43 +619,242 @@ pub mod lending {
             amount,
         )?;
 
-        // SECURITY FIX: Calculate principal repaid based on repayment amount
-        // For partial repayments, calculate the principal portion
-        // principal_repaid = amount × (borrow_index / current_index)
-        let principal_repaid_u128 = if amount >= total_owed_u64 {
-            // Full repayment - repay all principal
-            borrower_account.principal
-        } else {
-            // Partial repayment - calculate principal portion
-            // principal_repaid = amount × (borrow_index / accumulated_index)
-            // SECURITY FIX: Return error on calculation failure instead of silently defaulting to 0
-            (amount as u128)
-                .checked_mul(borrower_account.borrow_index)
-                .and_then(|v| v.checked_div(market.accumulated_index.max(1)))
-                .ok_or(LendingError::InvalidAmount)?
-        };
-        
-        // SECURITY FIX: Validate amounts are sufficient before subtraction to detect accounting errors
+        apply_repayment(market, borrower_account, amount, total_owed_u64)?;
+
+        emit!(RepayEvent { user: ctx.accounts.user.key(), amount });
+        Ok(())
+    }
+    /// Configure the collateral asset accepted by a market (market authority only, once per market)
+    pub fn configure_collateral(ctx: Context<ConfigureCollateral>, params: ConfigureCollateralParams) -> Result<()> {
+        let market = &ctx.accounts.market;
+        // Borrowing must stop strictly before the position becomes liquidatable
         require!(
-            borrower_account.principal >= principal_repaid_u128,
-            LendingError::InvalidAmount
+            params.max_ltv_bps > 0 && params.max_ltv_bps < market.liquidation_threshold_bps,
+            LendingError::InvalidParams
         );
         require!(
-            market.total_borrowed >= principal_repaid_u128,
-            LendingError::InvalidAmount
+            params.liquidation_bonus_bps <= MAX_LIQUIDATION_BONUS_BPS,
+            LendingError::InvalidParams
         );
-        
-        // Update borrower account
-        borrower_account.principal = borrower_account.principal
-            .checked_sub(principal_repaid_u128)
+
+        let collateral_mint = ctx.accounts.collateral_mint.key();
+        let underlying_mint = ctx.accounts.underlying_mint.key();
+        let source = match params.kind {
+            CollateralKind::Token => {
+                require_keys_eq!(underlying_mint, collateral_mint, LendingError::InvalidCollateral);
+                Pubkey::default()
+            }
+            CollateralKind::CrucibleCToken => {
+                let source = ctx.accounts.collateral_source.as_ref().ok_or(LendingError::InvalidCollateral)?;
+                let crucible = load_crucible(source)?;
+                require_keys_eq!(crucible.ctoken_mint, collateral_mint, LendingError::InvalidCollateral);
+                require_keys_eq!(crucible.base_mint, underlying_mint, LendingError::InvalidCollateral);
+                source.key()
+            }
+            CollateralKind::MarketReceipt => {
+                let source = ctx.accounts.collateral_source.as_ref().ok_or(LendingError::InvalidCollateral)?;
+                require_keys_eq!(*source.owner, crate::ID, LendingError::InvalidCollateral);
+                require!(source.key() != market.key(), LendingError::InvalidCollateral);
+                let data = source.try_borrow_data()?;
+                let source_market = Market::try_deserialize(&mut &data[..])?;
+                require_keys_eq!(source_market.receipt_mint, collateral_mint, LendingError::InvalidCollateral);
+                require_keys_eq!(source_market.base_mint, underlying_mint, LendingError::InvalidCollateral);
+                source.key()
+            }
+        };
+
+        let config = &mut ctx.accounts.collateral_config;
+        config.market = market.key();
+        config.collateral_mint = collateral_mint;
+        config.collateral_vault = ctx.accounts.collateral_vault.key();
+        config.kind = params.kind;
+        config.source = source;
+        config.collateral_price_feed = params.collateral_price_feed;
+        config.base_price_feed = params.base_price_feed;
+        config.underlying_decimals = ctx.accounts.underlying_mint.decimals;
+        config.base_decimals = ctx.accounts.base_mint.decimals;
+        config.max_ltv_bps = params.max_ltv_bps;
+        config.liquidation_bonus_bps = params.liquidation_bonus_bps;
+        config.total_deposited = 0;
+        config.bump = ctx.bumps.collateral_config;
+
+        emit!(CollateralConfiguredEvent {
+            market: config.market,
+            collateral_mint,
+            max_ltv_bps: params.max_ltv_bps,
+            liquidation_bonus_bps: params.liquidation_bonus_bps,
+        });
+        Ok(())
+    }
+
+    pub fn deposit_collateral(ctx: Context<DepositCollateral>, amount: u64) -> Result<()> {
+        require!(!ctx.accounts.market.paused, LendingError::Paused);
+        forge_core::require_protocol_active(&ctx.accounts.forge_protocol)?;
+        require!(amount > 0, LendingError::InvalidAmount);
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.user_collateral_account.to_account_info(),
+            to: ctx.accounts.collateral_vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        token::transfer(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts), amount)?;
+
+        let user_collateral = &mut ctx.accounts.user_collateral;
+        if user_collateral.owner == Pubkey::default() {
+            user_collateral.owner = ctx.accounts.user.key();
+            user_collateral.market = ctx.accounts.market.key();
+        }
+        user_collateral.amount = user_collateral.amount
+            .checked_add(amount)
             .ok_or(LendingError::InvalidAmount)?;
-        
-        // Update market borrowed amount
-        market.total_borrowed = market.total_borrowed
-            .checked_sub(principal_repaid_u128)
+        let config = &mut ctx.accounts.collateral_config;
+        config.total_deposited = config.total_deposited
+            .checked_add(amount)
             .ok_or(LendingError::InvalidAmount)?;
 
-        emit!(RepayEvent { user: ctx.accounts.user.key(), amount });
+        emit!(CollateralDepositEvent { user: ctx.accounts.user.key(), amount });
+        Ok(())
+    }
+
+    /// Withdraw collateral - remaining collateral must still cover any debt at max LTV
+    pub fn withdraw_collateral(ctx: Context<WithdrawCollateral>, amount: u64) -> Result<()> {
+        require!(!ctx.accounts.market.paused, LendingError::Paused);
+        forge_core::require_protocol_active_if_present(ctx.accounts.forge_protocol.as_deref())?;
+        require!(amount > 0, LendingError::InvalidAmount);
+        require!(amount <= ctx.accounts.user_collateral.amount, LendingError::InvalidAmount);
+
+        do_accrue_interest(&mut ctx.accounts.market)?;
+
+        let remaining = ctx.accounts.user_collateral.amount
+            .checked_sub(amount)
+            .ok_or(LendingError::InvalidAmount)?;
+        let debt = load_borrower_debt(&ctx.accounts.borrower_account.to_account_info(), &ctx.accounts.market)?;
+        if debt > 0 {
+            let value = collateral_value(
+                &ctx.accounts.collateral_config,
+                remaining,
+                ctx.accounts.collateral_source.as_ref().map(|s| s.as_ref()),
+                &ctx.accounts.collateral_price_update,
+                &ctx.accounts.base_price_update,
+            )?;
+            require_within_ltv(debt, value, ctx.accounts.collateral_config.max_ltv_bps)?;
+        }
+
+        let market = &ctx.accounts.market;
+        let seeds = &[b"market", market.base_mint.as_ref(), &[market.bump]];
+        let signer = &[&seeds[..]];
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.collateral_vault.to_account_info(),
+            to: ctx.accounts.user_collateral_account.to_account_info(),
+            authority: market.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer),
+            amount,
+        )?;
+
+        ctx.accounts.user_collateral.amount = remaining;
+        let config = &mut ctx.accounts.collateral_config;
+        config.total_deposited = config.total_deposited
+            .checked_sub(amount)
+            .ok_or(LendingError::InvalidAmount)?;
+
+        emit!(CollateralWithdrawEvent { user: ctx.accounts.user.key(), amount });
+        Ok(())
+    }
+
+    /// Liquidate a borrower whose debt exceeds the market liquidation threshold.
+    /// The liquidator repays up to `repay_amount` of debt and receives collateral worth
+    /// the repaid amount plus the liquidation bonus.
+    pub fn liquidate(ctx: Context<Liquidate>, repay_amount: u64) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        require!(!market.paused, LendingError::Paused);
+        forge_core::require_protocol_active_if_present(ctx.accounts.forge_protocol.as_deref())?;
+        require!(repay_amount > 0, LendingError::InvalidAmount);
+
+        do_accrue_interest(market)?;
+
+        let borrower_account = &mut ctx.accounts.borrower_account;
+        let debt = borrower_debt(borrower_account, market)?;
+        require!(debt > 0, LendingError::NotLiquidatable);
+        let total_owed = if debt > u64::MAX as u128 {
+            return Err(LendingError::InvalidAmount.into());
+        } else {
+            debt as u64
+        };
+
+        let collateral = ctx.accounts.user_collateral.amount;
+        let config = &ctx.accounts.collateral_config;
+        let value = collateral_value(
+            config,
+            collateral,
+            ctx.accounts.collateral_source.as_ref().map(|s| s.as_ref()),
+            &ctx.accounts.collateral_price_update,
+            &ctx.accounts.base_price_update,
+        )?;
+        // A zero valuation would hand the liquidator all collateral for any repayment
+        require!(value > 0, LendingError::InvalidOraclePrice);
+        // Liquidatable once debt / collateral value reaches the liquidation threshold
+        let threshold_value = value
+            .checked_mul(market.liquidation_threshold_bps as u128)
+            .and_then(|v| v.checked_div(10_000))
+            .ok_or(LendingError::InvalidAmount)?;
+        require!(debt >= threshold_value, LendingError::NotLiquidatable);
+
+        let repay_amount = repay_amount.min(total_owed);
+
+        // Collateral value is linear in the amount, so seize proportionally:
+        // seized = repay × (1 + bonus) × collateral / value, capped at the borrower's collateral
+        let seized = (repay_amount as u128)
+            .checked_mul(10_000 + config.liquidation_bonus_bps as u128)
+            .and_then(|v| v.checked_mul(collateral as u128))
+            .and_then(|v| v.checked_div(10_000))
+            .and_then(|v| v.checked_div(value))
+            .ok_or(LendingError::InvalidAmount)?;
+        let seized = seized.min(collateral as u128) as u64;
+
+        // Liquidator repays the debt into the market vault
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.liquidator_base_account.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.liquidator.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+            repay_amount,
+        )?;
+        apply_repayment(market, borrower_account, repay_amount, total_owed)?;
+
+        // Pay the liquidator out of the borrower's collateral
+        if seized > 0 {
+            let seeds = &[b"market", market.base_mint.as_ref(), &[market.bump]];
+            let signer = &[&seeds[..]];
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.collateral_vault.to_account_info(),
+                to: ctx.accounts.liquidator_collateral_account.to_account_info(),
+                authority: market.to_account_info(),
+            };
+            token::transfer(
+                CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer),
+                seized,
+            )?;
+        }
+
+        let user_collateral = &mut ctx.accounts.user_collateral;
+        user_collateral.amount = user_collateral.amount
+            .checked_sub(seized)
+            .ok_or(LendingError::InvalidAmount)?;
+        let config = &mut ctx.accounts.collateral_config;
+        config.total_deposited = config.total_deposited
+            .checked_sub(seized)
+            .ok_or(LendingError::InvalidAmount)?;
+
+        emit!(LiquidateEvent {
+            borrower: ctx.accounts.borrower.key(),
+            liquidator: ctx.accounts.liquidator.key(),
+            repaid: repay_amount,
+            collateral_seized: seized,
+        });
         Ok(())
     }
 }
@@ -535,6 +920,8 @@ pub struct Supply<'info> {
     #[account(mut)]
     pub user_receipt_account: Account<'info, TokenAccount>,
     pub token_program: Program<'info, Token>,
+    /// CHECK: Forge protocol PDA - entries are refused while the protocol is inactive, validated in forge_core::load_protocol
+    pub forge_protocol: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
@@ -552,6 +939,8 @@ pub struct Withdraw<'info> {
     #[account(mut)]
     pub user_receipt_account: Account<'info, TokenAccount>,
     pub token_program: Program<'info, Token>,
+    /// CHECK: Optional Forge protocol PDA - state changes are refused while the protocol is inactive
+    pub forge_protocol: Option<UncheckedAccount<'info>>,
 }
 
 #[derive(Accounts)]
@@ -573,8 +962,23 @@ pub struct BorrowAccounts<'info> {
     pub vault: Account<'info, TokenAccount>,
     #[account(mut)]
     pub user_account: Account<'info, TokenAccount>,
+    #[account(seeds = [b"collateral_config", market.key().as_ref()], bump = collateral_config.bump)]
+    pub collateral_config: Box<Account<'info, CollateralConfig>>,
+    #[account(
+        seeds = [b"collateral", market.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_collateral: Box<Account<'info, UserCollateral>>,
+    /// CHECK: Pyth PriceUpdateV2 for the collateral - owner and discriminator checked when read
+    pub collateral_price_update: UncheckedAccount<'info>,
+    /// CHECK: Pyth PriceUpdateV2 for the base token - owner and discriminator checked when read
+    pub base_price_update: UncheckedAccount<'info>,
+    /// CHECK: Crucible or lending market backing the collateral - validated against collateral_config.source
+    pub collateral_source: Option<UncheckedAccount<'info>>,
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
+    /// CHECK: Forge protocol PDA - entries are refused while the protocol is inactive, validated in forge_core::load_protocol
+    pub forge_protocol: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
@@ -595,6 +999,150 @@ pub struct Repay<'info> {
     #[account(mut)]
     pub user_account: Account<'info, TokenAccount>,
     pub token_program: Program<'info, Token>,
+    /// CHECK: Optional Forge protocol PDA - state changes are refused while the protocol is inactive
+    pub forge_protocol: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureCollateral<'info> {
+    #[account(has_one = authority)]
+    pub market: Box<Account<'info, Market>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = CollateralConfig::SIZE,
+        seeds = [b"collateral_config", market.key().as_ref()],
+        bump
+    )]
+    pub collateral_config: Box<Account<'info, CollateralConfig>>,
+    pub collateral_mint: Box<Account<'info, Mint>>,
+    /// Asset priced by the collateral feed - the collateral mint itself for plain tokens
+    pub underlying_mint: Box<Account<'info, Mint>>,
+    #[account(address = market.base_mint)]
+    pub base_mint: Box<Account<'info, Mint>>,
+    #[account(
+        init,
+        payer = authority,
+        token::mint = collateral_mint,
+        token::authority = market,
+        seeds = [b"collateral_vault", market.key().as_ref()],
+        bump
+    )]
+    pub collateral_vault: Box<Account<'info, TokenAccount>>,
+    /// CHECK: Crucible or lending market backing the collateral - validated in instruction
+    pub collateral_source: Option<UncheckedAccount<'info>>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositCollateral<'info> {
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"collateral_config", market.key().as_ref()],
+        bump = collateral_config.bump
+    )]
+    pub collateral_config: Box<Account<'info, CollateralConfig>>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserCollateral::LEN,
+        seeds = [b"collateral", market.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_collateral: Box<Account<'info, UserCollateral>>,
+    #[account(mut, constraint = user_collateral_account.mint == collateral_config.collateral_mint @ LendingError::InvalidCollateral)]
+    pub user_collateral_account: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = collateral_config.collateral_vault)]
+    pub collateral_vault: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: Forge protocol PDA - entries are refused while the protocol is inactive, validated in forge_core::load_protocol
+    pub forge_protocol: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawCollateral<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"collateral_config", market.key().as_ref()],
+        bump = collateral_config.bump
+    )]
+    pub collateral_config: Box<Account<'info, CollateralConfig>>,
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"collateral", market.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_collateral: Box<Account<'info, UserCollateral>>,
+    /// CHECK: Borrower account PDA - may be uninitialized if the user never borrowed
+    #[account(seeds = [b"borrower", market.key().as_ref(), user.key().as_ref()], bump)]
+    pub borrower_account: UncheckedAccount<'info>,
+    #[account(mut, constraint = user_collateral_account.mint == collateral_config.collateral_mint @ LendingError::InvalidCollateral)]
+    pub user_collateral_account: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = collateral_config.collateral_vault)]
+    pub collateral_vault: Box<Account<'info, TokenAccount>>,
+    /// CHECK: Pyth PriceUpdateV2 for the collateral - owner and discriminator checked when read
+    pub collateral_price_update: UncheckedAccount<'info>,
+    /// CHECK: Pyth PriceUpdateV2 for the base token - owner and discriminator checked when read
+    pub base_price_update: UncheckedAccount<'info>,
+    /// CHECK: Crucible or lending market backing the collateral - validated against collateral_config.source
+    pub collateral_source: Option<UncheckedAccount<'info>>,
+    pub token_program: Program<'info, Token>,
+    /// CHECK: Optional Forge protocol PDA - state changes are refused while the protocol is inactive
+    pub forge_protocol: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct Liquidate<'info> {
+    #[account(mut)]
+    pub market: Box<Account<'info, Market>>,
+    #[account(
+        mut,
+        seeds = [b"collateral_config", market.key().as_ref()],
+        bump = collateral_config.bump
+    )]
+    pub collateral_config: Box<Account<'info, CollateralConfig>>,
+    pub liquidator: Signer<'info>,
+    /// CHECK: Borrower being liquidated - only used to derive PDAs
+    pub borrower: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"borrower", market.key().as_ref(), borrower.key().as_ref()],
+        bump
+    )]
+    pub borrower_account: Box<Account<'info, BorrowerAccount>>,
+    #[account(
+        mut,
+        seeds = [b"collateral", market.key().as_ref(), borrower.key().as_ref()],
+        bump
+    )]
+    pub user_collateral: Box<Account<'info, UserCollateral>>,
+    #[account(mut, address = market.vault)]
+    pub vault: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = collateral_config.collateral_vault)]
+    pub collateral_vault: Box<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub liquidator_base_account: Box<Account<'info, TokenAccount>>,
+    #[account(mut, constraint = liquidator_collateral_account.mint == collateral_config.collateral_mint @ LendingError::InvalidCollateral)]
+    pub liquidator_collateral_account: Box<Account<'info, TokenAccount>>,
+    /// CHECK: Pyth PriceUpdateV2 for the collateral - owner and discriminator checked when read
+    pub collateral_price_update: UncheckedAccount<'info>,
+    /// CHECK: Pyth PriceUpdateV2 for the base token - owner and discriminator checked when read
+    pub base_price_update: UncheckedAccount<'info>,
+    /// CHECK: Crucible or lending market backing the collateral - validated against collateral_config.source
+    pub collateral_source: Option<UncheckedAccount<'info>>,
+    pub token_program: Program<'info, Token>,
+    /// CHECK: Optional Forge protocol PDA - state changes are refused while the protocol is inactive
+    pub forge_protocol: Option<UncheckedAccount<'info>>,
 }
 
 #[event]
@@ -621,6 +1169,34 @@ pub struct RepayEvent {
     pub amount: u64,
 }
 
+#[event]
+pub struct CollateralConfiguredEvent {
+    pub market: Pubkey,
+    pub collateral_mint: Pubkey,
+    pub max_ltv_bps: u64,
+    pub liquidation_bonus_bps: u64,
+}
+
+#[event]
+pub struct CollateralDepositEvent {
+    pub user: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct CollateralWithdrawEvent {
+    pub user: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct LiquidateEvent {
+    pub borrower: Pubkey,
+    pub liquidator: Pubkey,
+    pub repaid: u64,
+    pub collateral_seized: u64,
+}
+
 #[error_code]
 pub enum LendingError {
     #[msg("Invalid parameters")] InvalidParams,
@@ -632,6 +1208,10 @@ pub enum LendingError {
     #[msg("Timelock has not expired")] TimelockNotExpired,
     #[msg("No pause proposal exists")] NoPauseProposal,
     #[msg("Pause proposal already exists")] PauseProposalAlreadyExists,
+    #[msg("Invalid collateral")] InvalidCollateral,
+    #[msg("Invalid or stale oracle price")] InvalidOraclePrice,
+    #[msg("Insufficient collateral")] InsufficientCollateral,
+    #[msg("Position is not liquidatable")] NotLiquidatable,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -644,3 +1224,12 @@ pub struct InitializeMarketParams {
 }
 
 
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ConfigureCollateralParams {
+    pub kind: CollateralKind,
+    pub collateral_price_feed: [u8; 32],
+    pub base_price_feed: [u8; 32],
+    pub max_ltv_bps: u64,
+    pub liquidation_bonus_bps: u64,
+}
@@ -59,3 +59,57 @@ impl BorrowerAccount {
 }
 
 
+
+/// How deposited collateral converts into its underlying (oracle-priced) asset
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum CollateralKind {
+    Token,          // Collateral mint is the priced asset
+    CrucibleCToken, // cTokens of the `source` crucible - underlying via crucible exchange rate
+    MarketReceipt,  // Receipt tokens of the `source` lending market - underlying via its accumulated index
+}
+
+/// Collateral accepted by an isolated market - one collateral asset per market
+#[account]
+pub struct CollateralConfig {
+    pub market: Pubkey,
+    pub collateral_mint: Pubkey,
+    pub collateral_vault: Pubkey,
+    pub kind: CollateralKind,
+    pub source: Pubkey, // Crucible or lending market for non-Token kinds, default otherwise
+    pub collateral_price_feed: [u8; 32], // Pyth feed id of the underlying collateral asset
+    pub base_price_feed: [u8; 32], // Pyth feed id of the market base asset
+    pub underlying_decimals: u8,
+    pub base_decimals: u8,
+    pub max_ltv_bps: u64, // Max debt / collateral value when borrowing or withdrawing
+    pub liquidation_bonus_bps: u64, // Extra collateral paid to liquidators
+    pub total_deposited: u64,
+    pub bump: u8,
+}
+
+impl CollateralConfig {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // market
+        32 + // collateral_mint
+        32 + // collateral_vault
+        1 +  // kind
+        32 + // source
+        32 + // collateral_price_feed
+        32 + // base_price_feed
+        1 +  // underlying_decimals
+        1 +  // base_decimals
+        8 +  // max_ltv_bps
+        8 +  // liquidation_bonus_bps
+        8 +  // total_deposited
+        1;   // bump
+}
+
+#[account]
+pub struct UserCollateral {
+    pub owner: Pubkey,
+    pub market: Pubkey,
+    pub amount: u64, // Collateral tokens deposited
+}
+
+impl UserCollateral {
+    pub const LEN: usize = 32 + 32 + 8; // owner (32) + market (32) + amount (8)
+}
@@ -1,9 +1,18 @@
 // Summary: LVF program to open/close leveraged positions using cTokens as collateral
-// and interacting with the lending market. Includes pause, health check stubs,
+// and interacting with the lending market. Includes pause, health check,
 // liquidation entry point and admin config.
+//
+// Each position has a data-less authority PDA that is the borrower in the lending
+// market. It deposits the position's cTokens as lending collateral and owns the
+// position's transit and repay vaults, so keepers can repay and liquidate without
+// the owner's signature.
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer};
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount, Mint, Transfer};
+use forge_core::pause::{is_paused, PAUSE_LEVERAGE_OPEN};
+use lending::cpi::accounts::{AccrueInterest, BorrowAccounts, DepositCollateral, Repay, WithdrawCollateral};
+use lending::program::Lending;
+use lending::state::{BorrowerAccount, CollateralConfig, Market, UserCollateral};
 
 pub mod state;
 use state::*;
@@ -11,6 +20,39 @@ use state::*;
 declare_id!("6izkeaYVyYFmDfQ4y749jE1Ew3EVtvNDeWxNzebjYFjs");
 
 pub const RATE_SCALE: u128 = 1_000_000_000u128; // align with crucibles/lending
+const LEVERAGE_SCALE: u64 = 100; // 100 = 1x
+
+/// cTokens out of `collateral` worth `value` borrow-mint units, given the collateral's total value
+/// Collateral is valued by lending::collateral_value - the same pricing the market enforces on borrow
+fn ctokens_for_value(value: u128, collateral: u64, collateral_value: u128) -> Result<u128> {
+    require!(collateral_value > 0, LvfError::InvalidCollateralValue);
+    value
+        .checked_mul(collateral as u128)
+        .and_then(|v| v.checked_div(collateral_value))
+        .ok_or(ProgramError::ArithmeticOverflow.into())
+}
+
+/// Debt owed at the market's current index - same formula as lending::repay
+fn current_debt(borrower_account: &BorrowerAccount, market: &Market) -> Result<u64> {
+    let debt = borrower_account.principal
+        .checked_mul(market.accumulated_index)
+        .and_then(|v| v.checked_div(borrower_account.borrow_index.max(1)))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if debt > u64::MAX as u128 {
+        return Err(ProgramError::ArithmeticOverflow.into());
+    }
+    Ok(debt as u64)
+}
+
+/// Loan-to-value in basis points: debt * 10_000 / collateral_value
+fn ltv_bps(debt: u128, collateral_value: u128) -> Result<u64> {
+    require!(collateral_value > 0, LvfError::InvalidCollateralValue);
+    let ltv = debt
+        .checked_mul(10_000)
+        .and_then(|v| v.checked_div(collateral_value))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    Ok(ltv.min(u64::MAX as u128) as u64)
+}
 
 #[program]
 pub mod lvf {
@@ -56,31 +98,423 @@ pub mod lvf {
         Ok(())
     }
 
+    /// Pause or resume new positions - closes and liquidations are never paused
     pub fn pause(ctx: Context<Pause>, paused: bool) -> Result<()> {
         require_keys_eq!(ctx.accounts.config.authority, ctx.accounts.authority.key(), LvfError::Unauthorized);
         ctx.accounts.config.paused = paused;
         Ok(())
     }
 
-    pub fn open_position(_ctx: Context<OpenPosition>, _params: OpenPositionParams) -> Result<()> {
-        // Placeholder; full CPI to crucibles and lending to be added in next iteration
-        err!(LvfError::Unimplemented)
+    /// Open a leveraged position: deposit crucible cTokens as collateral and borrow
+    /// (leverage - 1x) of their value from the lending market to the owner
+    pub fn open_position(ctx: Context<OpenPosition>, params: OpenPositionParams) -> Result<()> {
+        let config = &ctx.accounts.config;
+        require!(!config.paused, LvfError::Paused);
+        let crucible = lending::load_crucible(&ctx.accounts.crucible)?;
+        require_keys_eq!(crucible.ctoken_mint, ctx.accounts.ctoken_mint.key(), LvfError::InvalidAccount);
+        require!(
+            !is_paused(crucible.pause_flags, PAUSE_LEVERAGE_OPEN),
+            LvfError::Paused
+        );
+        require!(params.collateral_amount > 0, LvfError::InvalidParams);
+        require!(
+            params.leverage_bps > LEVERAGE_SCALE && params.leverage_bps <= config.max_leverage_bps,
+            LvfError::InvalidLeverage
+        );
+
+        let value = lending::collateral_value(
+            &ctx.accounts.collateral_config,
+            params.collateral_amount,
+            Some(&ctx.accounts.crucible),
+            &ctx.accounts.collateral_price_update,
+            &ctx.accounts.base_price_update,
+        )?;
+
+        let borrow_amount = value
+            .checked_mul((params.leverage_bps - LEVERAGE_SCALE) as u128)
+            .and_then(|v| v.checked_div(LEVERAGE_SCALE as u128))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        if borrow_amount > u64::MAX as u128 {
+            return Err(ProgramError::ArithmeticOverflow.into());
+        }
+        let borrow_amount = borrow_amount as u64;
+        require!(borrow_amount > 0, LvfError::InvalidParams);
+
+        // A new position must open below the liquidation threshold
+        let ltv = ltv_bps(borrow_amount as u128, value)?;
+        require!(ltv < config.liquidation_threshold_bps, LvfError::PositionUnhealthy);
+
+        // Move collateral into the position's transit vault
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.owner_ctoken_account.to_account_info(),
+            to: ctx.accounts.collateral_vault.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+            params.collateral_amount,
+        )?;
+
+        // The position authority pays for its lending collateral and borrower accounts - fund it from the owner
+        let rent = Rent::get()?;
+        let required_lamports = rent.minimum_balance(8 + BorrowerAccount::LEN)
+            .checked_add(rent.minimum_balance(8 + UserCollateral::LEN))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let top_up = required_lamports.saturating_sub(ctx.accounts.position_authority.lamports());
+        if top_up > 0 {
+            let cpi_accounts = anchor_lang::system_program::Transfer {
+                from: ctx.accounts.owner.to_account_info(),
+                to: ctx.accounts.position_authority.to_account_info(),
+            };
+            anchor_lang::system_program::transfer(
+                CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts),
+                top_up,
+            )?;
+        }
+
+        // Deposit the cTokens as lending collateral and borrow against them as the position authority
+        let position_key = ctx.accounts.position.key();
+        let authority_bump = ctx.bumps.position_authority;
+        let seeds = &[b"lvf_authority", position_key.as_ref(), &[authority_bump]];
+        let signer = &[&seeds[..]];
+        let cpi_accounts = DepositCollateral {
+            market: ctx.accounts.market.to_account_info(),
+            collateral_config: ctx.accounts.collateral_config.to_account_info(),
+            user: ctx.accounts.position_authority.to_account_info(),
+            user_collateral: ctx.accounts.user_collateral.to_account_info(),
+            user_collateral_account: ctx.accounts.collateral_vault.to_account_info(),
+            collateral_vault: ctx.accounts.lending_collateral_vault.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            forge_protocol: ctx.accounts.forge_protocol.to_account_info(),
+        };
+        lending::cpi::deposit_collateral(
+            CpiContext::new_with_signer(ctx.accounts.lending_program.to_account_info(), cpi_accounts, signer),
+            params.collateral_amount,
+        )?;
+
+        let cpi_accounts = BorrowAccounts {
+            market: ctx.accounts.market.to_account_info(),
+            user: ctx.accounts.position_authority.to_account_info(),
+            borrower_account: ctx.accounts.borrower_account.to_account_info(),
+            vault: ctx.accounts.market_vault.to_account_info(),
+            user_account: ctx.accounts.owner_borrow_account.to_account_info(),
+            collateral_config: ctx.accounts.collateral_config.to_account_info(),
+            user_collateral: ctx.accounts.user_collateral.to_account_info(),
+            collateral_price_update: ctx.accounts.collateral_price_update.to_account_info(),
+            base_price_update: ctx.accounts.base_price_update.to_account_info(),
+            collateral_source: Some(ctx.accounts.crucible.to_account_info()),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            forge_protocol: ctx.accounts.forge_protocol.to_account_info(),
+        };
+        lending::cpi::borrow(
+            CpiContext::new_with_signer(ctx.accounts.lending_program.to_account_info(), cpi_accounts, signer),
+            borrow_amount,
+        )?;
+
+        let position = &mut ctx.accounts.position;
+        position.owner = ctx.accounts.owner.key();
+        position.crucible = ctx.accounts.crucible.key();
+        position.p_token_mint = ctx.accounts.ctoken_mint.key();
+        position.pair_market = ctx.accounts.market.key();
+        position.position_shares = params.collateral_amount as u128;
+        position.borrowed_amount = borrow_amount as u128;
+        position.entry_exchange_rate = crucible.exchange_rate as u128;
+        position.leverage_bps = params.leverage_bps;
+        position.nonce = params.position_nonce;
+        position.bump = ctx.bumps.position;
+        position.authority_bump = authority_bump;
+
+        emit!(LvfPositionOpened {
+            position: position_key,
+            owner: position.owner,
+            crucible: position.crucible,
+            market: position.pair_market,
+            collateral: params.collateral_amount,
+            borrowed: borrow_amount,
+            leverage_bps: params.leverage_bps,
+        });
+        Ok(())
     }
 
-    pub fn close_position(_ctx: Context<ClosePosition>) -> Result<()> {
-        err!(LvfError::Unimplemented)
+    /// Close a position: the owner repays the full debt and receives their collateral back
+    /// Allowed while LVF is paused, so a pause never traps collateral
+    pub fn close_position(ctx: Context<ClosePosition>) -> Result<()> {
+        let debt = accrue_and_get_debt(
+            &ctx.accounts.lending_program,
+            &mut ctx.accounts.market,
+            &ctx.accounts.borrower_account,
+        )?;
+
+        if debt > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.owner_borrow_account.to_account_info(),
+                to: ctx.accounts.repay_vault.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            };
+            token::transfer(
+                CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+                debt,
+            )?;
+        }
+
+        let position_key = ctx.accounts.position.key();
+        let seeds = &[b"lvf_authority", position_key.as_ref(), &[ctx.accounts.position.authority_bump]];
+        let signer = &[&seeds[..]];
+
+        if debt > 0 {
+            let cpi_accounts = Repay {
+                market: ctx.accounts.market.to_account_info(),
+                user: ctx.accounts.position_authority.to_account_info(),
+                borrower_account: ctx.accounts.borrower_account.to_account_info(),
+                vault: ctx.accounts.market_vault.to_account_info(),
+                user_account: ctx.accounts.repay_vault.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                forge_protocol: ctx.accounts.forge_protocol.as_ref().map(|p| p.to_account_info()),
+            };
+            lending::cpi::repay(
+                CpiContext::new_with_signer(ctx.accounts.lending_program.to_account_info(), cpi_accounts, signer),
+                debt,
+            )?;
+        }
+
+        // Withdraw all lending collateral back to the owner
+        let collateral = ctx.accounts.user_collateral.amount;
+        if collateral > 0 {
+            let cpi_accounts = WithdrawCollateral {
+                market: ctx.accounts.market.to_account_info(),
+                collateral_config: ctx.accounts.collateral_config.to_account_info(),
+                user: ctx.accounts.position_authority.to_account_info(),
+                user_collateral: ctx.accounts.user_collateral.to_account_info(),
+                borrower_account: ctx.accounts.borrower_account.to_account_info(),
+                user_collateral_account: ctx.accounts.owner_ctoken_account.to_account_info(),
+                collateral_vault: ctx.accounts.lending_collateral_vault.to_account_info(),
+                collateral_price_update: ctx.accounts.collateral_price_update.to_account_info(),
+                base_price_update: ctx.accounts.base_price_update.to_account_info(),
+                collateral_source: Some(ctx.accounts.crucible.to_account_info()),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                forge_protocol: ctx.accounts.forge_protocol.as_ref().map(|p| p.to_account_info()),
+            };
+            lending::cpi::withdraw_collateral(
+                CpiContext::new_with_signer(ctx.accounts.lending_program.to_account_info(), cpi_accounts, signer),
+                collateral,
+            )?;
+        }
+
+        // Close the position vaults to the owner
+        let token_program = ctx.accounts.token_program.to_account_info();
+        let authority = ctx.accounts.position_authority.to_account_info();
+        let owner = ctx.accounts.owner.to_account_info();
+        sweep_and_close(
+            &ctx.accounts.collateral_vault,
+            ctx.accounts.owner_ctoken_account.to_account_info(),
+            owner.clone(),
+            authority.clone(),
+            token_program.clone(),
+            signer,
+        )?;
+        sweep_and_close(
+            &ctx.accounts.repay_vault,
+            ctx.accounts.owner_borrow_account.to_account_info(),
+            owner,
+            authority,
+            token_program,
+            signer,
+        )?;
+
+        emit!(LvfPositionClosed {
+            position: position_key,
+            owner: ctx.accounts.owner.key(),
+            collateral_returned: collateral,
+            debt_repaid: debt,
+        });
+        Ok(())
     }
 
-    pub fn liquidate_position(_ctx: Context<LiquidatePosition>) -> Result<()> {
-        err!(LvfError::Unimplemented)
+    /// Liquidate an unhealthy position: any keeper repays the full debt and receives
+    /// collateral worth the debt plus the configured bounty; the rest goes to the owner
+    pub fn liquidate_position(ctx: Context<LiquidatePosition>) -> Result<()> {
+        // Liquidations keep running while LVF is paused so bad debt cannot build up
+        let config = &ctx.accounts.config;
+        let liquidation_threshold_bps = config.liquidation_threshold_bps;
+        let liquidation_bounty_bps = config.liquidation_bounty_bps;
+
+        let debt = accrue_and_get_debt(
+            &ctx.accounts.lending_program,
+            &mut ctx.accounts.market,
+            &ctx.accounts.borrower_account,
+        )?;
+
+        let collateral = ctx.accounts.user_collateral.amount;
+        let value = lending::collateral_value(
+            &ctx.accounts.collateral_config,
+            collateral,
+            Some(&ctx.accounts.crucible),
+            &ctx.accounts.collateral_price_update,
+            &ctx.accounts.base_price_update,
+        )?;
+        let ltv = ltv_bps(debt as u128, value)?;
+        require!(ltv >= liquidation_threshold_bps, LvfError::PositionNotLiquidatable);
+
+        // Seize collateral worth debt + bounty, capped at the whole position
+        let seize_value = (debt as u128)
+            .checked_mul(10_000 + liquidation_bounty_bps as u128)
+            .and_then(|v| v.checked_div(10_000))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let seized = ctokens_for_value(seize_value, collateral, value)?
+            .min(collateral as u128) as u64;
+        let returned = collateral
+            .checked_sub(seized)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        // Liquidator funds the repayment
+        if debt > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.liquidator_borrow_account.to_account_info(),
+                to: ctx.accounts.repay_vault.to_account_info(),
+                authority: ctx.accounts.liquidator.to_account_info(),
+            };
+            token::transfer(
+                CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+                debt,
+            )?;
+        }
+
+        let position_key = ctx.accounts.position.key();
+        let seeds = &[b"lvf_authority", position_key.as_ref(), &[ctx.accounts.position.authority_bump]];
+        let signer = &[&seeds[..]];
+
+        if debt > 0 {
+            let cpi_accounts = Repay {
+                market: ctx.accounts.market.to_account_info(),
+                user: ctx.accounts.position_authority.to_account_info(),
+                borrower_account: ctx.accounts.borrower_account.to_account_info(),
+                vault: ctx.accounts.market_vault.to_account_info(),
+                user_account: ctx.accounts.repay_vault.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                forge_protocol: ctx.accounts.forge_protocol.as_ref().map(|p| p.to_account_info()),
+            };
+            lending::cpi::repay(
+                CpiContext::new_with_signer(ctx.accounts.lending_program.to_account_info(), cpi_accounts, signer),
+                debt,
+            )?;
+        }
+
+        // Debt is repaid in full, so the lending collateral can be withdrawn without a health check
+        let transfers = [
+            (ctx.accounts.liquidator_ctoken_account.to_account_info(), seized),
+            (ctx.accounts.owner_ctoken_account.to_account_info(), returned),
+        ];
+        for (to, amount) in transfers {
+            if amount == 0 {
+                continue;
+            }
+            let cpi_accounts = WithdrawCollateral {
+                market: ctx.accounts.market.to_account_info(),
+                collateral_config: ctx.accounts.collateral_config.to_account_info(),
+                user: ctx.accounts.position_authority.to_account_info(),
+                user_collateral: ctx.accounts.user_collateral.to_account_info(),
+                borrower_account: ctx.accounts.borrower_account.to_account_info(),
+                user_collateral_account: to,
+                collateral_vault: ctx.accounts.lending_collateral_vault.to_account_info(),
+                collateral_price_update: ctx.accounts.collateral_price_update.to_account_info(),
+                base_price_update: ctx.accounts.base_price_update.to_account_info(),
+                collateral_source: Some(ctx.accounts.crucible.to_account_info()),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                forge_protocol: ctx.accounts.forge_protocol.as_ref().map(|p| p.to_account_info()),
+            };
+            lending::cpi::withdraw_collateral(
+                CpiContext::new_with_signer(ctx.accounts.lending_program.to_account_info(), cpi_accounts, signer),
+                amount,
+            )?;
+        }
+
+        let token_program = ctx.accounts.token_program.to_account_info();
+        let authority = ctx.accounts.position_authority.to_account_info();
+        let owner = ctx.accounts.owner.to_account_info();
+        sweep_and_close(
+            &ctx.accounts.collateral_vault,
+            ctx.accounts.owner_ctoken_account.to_account_info(),
+            owner.clone(),
+            authority.clone(),
+            token_program.clone(),
+            signer,
+        )?;
+        sweep_and_close(
+            &ctx.accounts.repay_vault,
+            ctx.accounts.liquidator_borrow_account.to_account_info(),
+            owner,
+            authority,
+            token_program,
+            signer,
+        )?;
+
+        emit!(LvfPositionLiquidated {
+            position: position_key,
+            owner: ctx.accounts.owner.key(),
+            liquidator: ctx.accounts.liquidator.key(),
+            debt_repaid: debt,
+            collateral_seized: seized,
+            collateral_returned: returned,
+            ltv_bps: ltv,
+        });
+        Ok(())
     }
 
-    pub fn health_check(_ctx: Context<HealthCheck>) -> Result<u64> {
-        // Return LTV in basis points (stub)
-        Ok(0)
+    /// Return position LTV in basis points at the market's last accrued index
+    pub fn health_check(ctx: Context<HealthCheck>) -> Result<u64> {
+        let value = lending::collateral_value(
+            &ctx.accounts.collateral_config,
+            ctx.accounts.user_collateral.amount,
+            Some(&ctx.accounts.crucible),
+            &ctx.accounts.collateral_price_update,
+            &ctx.accounts.base_price_update,
+        )?;
+        let debt = current_debt(&ctx.accounts.borrower_account, &ctx.accounts.market)?;
+        ltv_bps(debt as u128, value)
     }
 }
 
+/// Move any stray balance out of a position vault, then close it and refund its rent
+fn sweep_and_close<'info>(
+    vault: &Account<'info, TokenAccount>,
+    sweep_to: AccountInfo<'info>,
+    rent_to: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+) -> Result<()> {
+    if vault.amount > 0 {
+        let cpi_accounts = Transfer {
+            from: vault.to_account_info(),
+            to: sweep_to,
+            authority: authority.clone(),
+        };
+        token::transfer(CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer), vault.amount)?;
+    }
+    let cpi_accounts = CloseAccount {
+        account: vault.to_account_info(),
+        destination: rent_to,
+        authority,
+    };
+    token::close_account(CpiContext::new_with_signer(token_program, cpi_accounts, signer))
+}
+
+/// Accrue market interest via CPI, reload the market and return the position's current debt
+fn accrue_and_get_debt<'info>(
+    lending_program: &Program<'info, Lending>,
+    market: &mut Box<Account<'info, Market>>,
+    borrower_account: &Account<'info, BorrowerAccount>,
+) -> Result<u64> {
+    let cpi_accounts = AccrueInterest {
+        market: market.to_account_info(),
+    };
+    lending::cpi::accrue_interest(CpiContext::new(lending_program.to_account_info(), cpi_accounts))?;
+    market.reload()?;
+    current_debt(borrower_account, market)
+}
+
 #[derive(Accounts)]
 #[instruction(params: InitializeLvfParams)]
 pub struct InitializeConfig<'info> {
@@ -105,23 +539,307 @@ pub struct Pause<'info> {
 }
 
 #[derive(Accounts)]
+#[instruction(params: OpenPositionParams)]
 pub struct OpenPosition<'info> {
-    pub config: Account<'info, LvfConfig>,
+    #[account(seeds = [b"lvf_config"], bump = config.bump)]
+    pub config: Box<Account<'info, LvfConfig>>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        init,
+        payer = owner,
+        space = LvfPosition::SIZE,
+        seeds = [b"lvf_position", owner.key().as_ref(), crucible.key().as_ref(), &params.position_nonce.to_le_bytes()],
+        bump
+    )]
+    pub position: Box<Account<'info, LvfPosition>>,
+    /// CHECK: Data-less position authority PDA - lending borrower and vault owner
+    #[account(mut, seeds = [b"lvf_authority", position.key().as_ref()], bump)]
+    pub position_authority: UncheckedAccount<'info>,
+    /// CHECK: forge-crucibles crucible - read by lending::load_crucible and bound to ctoken_mint
+    pub crucible: UncheckedAccount<'info>,
+    pub ctoken_mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        constraint = owner_ctoken_account.mint == ctoken_mint.key() @ LvfError::InvalidAccount,
+        constraint = owner_ctoken_account.owner == owner.key() @ LvfError::Unauthorized,
+    )]
+    pub owner_ctoken_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = owner,
+        token::mint = ctoken_mint,
+        token::authority = position_authority,
+        seeds = [b"lvf_collateral", position.key().as_ref()],
+        bump
+    )]
+    pub collateral_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = owner,
+        token::mint = borrow_mint,
+        token::authority = position_authority,
+        seeds = [b"lvf_repay", position.key().as_ref()],
+        bump
+    )]
+    pub repay_vault: Box<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub market: Box<Account<'info, Market>>,
+    #[account(address = market.base_mint @ LvfError::InvalidAccount)]
+    pub borrow_mint: Box<Account<'info, Mint>>,
+    #[account(mut, address = market.vault @ LvfError::InvalidAccount)]
+    pub market_vault: Box<Account<'info, TokenAccount>>,
+    /// CHECK: Lending borrower account for the position authority - initialized by the lending program
+    #[account(mut)]
+    pub borrower_account: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"collateral_config", market.key().as_ref()],
+        bump = collateral_config.bump,
+        seeds::program = lending_program.key(),
+        constraint = collateral_config.collateral_mint == ctoken_mint.key() @ LvfError::InvalidAccount,
+    )]
+    pub collateral_config: Box<Account<'info, CollateralConfig>>,
+    /// CHECK: Lending collateral record for the position authority - initialized by the lending program
+    #[account(mut)]
+    pub user_collateral: UncheckedAccount<'info>,
+    /// CHECK: Lending collateral vault - validated by the lending program
+    #[account(mut, address = collateral_config.collateral_vault @ LvfError::InvalidAccount)]
+    pub lending_collateral_vault: UncheckedAccount<'info>,
+    /// CHECK: Pyth price update for the collateral - validated by lending::collateral_value
+    pub collateral_price_update: UncheckedAccount<'info>,
+    /// CHECK: Pyth price update for the borrow mint - validated by lending::collateral_value
+    pub base_price_update: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = owner_borrow_account.mint == borrow_mint.key() @ LvfError::InvalidAccount,
+    )]
+    pub owner_borrow_account: Box<Account<'info, TokenAccount>>,
+    pub lending_program: Program<'info, Lending>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: Forge protocol PDA - entries are refused while the protocol is inactive, validated in forge_core::load_protocol
+    pub forge_protocol: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
 pub struct ClosePosition<'info> {
-    pub config: Account<'info, LvfConfig>,
+    #[account(seeds = [b"lvf_config"], bump = config.bump)]
+    pub config: Box<Account<'info, LvfConfig>>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner @ LvfError::Unauthorized,
+        seeds = [b"lvf_position", owner.key().as_ref(), position.crucible.as_ref(), &position.nonce.to_le_bytes()],
+        bump = position.bump,
+    )]
+    pub position: Box<Account<'info, LvfPosition>>,
+    /// CHECK: Position authority PDA - lending borrower and vault owner
+    #[account(seeds = [b"lvf_authority", position.key().as_ref()], bump = position.authority_bump)]
+    pub position_authority: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"lvf_collateral", position.key().as_ref()], bump)]
+    pub collateral_vault: Box<Account<'info, TokenAccount>>,
+    #[account(mut, seeds = [b"lvf_repay", position.key().as_ref()], bump)]
+    pub repay_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = owner_ctoken_account.mint == position.p_token_mint @ LvfError::InvalidAccount,
+    )]
+    pub owner_ctoken_account: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = position.pair_market @ LvfError::InvalidAccount)]
+    pub market: Box<Account<'info, Market>>,
+    #[account(mut, address = market.vault @ LvfError::InvalidAccount)]
+    pub market_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [b"borrower", market.key().as_ref(), position_authority.key().as_ref()],
+        bump,
+        seeds::program = lending_program.key(),
+    )]
+    pub borrower_account: Box<Account<'info, BorrowerAccount>>,
+    #[account(
+        seeds = [b"collateral_config", market.key().as_ref()],
+        bump = collateral_config.bump,
+        seeds::program = lending_program.key(),
+    )]
+    pub collateral_config: Box<Account<'info, CollateralConfig>>,
+    #[account(
+        mut,
+        seeds = [b"collateral", market.key().as_ref(), position_authority.key().as_ref()],
+        bump,
+        seeds::program = lending_program.key(),
+    )]
+    pub user_collateral: Box<Account<'info, UserCollateral>>,
+    /// CHECK: Lending collateral vault - validated by the lending program
+    #[account(mut, address = collateral_config.collateral_vault @ LvfError::InvalidAccount)]
+    pub lending_collateral_vault: UncheckedAccount<'info>,
+    /// CHECK: Pyth price update for the collateral - validated by lending::collateral_value
+    pub collateral_price_update: UncheckedAccount<'info>,
+    /// CHECK: Pyth price update for the borrow mint - validated by lending::collateral_value
+    pub base_price_update: UncheckedAccount<'info>,
+    /// CHECK: Position's crucible - collateral source forwarded to the lending program
+    #[account(address = position.crucible @ LvfError::InvalidAccount)]
+    pub crucible: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = owner_borrow_account.mint == market.base_mint @ LvfError::InvalidAccount,
+    )]
+    pub owner_borrow_account: Box<Account<'info, TokenAccount>>,
+    pub lending_program: Program<'info, Lending>,
+    pub token_program: Program<'info, Token>,
+    /// CHECK: Optional Forge protocol PDA - forwarded to lending, which refuses state changes while the protocol is inactive
+    pub forge_protocol: Option<UncheckedAccount<'info>>,
 }
 
 #[derive(Accounts)]
 pub struct LiquidatePosition<'info> {
-    pub config: Account<'info, LvfConfig>,
+    #[account(seeds = [b"lvf_config"], bump = config.bump)]
+    pub config: Box<Account<'info, LvfConfig>>,
+    pub liquidator: Signer<'info>,
+    /// CHECK: Position owner - receives remaining collateral and rent, validated by has_one
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner @ LvfError::Unauthorized,
+        has_one = crucible @ LvfError::InvalidAccount,
+        seeds = [b"lvf_position", owner.key().as_ref(), crucible.key().as_ref(), &position.nonce.to_le_bytes()],
+        bump = position.bump,
+    )]
+    pub position: Box<Account<'info, LvfPosition>>,
+    /// CHECK: Position authority PDA - lending borrower and vault owner
+    #[account(seeds = [b"lvf_authority", position.key().as_ref()], bump = position.authority_bump)]
+    pub position_authority: UncheckedAccount<'info>,
+    /// CHECK: Position's crucible (has_one) - collateral source for lending::collateral_value
+    pub crucible: UncheckedAccount<'info>,
+    #[account(address = position.p_token_mint @ LvfError::InvalidAccount)]
+    pub ctoken_mint: Box<Account<'info, Mint>>,
+    #[account(mut, seeds = [b"lvf_collateral", position.key().as_ref()], bump)]
+    pub collateral_vault: Box<Account<'info, TokenAccount>>,
+    #[account(mut, seeds = [b"lvf_repay", position.key().as_ref()], bump)]
+    pub repay_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = owner_ctoken_account.mint == ctoken_mint.key() @ LvfError::InvalidAccount,
+        constraint = owner_ctoken_account.owner == owner.key() @ LvfError::Unauthorized,
+    )]
+    pub owner_ctoken_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = liquidator_ctoken_account.mint == ctoken_mint.key() @ LvfError::InvalidAccount,
+    )]
+    pub liquidator_ctoken_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = liquidator_borrow_account.mint == market.base_mint @ LvfError::InvalidAccount,
+    )]
+    pub liquidator_borrow_account: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = position.pair_market @ LvfError::InvalidAccount)]
+    pub market: Box<Account<'info, Market>>,
+    #[account(mut, address = market.vault @ LvfError::InvalidAccount)]
+    pub market_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [b"borrower", market.key().as_ref(), position_authority.key().as_ref()],
+        bump,
+        seeds::program = lending_program.key(),
+    )]
+    pub borrower_account: Box<Account<'info, BorrowerAccount>>,
+    #[account(
+        seeds = [b"collateral_config", market.key().as_ref()],
+        bump = collateral_config.bump,
+        seeds::program = lending_program.key(),
+    )]
+    pub collateral_config: Box<Account<'info, CollateralConfig>>,
+    #[account(
+        mut,
+        seeds = [b"collateral", market.key().as_ref(), position_authority.key().as_ref()],
+        bump,
+        seeds::program = lending_program.key(),
+    )]
+    pub user_collateral: Box<Account<'info, UserCollateral>>,
+    /// CHECK: Lending collateral vault - validated by the lending program
+    #[account(mut, address = collateral_config.collateral_vault @ LvfError::InvalidAccount)]
+    pub lending_collateral_vault: UncheckedAccount<'info>,
+    /// CHECK: Pyth price update for the collateral - validated by lending::collateral_value
+    pub collateral_price_update: UncheckedAccount<'info>,
+    /// CHECK: Pyth price update for the borrow mint - validated by lending::collateral_value
+    pub base_price_update: UncheckedAccount<'info>,
+    pub lending_program: Program<'info, Lending>,
+    pub token_program: Program<'info, Token>,
+    /// CHECK: Optional Forge protocol PDA - forwarded to lending, which refuses state changes while the protocol is inactive
+    pub forge_protocol: Option<UncheckedAccount<'info>>,
 }
 
 #[derive(Accounts)]
 pub struct HealthCheck<'info> {
-    pub config: Account<'info, LvfConfig>,
+    #[account(
+        has_one = crucible @ LvfError::InvalidAccount,
+        seeds = [b"lvf_position", position.owner.as_ref(), crucible.key().as_ref(), &position.nonce.to_le_bytes()],
+        bump = position.bump,
+    )]
+    pub position: Box<Account<'info, LvfPosition>>,
+    /// CHECK: Position authority PDA - used to derive the lending borrower account
+    #[account(seeds = [b"lvf_authority", position.key().as_ref()], bump = position.authority_bump)]
+    pub position_authority: UncheckedAccount<'info>,
+    /// CHECK: Position's crucible (has_one) - collateral source for lending::collateral_value
+    pub crucible: UncheckedAccount<'info>,
+    #[account(address = position.pair_market @ LvfError::InvalidAccount)]
+    pub market: Box<Account<'info, Market>>,
+    #[account(
+        seeds = [b"borrower", market.key().as_ref(), position_authority.key().as_ref()],
+        bump,
+        seeds::program = lending::ID,
+    )]
+    pub borrower_account: Box<Account<'info, BorrowerAccount>>,
+    #[account(
+        seeds = [b"collateral", market.key().as_ref(), position_authority.key().as_ref()],
+        bump,
+        seeds::program = lending::ID,
+    )]
+    pub user_collateral: Box<Account<'info, UserCollateral>>,
+    #[account(
+        seeds = [b"collateral_config", market.key().as_ref()],
+        bump = collateral_config.bump,
+        seeds::program = lending::ID,
+    )]
+    pub collateral_config: Box<Account<'info, CollateralConfig>>,
+    /// CHECK: Pyth price update for the collateral - validated by lending::collateral_value
+    pub collateral_price_update: UncheckedAccount<'info>,
+    /// CHECK: Pyth price update for the borrow mint - validated by lending::collateral_value
+    pub base_price_update: UncheckedAccount<'info>,
+}
+
+#[event]
+pub struct LvfPositionOpened {
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub crucible: Pubkey,
+    pub market: Pubkey,
+    pub collateral: u64,
+    pub borrowed: u64,
+    pub leverage_bps: u64,
+}
+
+#[event]
+pub struct LvfPositionClosed {
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub collateral_returned: u64,
+    pub debt_repaid: u64,
+}
+
+#[event]
+pub struct LvfPositionLiquidated {
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub liquidator: Pubkey,
+    pub debt_repaid: u64,
+    pub collateral_seized: u64,
+    pub collateral_returned: u64,
+    pub ltv_bps: u64,
 }
 
 #[error_code]
@@ -129,6 +847,12 @@ pub enum LvfError {
     #[msg("Unauthorized")] Unauthorized,
     #[msg("Unimplemented")] Unimplemented,
     #[msg("Invalid parameters")] InvalidParams,
+    #[msg("LVF is paused")] Paused,
+    #[msg("Invalid leverage")] InvalidLeverage,
+    #[msg("Invalid account")] InvalidAccount,
+    #[msg("Invalid collateral value")] InvalidCollateralValue,
+    #[msg("Position would be unhealthy")] PositionUnhealthy,
+    #[msg("Position is not liquidatable")] PositionNotLiquidatable,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -140,7 +864,9 @@ pub struct InitializeLvfParams {
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct OpenPositionParams {
-    pub leverage_bps: u64,
+    pub leverage_bps: u64, // 100 = 1x, capped by config.max_leverage_bps
+    pub collateral_amount: u64, // cTokens to deposit as collateral
+    pub position_nonce: u64, // Allows multiple positions per owner per crucible
 }
 
 
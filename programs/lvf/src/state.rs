@@ -6,7 +6,7 @@ pub struct LvfConfig {
     pub max_leverage_bps: u64,
     pub liquidation_threshold_bps: u64,
     pub liquidation_bounty_bps: u64,
-    pub paused: bool,
+    pub paused: bool, // Blocks new positions only; closes and liquidations always run
     pub bump: u8,
 }
 
@@ -18,18 +18,19 @@ impl LvfConfig {
 pub struct LvfPosition {
     pub owner: Pubkey,
     pub crucible: Pubkey,
-    pub p_token_mint: Pubkey,
-    pub pair_market: Pubkey,
-    pub lp_pool: Pubkey,
-    pub position_shares: u128,
-    pub borrowed_amount: u128,
-    pub entry_exchange_rate: u128,
-    pub leverage_bps: u64,
+    pub p_token_mint: Pubkey, // Crucible cToken mint posted as collateral
+    pub pair_market: Pubkey, // Lending market the position borrows from
+    pub position_shares: u128, // cTokens held as collateral
+    pub borrowed_amount: u128, // Principal borrowed at open
+    pub entry_exchange_rate: u128, // Crucible exchange rate at open (scaled by 1M)
+    pub leverage_bps: u64, // 100 = 1x, 200 = 2x
+    pub nonce: u64, // Allows multiple positions per owner per crucible
     pub bump: u8,
+    pub authority_bump: u8, // Bump of the position authority PDA (lending borrower)
 }
 
 impl LvfPosition {
-    pub const SIZE: usize = 8 + 32*5 + 16*3 + 8 + 1;
+    pub const SIZE: usize = 8 + 32*4 + 16*3 + 8 + 8 + 1 + 1;
 }
 
 
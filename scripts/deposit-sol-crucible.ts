@@ -143,8 +143,9 @@ async function main() {
   // Check if crucible exists and is not paused
   try {
     const crucibleAccount = await program.account.crucible.fetch(SOL_CRUCIBLE)
-    if (crucibleAccount.paused) {
-      console.error('❌ Crucible is paused')
+    // PAUSE_DEPOSITS = 1 << 0 (forge_core::pause)
+    if (crucibleAccount.pauseFlags & 1) {
+      console.error('❌ Crucible deposits are paused')
       process.exit(1)
     }
     console.log('✅ Crucible is active')
@@ -12,7 +12,7 @@
 
 import * as anchor from '@coral-xyz/anchor'
 import { Connection, Keypair, PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY } from '@solana/web3.js'
-import { TOKEN_PROGRAM_ID } from '@solana/spl-token'
+import { TOKEN_PROGRAM_ID, getAssociatedTokenAddressSync } from '@solana/spl-token'
 import fs from 'fs'
 import path from 'path'
 import { fileURLToPath } from 'url'
@@ -56,16 +56,37 @@ async function main() {
   // Create program instance with proper typing
   const program = new anchor.Program(lendingPoolIdl, provider) as any
   
-  // Derive PDAs
+  // Derive PDAs - pools are keyed by mint; the original USDC pool stays at [b"pool"]
   const [poolPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from('pool'), USDC_MINT.toBuffer()],
+    LENDING_POOL_PROGRAM_ID
+  )
+  const [legacyPoolPDA] = PublicKey.findProgramAddressSync(
     [Buffer.from('pool')],
     LENDING_POOL_PROGRAM_ID
   )
+  // Pools can only be created by the program upgrade authority
+  const [programDataPDA] = PublicKey.findProgramAddressSync(
+    [LENDING_POOL_PROGRAM_ID.toBuffer()],
+    new PublicKey('BPFLoaderUpgradeab1e11111111111111111111111')
+  )
   
   const [poolVaultPDA] = PublicKey.findProgramAddressSync(
     [Buffer.from('vault'), poolPDA.toBuffer()],
     LENDING_POOL_PROGRAM_ID
   )
+  const [insuranceFundPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from('insurance_fund'), poolPDA.toBuffer()],
+    LENDING_POOL_PROGRAM_ID
+  )
+  const [shareMintPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from('share_mint'), poolPDA.toBuffer()],
+    LENDING_POOL_PROGRAM_ID
+  )
+  const [deadShareEscrowPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from('dead_shares'), poolPDA.toBuffer()],
+    LENDING_POOL_PROGRAM_ID
+  )
   
   console.log('📋 Pool PDA:', poolPDA.toString())
   console.log('📋 Pool Vault PDA:', poolVaultPDA.toString())
@@ -96,7 +117,7 @@ async function main() {
           totalBorrowed: poolAccount.totalBorrowed.toString(),
           borrowRate: poolAccount.borrowRate.toString(),
           lenderRate: poolAccount.lenderRate.toString(),
-          paused: poolAccount.paused,
+          pauseFlags: poolAccount.pauseFlags,
         })
         return
       } else {
@@ -116,8 +137,8 @@ async function main() {
     }
   }
   
-  // Initialize pool (initial liquidity = 0, can be funded separately)
-  const initialLiquidity = 0
+  // Initialize pool - the authority funds 1 USDC of initial liquidity, whose shares are locked
+  const initialLiquidity = 1_000_000
   
   console.log('🔨 Initializing pool...')
   const tx = await program.methods
@@ -125,8 +146,14 @@ async function main() {
     .accounts({
       pool: poolPDA,
       usdcMint: USDC_MINT,
+      legacyPool: legacyPoolPDA,
       poolVault: poolVaultPDA,
+      insuranceFund: insuranceFundPDA,
+      shareMint: shareMintPDA,
+      deadShareEscrow: deadShareEscrowPDA,
+      authorityUsdcAccount: getAssociatedTokenAddressSync(USDC_MINT, walletKeypair.publicKey),
       authority: walletKeypair.publicKey,
+      programData: programDataPDA,
       tokenProgram: TOKEN_PROGRAM_ID,
       rent: SYSVAR_RENT_PUBKEY,
       systemProgram: SystemProgram.programId,
@@ -351,7 +351,7 @@ async function main() {
     treasury: crucibleAccount.treasury.toString(),
     oracle: crucibleAccount.oracle?.toString() || 'None',
     feeRate: crucibleAccount.feeRate.toString(),
-    paused: crucibleAccount.paused,
+    pauseFlags: crucibleAccount.pauseFlags,
   })
   
   console.log('\n🎉 SOL Crucible initialization complete!')
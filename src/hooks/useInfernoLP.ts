@@ -7,7 +7,7 @@ import { useWallet } from '../contexts/WalletContext'
 import { usePrice } from '../contexts/PriceContext'
 import { useLending } from './useLending'
 import { getInfernoCruciblesProgram } from '../utils/infernoProgram'
-import { deriveInfernoCruciblePDA, deriveInfernoVaultPDA, deriveInfernoUSDCVaultPDA, deriveInfernoLPPositionPDA, deriveInfernoCrucibleAuthorityPDA, deriveInfernoLPPositionPDALegacy } from '../utils/infernoPdas'
+import { deriveInfernoCruciblePDA, deriveInfernoVaultPDA, deriveInfernoUSDCVaultPDA, deriveInfernoLPPositionPDA, deriveInfernoCrucibleAuthorityPDA, deriveInfernoLPPositionPDALegacy, deriveInfernoLPEscrowPDA } from '../utils/infernoPdas'
 import { SOLANA_TESTNET_CONFIG, SOLANA_TESTNET_PROGRAM_IDS } from '../config/solana-testnet'
 import { getInfernoLPPositions, setInfernoLPPositions, type StoredInfernoLPPosition } from '../utils/localStorage'
 import { getLendingPoolPDA, getBorrowerAccountPDA, getPoolVaultPDA } from '../utils/lendingProgram'
@@ -164,18 +164,24 @@ export function useInfernoLP({ crucibleAddress, baseTokenSymbol, baseAPY }: UseI
               const usdcAmountNum = Number(positionAccount.usdcAmount) / 1e6
               const currentValue = baseAmountNum * solPrice + usdcAmountNum
               
-              // Fetch actual LP token balance from user's wallet
+              // Fetch the position's LP balance - held in its escrow, or in the user's
+              // wallet for positions opened before LP escrow
               let lpTokenAmount = 0
               try {
-                const crucibleAccount = await (program.account as any).infernoCrucible.fetch(positionAccount.crucible)
-                const userLpTokenAccount = await getAssociatedTokenAddress(
-                  crucibleAccount.lpTokenMint,
-                  publicKey
-                )
-                const lpAccountInfo = await getAccount(connection, userLpTokenAccount)
+                const lpAccountInfo = await getAccount(connection, deriveInfernoLPEscrowPDA(positionPDA)[0])
                 lpTokenAmount = Number(lpAccountInfo.amount) / 1e9
               } catch {
-                // LP token account might not exist
+                try {
+                  const crucibleAccount = await (program.account as any).infernoCrucible.fetch(positionAccount.crucible)
+                  const userLpTokenAccount = await getAssociatedTokenAddress(
+                    crucibleAccount.lpTokenMint,
+                    publicKey
+                  )
+                  const lpAccountInfo = await getAccount(connection, userLpTokenAccount)
+                  lpTokenAmount = Number(lpAccountInfo.amount) / 1e9
+                } catch {
+                  // LP token account might not exist
+                }
               }
               
               // Get nonce from account or use loop index
@@ -334,7 +340,7 @@ export function useInfernoLP({ crucibleAddress, baseTokenSymbol, baseAPY }: UseI
 
         const userBaseTokenAccount = await getAssociatedTokenAddress(baseMint, publicKey)
         const userUsdcAccount = await getAssociatedTokenAddress(usdcMint, publicKey)
-        const userLpTokenAccount = await getAssociatedTokenAddress(crucibleAccount.lpTokenMint, publicKey)
+        const [lpEscrowPDA] = deriveInfernoLPEscrowPDA(positionPDA)
 
         const accountCreationInstructions: anchor.web3.TransactionInstruction[] = []
         
@@ -364,20 +370,6 @@ export function useInfernoLP({ crucibleAddress, baseTokenSymbol, baseAPY }: UseI
             )
           )
         }
-        try {
-          await getAccount(connection, userLpTokenAccount)
-        } catch {
-          accountCreationInstructions.push(
-            createAssociatedTokenAccountIdempotentInstruction(
-              publicKey,
-              userLpTokenAccount,
-              publicKey,
-              crucibleAccount.lpTokenMint,
-              TOKEN_PROGRAM_ID,
-              ASSOCIATED_TOKEN_PROGRAM_ID
-            )
-          )
-        }
 
         const totalBaseLamports = Math.floor(baseAmount * 1e9) + swapOutLamports
         
@@ -415,8 +407,8 @@ export function useInfernoLP({ crucibleAddress, baseTokenSymbol, baseAPY }: UseI
             crucibleBaseVault: baseVaultPDA,
             crucibleUsdcVault: usdcVaultPDA,
             lpTokenMint: crucibleAccount.lpTokenMint,
-            userLpTokenAccount,
             position: positionPDA,
+            lpEscrow: lpEscrowPDA,
             crucibleAuthority: crucibleAuthorityPDA,
             oracle: crucibleAccount.oracle || null,
             treasuryBase: crucibleAccount.treasuryBase,
@@ -602,6 +594,8 @@ export function useInfernoLP({ crucibleAddress, baseTokenSymbol, baseAPY }: UseI
               position: positionPDA,
               userBaseTokenAccount,
               userUsdcAccount,
+              lpEscrow: deriveInfernoLPEscrowPDA(positionPDA)[0],
+              // Only used by positions opened before LP escrow
               userLpTokenAccount: await getAssociatedTokenAddress(crucibleAccount.lpTokenMint, publicKey),
               lpTokenMint: crucibleAccount.lpTokenMint,
               crucibleBaseVault: baseVaultPDA,
@@ -476,6 +476,7 @@ export function useLVFPosition({ crucibleAddress, baseTokenSymbol }: UseLVFPosit
             crucibleAuthority: crucibleAuthorityPDA,
             oracle: oracleAccount,
             lendingProgram: new PublicKey(SOLANA_TESTNET_PROGRAM_IDS.LENDING_POOL),
+            quoteMint: usdcMint,
             lendingMarket: lendingPoolPDA,
             poolAuthority: poolAuthorityPDA,
             borrowerAccount: borrowerAccountPDA,
@@ -655,7 +655,8 @@ export function useLending() {
 
     try {
       const tx = await (program as any).methods
-        .repayUsdc(new anchor.BN(Math.floor(amount * 1e6))) // Convert to USDC decimals
+        // Default position = the wallet's legacy (non-position) borrower account
+        .repayUsdc(new anchor.BN(Math.floor(amount * 1e6)), PublicKey.default) // Convert to USDC decimals
         .accounts({
           pool: poolPDA,
           borrower: publicKey,
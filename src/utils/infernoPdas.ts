@@ -45,6 +45,14 @@ export function deriveInfernoLPPositionPDALegacy(user: PublicKey, baseMint: Publ
   )
 }
 
+// Escrow token account holding a position's LP tokens
+export function deriveInfernoLPEscrowPDA(position: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('lp_escrow'), position.toBuffer()],
+    FORGE_CRUCIBLES_INFERNO_PROGRAM_ID
+  )
+}
+
 export function deriveInfernoCrucibleAuthorityPDA(baseMint: PublicKey): [PublicKey, number] {
   return deriveInfernoCruciblePDA(baseMint)
 }
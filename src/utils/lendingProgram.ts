@@ -278,7 +278,7 @@ export async function getMarketState(
       lenderRate: typeof poolAccount.lenderRate === 'object' && poolAccount.lenderRate.toNumber 
         ? poolAccount.lenderRate.toNumber() 
         : Number(poolAccount.lenderRate || 0),
-      paused: poolAccount.paused || false,
+      paused: (poolAccount.pauseFlags || 0) !== 0,
       bump: poolAccount.bump || 0,
     }
   } catch (error) {